@@ -166,6 +166,10 @@ pub mod signers {
     #[doc(inline)]
     pub use atoms_signer_gcp as gcp;
 
+    #[cfg(feature = "signer-node")]
+    #[doc(inline)]
+    pub use atoms_signer_node as node;
+
     #[cfg(feature = "signer-ledger")]
     #[doc(inline)]
     pub use atoms_signer_ledger as ledger;
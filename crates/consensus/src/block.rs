@@ -0,0 +1,348 @@
+use crate::{
+    proofs::{calculate_ommers_root, calculate_transaction_root},
+    recover_signers, Header, RecoveryError, Sealable, TxEnvelope,
+};
+use alloy_rlp::{length_of_length, BufMut, Decodable, Encodable};
+use base_primitives::{IcanAddress, B256};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Core block: a header together with the transactions and ommers it commits to.
+///
+/// The RLP encoding matches the one used by go-core on the wire: a list of the header, the list
+/// of signed transactions, in their network encoding, and the list of ommer headers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Block {
+    /// The block header.
+    pub header: Header,
+    /// The block body.
+    pub body: BlockBody,
+}
+
+impl Block {
+    /// Creates a new block from its header and body.
+    pub const fn new(header: Header, body: BlockBody) -> Self {
+        Self { header, body }
+    }
+
+    /// Heavy function that will calculate the hash of the block header.
+    ///
+    /// Use [`Sealable::seal_slow`] if you need the hash to be persistent.
+    pub fn hash_slow(&self) -> B256 {
+        self.header.hash_slow()
+    }
+
+    /// Splits the block into its header and body.
+    #[allow(clippy::missing_const_for_fn)] // false positive
+    pub fn into_parts(self) -> (Header, BlockBody) {
+        (self.header, self.body)
+    }
+
+    fn payload_len(&self) -> usize {
+        self.header.length() + self.body.fields_len()
+    }
+}
+
+impl Sealable for Block {
+    fn hash(&self) -> B256 {
+        self.hash_slow()
+    }
+}
+
+impl Encodable for Block {
+    fn encode(&self, out: &mut dyn BufMut) {
+        alloy_rlp::Header { list: true, payload_length: self.payload_len() }.encode(out);
+        self.header.encode(out);
+        self.body.encode_fields(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_len();
+        payload_length + length_of_length(payload_length)
+    }
+}
+
+impl Decodable for Block {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let rlp_head = alloy_rlp::Header::decode(buf)?;
+        if !rlp_head.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        let started_len = buf.len();
+
+        let header = Header::decode(buf)?;
+        let body = BlockBody::decode_fields(buf)?;
+
+        let consumed = started_len - buf.len();
+        if consumed != rlp_head.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: rlp_head.payload_length,
+                got: consumed,
+            });
+        }
+        Ok(Self { header, body })
+    }
+}
+
+/// The body of a [`Block`]: its signed transactions and ommer headers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockBody {
+    /// Transactions in the block.
+    pub transactions: Vec<TxEnvelope>,
+    /// Ommer headers of the block.
+    pub ommers: Vec<Header>,
+}
+
+impl BlockBody {
+    /// Calculates the transactions root of the body, to be compared against
+    /// [`Header::transactions_root`].
+    pub fn calculate_transactions_root(&self) -> B256 {
+        calculate_transaction_root(&self.transactions)
+    }
+
+    /// Calculates the ommers hash of the body, to be compared against [`Header::ommers_hash`].
+    pub fn calculate_ommers_root(&self) -> B256 {
+        calculate_ommers_root(&self.ommers)
+    }
+
+    /// Recovers the senders of the transactions in the body, in order.
+    ///
+    /// See [`recover_signers`].
+    pub fn recover_senders(&self, network_id: u64) -> Result<Vec<IcanAddress>, Vec<RecoveryError>> {
+        recover_signers(&self.transactions, network_id)
+    }
+
+    /// Creates a block by attaching the given header to this body.
+    pub const fn into_block(self, header: Header) -> Block {
+        Block { header, body: self }
+    }
+
+    fn transactions_payload_len(&self) -> usize {
+        self.transactions.iter().map(Encodable::length).sum()
+    }
+
+    /// Length of the encoded transactions and ommers lists, without a list header.
+    fn fields_len(&self) -> usize {
+        let transactions_len = self.transactions_payload_len();
+        transactions_len + length_of_length(transactions_len) + self.ommers.length()
+    }
+
+    /// Encodes the transactions and ommers lists, without a list header.
+    fn encode_fields(&self, out: &mut dyn BufMut) {
+        alloy_rlp::Header { list: true, payload_length: self.transactions_payload_len() }
+            .encode(out);
+        for tx in &self.transactions {
+            tx.encode(out);
+        }
+        self.ommers.encode(out);
+    }
+
+    /// Decodes the transactions and ommers lists, without a list header.
+    fn decode_fields(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let transactions_head = alloy_rlp::Header::decode(buf)?;
+        if !transactions_head.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        if buf.len() < transactions_head.payload_length {
+            return Err(alloy_rlp::Error::InputTooShort);
+        }
+        let (mut transactions_buf, rest) = buf.split_at(transactions_head.payload_length);
+        let mut transactions = Vec::new();
+        while !transactions_buf.is_empty() {
+            transactions.push(TxEnvelope::decode(&mut transactions_buf)?);
+        }
+        *buf = rest;
+
+        let ommers = Decodable::decode(buf)?;
+        Ok(Self { transactions, ommers })
+    }
+}
+
+impl Encodable for BlockBody {
+    fn encode(&self, out: &mut dyn BufMut) {
+        alloy_rlp::Header { list: true, payload_length: self.fields_len() }.encode(out);
+        self.encode_fields(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.fields_len();
+        payload_length + length_of_length(payload_length)
+    }
+}
+
+impl Decodable for BlockBody {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let rlp_head = alloy_rlp::Header::decode(buf)?;
+        if !rlp_head.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        let started_len = buf.len();
+
+        let this = Self::decode_fields(buf)?;
+
+        let consumed = started_len - buf.len();
+        if consumed != rlp_head.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: rlp_head.payload_length,
+                got: consumed,
+            });
+        }
+        Ok(this)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        proofs::ordered_trie_root_with_encoder, SignableTransaction, Signed, TxEip2930, TxLegacy,
+        EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH,
+    };
+    use atoms_eips::eip2930::{AccessList, AccessListItem};
+    use base_primitives::{bytes, cAddress, Signature, TxKind, U256};
+    use core::str::FromStr;
+    use libgoldilocks::{PrehashSigner, SigningKey};
+
+    const KEY: &str = "7d6231471b5dbb6204fe5129617082792ae468d01a3f3623184c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    fn signed_tx(nonce: u64) -> Signed<TxLegacy> {
+        let tx = TxLegacy {
+            network_id: 1,
+            nonce,
+            energy_price: 1_000_000_000,
+            energy_limit: 21000,
+            to: TxKind::Call(cAddress!("0000d8dA6BF26964aF9D7eEd9e03E53415D37aA96045")),
+            value: U256::from(100),
+            input: bytes!("c0ffee"),
+        };
+        let signature = Signature::from_str(&format!("0x{}", "ab".repeat(171))).unwrap();
+        tx.into_signed(signature)
+    }
+
+    fn block() -> Block {
+        let ommer = Header {
+            number: 9,
+            beneficiary: cAddress!("0000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+            ..Default::default()
+        };
+        let body = BlockBody {
+            transactions: vec![signed_tx(0).into(), signed_tx(1).into()],
+            ommers: vec![ommer],
+        };
+        let header = Header {
+            number: 10,
+            beneficiary: cAddress!("0000bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"),
+            gas_limit: 8_000_000,
+            gas_used: 42000,
+            timestamp: 1_700_000_000,
+            extra_data: bytes!("deadbeef"),
+            transactions_root: body.calculate_transactions_root(),
+            ommers_hash: body.calculate_ommers_root(),
+            ..Default::default()
+        };
+        body.into_block(header)
+    }
+
+    #[test]
+    fn block_rlp_roundtrip() {
+        let block = block();
+
+        let mut encoded = Vec::new();
+        block.encode(&mut encoded);
+        assert_eq!(block.length(), encoded.len());
+
+        let decoded = Block::decode(&mut encoded.as_slice()).unwrap();
+        assert_eq!(decoded, block);
+        for (decoded, tx) in decoded.body.transactions.iter().zip(&block.body.transactions) {
+            assert_eq!(decoded.tx_hash(), tx.tx_hash());
+        }
+    }
+
+    #[test]
+    fn body_rlp_roundtrip() {
+        let body = block().body;
+
+        let mut encoded = Vec::new();
+        body.encode(&mut encoded);
+        assert_eq!(body.length(), encoded.len());
+        assert_eq!(BlockBody::decode(&mut encoded.as_slice()).unwrap(), body);
+    }
+
+    #[test]
+    fn empty_body_roots() {
+        let body = BlockBody::default();
+        assert_eq!(body.calculate_transactions_root(), EMPTY_ROOT_HASH);
+        assert_eq!(body.calculate_ommers_root(), EMPTY_OMMER_ROOT_HASH);
+    }
+
+    #[test]
+    fn seal_block() {
+        let block = block();
+        let hash = block.header.hash_slow();
+        let sealed = block.seal_slow();
+        assert_eq!(sealed.hash(), hash);
+        assert_eq!(sealed.body.transactions.len(), 2);
+    }
+
+    #[test]
+    fn mixed_body() {
+        let key = SigningKey::from_str(KEY);
+        let signer = IcanAddress::from_private_key(&key, 1);
+        let to = TxKind::Call(cAddress!("0000d8dA6BF26964aF9D7eEd9e03E53415D37aA96045"));
+
+        let legacy = TxLegacy {
+            network_id: 1,
+            nonce: 0,
+            energy_price: 1_000_000_000,
+            energy_limit: 21000,
+            to,
+            value: U256::from(100),
+            input: Default::default(),
+        };
+        let legacy_signature = key.sign_prehash(legacy.signature_hash().as_ref()).unwrap();
+        let eip2930 = TxEip2930 {
+            network_id: 1,
+            nonce: 1,
+            energy_price: 1_000_000_000,
+            energy_limit: 50_000,
+            to,
+            value: U256::from(100),
+            access_list: AccessList(vec![AccessListItem {
+                address: cAddress!("0000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+                storage_keys: vec![B256::ZERO],
+            }]),
+            input: bytes!("c0ffee"),
+        };
+        let eip2930_signature = key.sign_prehash(eip2930.signature_hash().as_ref()).unwrap();
+
+        let body = BlockBody {
+            transactions: vec![
+                legacy.clone().into_signed(legacy_signature).into(),
+                eip2930.clone().into_signed(eip2930_signature).into(),
+            ],
+            ommers: Vec::new(),
+        };
+
+        let mut encoded = Vec::new();
+        body.encode(&mut encoded);
+        assert_eq!(body.length(), encoded.len());
+        let decoded = BlockBody::decode(&mut encoded.as_slice()).unwrap();
+        assert_eq!(decoded, body);
+        assert!(decoded.transactions[0].as_legacy().is_some());
+        assert!(decoded.transactions[1].as_eip2930().is_some());
+
+        // the legacy leaf is the bare RLP list, the typed one is prefixed with its type byte
+        let mut legacy_leaf = Vec::new();
+        legacy.encode_with_signature_fields(&legacy_signature, &mut legacy_leaf);
+        let mut eip2930_leaf = vec![1];
+        eip2930.encode_with_signature_fields(&eip2930_signature, &mut eip2930_leaf);
+        let leaves = [legacy_leaf, eip2930_leaf];
+        assert_eq!(
+            body.calculate_transactions_root(),
+            ordered_trie_root_with_encoder(&leaves, |leaf, buf| buf.extend_from_slice(leaf))
+        );
+
+        assert_eq!(body.recover_senders(1).unwrap(), vec![signer; 2]);
+    }
+}
@@ -0,0 +1,202 @@
+//! Clique proof-of-authority helpers: seal hash, seal signer recovery and checkpoint signers.
+//!
+//! The extra data of a Clique header is laid out as:
+//! - [`CLIQUE_EXTRA_VANITY`] bytes of signer vanity,
+//! - on checkpoint blocks only, the list of authorized signers as concatenated addresses,
+//! - [`CLIQUE_EXTRA_SEAL`] bytes of seal, the ed448 signature of the [`seal_hash`] followed by
+//!   the public key of the signer.
+
+use crate::{
+    constants::{CLIQUE_DEFAULT_EPOCH, CLIQUE_EXTRA_SEAL, CLIQUE_EXTRA_VANITY},
+    Header,
+};
+use atoms_genesis::CliqueConfig;
+use base_primitives::{IcanAddress, Signature, SignatureError, B256};
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Error returned when the Clique fields of a header can't be read.
+#[derive(Debug)]
+pub enum CliqueError {
+    /// The extra data is missing the signer vanity.
+    MissingVanity,
+    /// The extra data is missing the signer seal.
+    MissingSeal,
+    /// The checkpoint signer list is not a whole number of addresses.
+    InvalidCheckpointSigners,
+    /// The seal is not a valid signature of the seal hash.
+    Signature(SignatureError),
+}
+
+impl fmt::Display for CliqueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingVanity => f.write_str("extra data is missing the signer vanity"),
+            Self::MissingSeal => f.write_str("extra data is missing the signer seal"),
+            Self::InvalidCheckpointSigners => {
+                f.write_str("invalid signer list on checkpoint block")
+            }
+            Self::Signature(err) => write!(f, "invalid seal: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CliqueError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Signature(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<SignatureError> for CliqueError {
+    fn from(err: SignatureError) -> Self {
+        Self::Signature(err)
+    }
+}
+
+/// Returns the epoch length of the configuration, falling back to [`CLIQUE_DEFAULT_EPOCH`] if it
+/// is unset or zero.
+pub fn epoch(config: &CliqueConfig) -> u64 {
+    config.epoch.filter(|epoch| *epoch != 0).unwrap_or(CLIQUE_DEFAULT_EPOCH)
+}
+
+/// Returns whether the block with the given number is a checkpoint, i.e. it resets the pending
+/// votes and lists the authorized signers in its extra data.
+pub fn is_checkpoint(number: u64, config: &CliqueConfig) -> bool {
+    number % epoch(config) == 0
+}
+
+/// Calculates the hash signed by the sealer of the header: the hash of the header with the seal
+/// stripped from its extra data.
+pub fn seal_hash(header: &Header) -> Result<B256, CliqueError> {
+    let len =
+        header.extra_data.len().checked_sub(CLIQUE_EXTRA_SEAL).ok_or(CliqueError::MissingSeal)?;
+    let mut header = header.clone();
+    header.extra_data = header.extra_data.slice(..len);
+    Ok(header.hash_slow())
+}
+
+/// Returns the seal of the header, stored at the end of its extra data.
+pub fn seal(header: &Header) -> Result<Signature, CliqueError> {
+    let len =
+        header.extra_data.len().checked_sub(CLIQUE_EXTRA_SEAL).ok_or(CliqueError::MissingSeal)?;
+    Ok(Signature::try_from(&header.extra_data[len..])?)
+}
+
+/// Recovers the address of the signer that sealed the header.
+pub fn recover_signer(header: &Header, network_id: u64) -> Result<IcanAddress, CliqueError> {
+    let seal_hash = seal_hash(header)?;
+    Ok(seal(header)?.recover_address_from_prehash(&seal_hash, network_id)?)
+}
+
+/// Parses the list of authorized signers stored in the extra data of the header.
+///
+/// Only checkpoint blocks, see [`is_checkpoint`], carry the signer list; the list is empty on
+/// other blocks.
+pub fn checkpoint_signers(header: &Header) -> Result<Vec<IcanAddress>, CliqueError> {
+    let extra_data = &header.extra_data;
+    if extra_data.len() < CLIQUE_EXTRA_VANITY {
+        return Err(CliqueError::MissingVanity);
+    }
+    if extra_data.len() < CLIQUE_EXTRA_VANITY + CLIQUE_EXTRA_SEAL {
+        return Err(CliqueError::MissingSeal);
+    }
+
+    let signers = &extra_data[CLIQUE_EXTRA_VANITY..extra_data.len() - CLIQUE_EXTRA_SEAL];
+    if signers.len() % IcanAddress::len_bytes() != 0 {
+        return Err(CliqueError::InvalidCheckpointSigners);
+    }
+    Ok(signers.chunks_exact(IcanAddress::len_bytes()).map(IcanAddress::from_slice).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base_primitives::cAddress;
+    use core::str::FromStr;
+    use libgoldilocks::{PrehashSigner, SigningKey};
+
+    const KEY: &str = "7d6231471b5dbb6204fe5129617082792ae468d01a3f3623184c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    fn sealed_header(key: &SigningKey, signers: &[IcanAddress]) -> Header {
+        let mut extra_data = vec![0xaa; CLIQUE_EXTRA_VANITY];
+        for signer in signers {
+            extra_data.extend_from_slice(signer.as_slice());
+        }
+        extra_data.extend_from_slice(&[0; CLIQUE_EXTRA_SEAL]);
+        let mut header = Header {
+            number: 16,
+            gas_limit: 8_000_000,
+            timestamp: 1_700_000_000,
+            extra_data: extra_data.into(),
+            ..Default::default()
+        };
+
+        let signature = key.sign_prehash(seal_hash(&header).unwrap().as_ref()).unwrap();
+        let mut extra_data = header.extra_data.to_vec();
+        let len = extra_data.len();
+        extra_data[len - CLIQUE_EXTRA_SEAL..].copy_from_slice(&signature.as_bytes());
+        header.extra_data = extra_data.into();
+        header
+    }
+
+    #[test]
+    fn recover_seal_signer() {
+        let key = SigningKey::from_str(KEY);
+        let signer = IcanAddress::from_private_key(&key, 1);
+        let header = sealed_header(&key, &[]);
+
+        assert_eq!(recover_signer(&header, 1).unwrap(), signer);
+        assert!(checkpoint_signers(&header).unwrap().is_empty());
+
+        // the seal does not cover itself
+        let mut unsealed = header.clone();
+        let len = unsealed.extra_data.len();
+        unsealed.extra_data = unsealed.extra_data.slice(..len - CLIQUE_EXTRA_SEAL);
+        assert_eq!(seal_hash(&header).unwrap(), unsealed.hash_slow());
+
+        // tampering with the header changes the recovered signer
+        let tampered = Header { number: 17, ..header };
+        assert_ne!(recover_signer(&tampered, 1).ok(), Some(signer));
+    }
+
+    #[test]
+    fn parse_checkpoint_signers() {
+        let key = SigningKey::from_str(KEY);
+        let signers = [
+            cAddress!("0000d8dA6BF26964aF9D7eEd9e03E53415D37aA96045"),
+            cAddress!("0000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+        ];
+        let header = sealed_header(&key, &signers);
+
+        assert_eq!(checkpoint_signers(&header).unwrap(), signers);
+        assert_eq!(recover_signer(&header, 1).unwrap(), IcanAddress::from_private_key(&key, 1));
+
+        let header = Header { extra_data: vec![0; CLIQUE_EXTRA_VANITY + 1].into(), ..header };
+        assert!(matches!(checkpoint_signers(&header), Err(CliqueError::MissingSeal)));
+        assert!(matches!(recover_signer(&header, 1), Err(CliqueError::MissingSeal)));
+
+        let header = Header {
+            extra_data: vec![0; CLIQUE_EXTRA_VANITY + 1 + CLIQUE_EXTRA_SEAL].into(),
+            ..header
+        };
+        assert!(matches!(checkpoint_signers(&header), Err(CliqueError::InvalidCheckpointSigners)));
+    }
+
+    #[test]
+    fn checkpoints() {
+        let config = CliqueConfig { period: Some(0), epoch: Some(8) };
+        assert!(is_checkpoint(0, &config));
+        assert!(!is_checkpoint(7, &config));
+        assert!(is_checkpoint(16, &config));
+
+        let config = CliqueConfig { period: None, epoch: None };
+        assert_eq!(epoch(&config), CLIQUE_DEFAULT_EPOCH);
+        assert!(!is_checkpoint(8, &config));
+    }
+}
@@ -7,6 +7,49 @@ pub const SELECTOR_LEN: usize = 4;
 /// Maximum extra data size in a block after genesis
 pub const MAXIMUM_EXTRA_DATA_SIZE: usize = 32;
 
+/// Minimum energy limit allowed for a block.
+pub const MINIMUM_ENERGY_LIMIT: u128 = 5000;
+
+/// Maximum energy limit allowed for a block, `2^63 - 1`.
+pub const MAXIMUM_ENERGY_LIMIT: u128 = 0x7fffffffffffffff;
+
+/// Bound divisor of the energy limit, used to bound how much it can change between blocks.
+pub const ENERGY_LIMIT_BOUND_DIVISOR: u128 = 1024;
+
+/// Energy charged for every transaction, before execution.
+pub const TX_ENERGY: u128 = 21000;
+
+/// Energy charged for every contract creation transaction, before execution.
+pub const TX_ENERGY_CONTRACT_CREATION: u128 = 53000;
+
+/// Energy charged per zero byte of transaction input.
+pub const TX_DATA_ZERO_ENERGY: u128 = 4;
+
+/// Energy charged per non-zero byte of transaction input.
+pub const TX_DATA_NON_ZERO_ENERGY: u128 = 16;
+
+/// Energy charged per address in the access list of a transaction.
+pub const TX_ACCESS_LIST_ADDRESS_ENERGY: u128 = 2400;
+
+/// Energy charged per storage key in the access list of a transaction.
+pub const TX_ACCESS_LIST_STORAGE_KEY_ENERGY: u128 = 1900;
+
+/// Default number of blocks after which to checkpoint and reset the pending votes in Clique.
+pub const CLIQUE_DEFAULT_EPOCH: u64 = 30000;
+
+/// Number of extra-data prefix bytes reserved for signer vanity in Clique.
+pub const CLIQUE_EXTRA_VANITY: usize = 32;
+
+/// Number of extra-data suffix bytes reserved for the signer seal in Clique: an ed448 signature
+/// followed by the signer public key.
+pub const CLIQUE_EXTRA_SEAL: usize = 171;
+
+/// Block difficulty of a Clique block signed in turn.
+pub const CLIQUE_DIFF_IN_TURN: u64 = 2;
+
+/// Block difficulty of a Clique block signed out of turn.
+pub const CLIQUE_DIFF_NO_TURN: u64 = 1;
+
 /// Multiplier for converting gwei to wei.
 pub const GWEI_TO_WEI: u64 = 1_000_000_000;
 
@@ -53,11 +96,11 @@ pub const KECCAK_EMPTY: B256 =
 
 /// Ommer root of empty list.
 pub const EMPTY_OMMER_ROOT_HASH: B256 =
-    b256!("1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347");
+    b256!("f18f47848fb293468f641c33863dca9e5278fa8e9690f77f7dc96e954ef9221b");
 
 /// Root hash of an empty trie.
 pub const EMPTY_ROOT_HASH: B256 =
-    b256!("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421");
+    b256!("bc2071a4de846f285702447f2589dd163678e0972a8a1b0d28b04ed5c094547f");
 
 /// Transactions root of empty receipts set.
 pub const EMPTY_RECEIPTS: B256 = EMPTY_ROOT_HASH;
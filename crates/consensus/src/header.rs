@@ -6,7 +6,7 @@ use atoms_eips::{
     eip1559::{calc_next_block_base_fee, BaseFeeParams},
     eip4844::{calc_blob_gasprice, calc_excess_blob_gas},
 };
-use base_primitives::{b256, sha3, Address, BlockNumber, Bloom, Bytes, B256, B64, U256};
+use base_primitives::{b256, sha3, BlockNumber, Bloom, Bytes, IcanAddress, B256, B64, U256};
 use core::mem;
 
 #[cfg(not(feature = "std"))]
@@ -14,11 +14,11 @@ use alloc::vec::Vec;
 
 /// Ommer root of empty list.
 pub const EMPTY_OMMER_ROOT_HASH: B256 =
-    b256!("1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347");
+    b256!("f18f47848fb293468f641c33863dca9e5278fa8e9690f77f7dc96e954ef9221b");
 
 /// Root hash of an empty trie.
 pub const EMPTY_ROOT_HASH: B256 =
-    b256!("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421");
+    b256!("bc2071a4de846f285702447f2589dd163678e0972a8a1b0d28b04ed5c094547f");
 
 /// Core Block header
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -28,9 +28,9 @@ pub struct Header {
     pub parent_hash: B256,
     /// The Keccak 256-bit hash of the ommers list portion of this block; formally Ho.
     pub ommers_hash: B256,
-    /// The 160-bit address to which all fees collected from the successful mining of this block
-    /// be transferred; formally Hc.
-    pub beneficiary: Address,
+    /// The 176-bit ICAN address to which all fees collected from the successful mining of this
+    /// block be transferred; formally Hc.
+    pub beneficiary: IcanAddress,
     /// The Keccak 256-bit hash of the root node of the state trie, after all transactions are
     /// executed and finalisations applied; formally Hr.
     pub state_root: B256,
@@ -222,7 +222,7 @@ impl Header {
     pub fn size(&self) -> usize {
         mem::size_of::<B256>() + // parent hash
         mem::size_of::<B256>() + // ommers hash
-        mem::size_of::<Address>() + // beneficiary
+        mem::size_of::<IcanAddress>() + // beneficiary
         mem::size_of::<B256>() + // state root
         mem::size_of::<B256>() + // transactions root
         mem::size_of::<B256>() + // receipts root
@@ -481,7 +481,7 @@ impl Decodable for Header {
         //    post-London, so this is technically not valid. However, a tool like proptest would
         //    generate a block like this.
         if started_len - buf.len() < rlp_head.payload_length {
-            this.parent_beacon_block_root = Some(B256::decode(buf)?);
+            this.requests_root = Some(B256::decode(buf)?);
         }
 
         let consumed = started_len - buf.len();
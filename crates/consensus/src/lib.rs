@@ -19,8 +19,17 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
+pub mod clique;
+
 pub mod constants;
 
+pub mod proofs;
+
+pub mod validation;
+
+mod block;
+pub use block::{Block, BlockBody};
+
 mod header;
 pub use header::{Header, EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH};
 
@@ -28,7 +37,10 @@ mod receipt;
 pub use receipt::{AnyReceiptEnvelope, Receipt, ReceiptWithBloom, TxReceipt};
 
 mod transaction;
-pub use transaction::{SignableTransaction, Transaction, TxLegacy, TypedTransaction};
+pub use transaction::{
+    calculate_intrinsic_energy, SignableTransaction, Transaction, TxEip2930, TxEnvelope, TxLegacy,
+    TxType, TypedTransaction,
+};
 
 #[cfg(feature = "kzg")]
 pub use transaction::BlobTransactionValidationError;
@@ -41,3 +53,8 @@ pub use sealed::{Sealable, Sealed};
 
 mod signed;
 pub use signed::Signed;
+
+mod recovered;
+pub use recovered::{
+    recover_signers, recover_transactions, Recovered, RecoveryError, SignerRecoverable,
+};
@@ -0,0 +1,239 @@
+//! Merkle Patricia trie root computation for the block transactions and receipts.
+
+use crate::{Header, ReceiptWithBloom, TxEnvelope, EMPTY_ROOT_HASH};
+use alloy_rlp::{Encodable, EMPTY_STRING_CODE};
+use atoms_eips::eip2718::Encodable2718;
+use base_primitives::{sha3, B256};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Nodes whose RLP encoding is shorter than this are inlined into their parent instead of being
+/// referenced by hash.
+const INLINE_NODE_LIMIT: usize = 32;
+
+/// Calculates the root hash of an ordered trie built from the RLP encodings of `items`.
+///
+/// Each item is keyed by the RLP encoding of its index in the slice.
+pub fn ordered_trie_root<T: Encodable>(items: &[T]) -> B256 {
+    ordered_trie_root_with_encoder(items, |item, buf| item.encode(buf))
+}
+
+/// Calculates the root hash of an ordered trie, using `encode` to produce the value stored for
+/// each item.
+///
+/// Each item is keyed by the RLP encoding of its index in the slice.
+pub fn ordered_trie_root_with_encoder<T, F>(items: &[T], mut encode: F) -> B256
+where
+    F: FnMut(&T, &mut Vec<u8>),
+{
+    if items.is_empty() {
+        return EMPTY_ROOT_HASH;
+    }
+
+    let mut entries = items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let mut value = Vec::new();
+            encode(item, &mut value);
+            (to_nibbles(&alloy_rlp::encode(index)), value)
+        })
+        .collect::<Vec<_>>();
+    entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+    sha3(encode_node(&entries, 0))
+}
+
+/// Calculates the transactions root of a block from its signed transactions.
+///
+/// The trie stores the [EIP-2718] encoding of each transaction: the bare RLP list of the legacy
+/// ones, and the type byte followed by the RLP list of the typed ones.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+pub fn calculate_transaction_root(transactions: &[TxEnvelope]) -> B256 {
+    ordered_trie_root_with_encoder(transactions, |tx, buf| tx.encode_2718(buf))
+}
+
+/// Calculates the receipts root of a block from its receipts.
+pub fn calculate_receipt_root<T: Encodable>(receipts: &[ReceiptWithBloom<T>]) -> B256 {
+    ordered_trie_root(receipts)
+}
+
+/// Calculates the ommers hash of a block from its ommer headers.
+pub fn calculate_ommers_root(ommers: &[Header]) -> B256 {
+    sha3(alloy_rlp::encode(ommers))
+}
+
+/// Splits each byte of `bytes` into two nibbles, high nibble first.
+fn to_nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]).collect()
+}
+
+/// Compacts a nibble path using the hex-prefix encoding, flagging whether it terminates in a
+/// leaf.
+fn encode_path(nibbles: &[u8], leaf: bool) -> Vec<u8> {
+    let flag = if leaf { 0x20 } else { 0x00 };
+    let mut out = Vec::with_capacity(nibbles.len() / 2 + 1);
+    let rest = if nibbles.len() % 2 == 1 {
+        out.push(flag | 0x10 | nibbles[0]);
+        &nibbles[1..]
+    } else {
+        out.push(flag);
+        nibbles
+    };
+    out.extend(rest.chunks_exact(2).map(|pair| (pair[0] << 4) | pair[1]));
+    out
+}
+
+/// Returns how a node is embedded in its parent: inline if short, by hash otherwise.
+fn node_reference(node: Vec<u8>) -> Vec<u8> {
+    if node.len() < INLINE_NODE_LIMIT {
+        return node;
+    }
+    let mut out = Vec::with_capacity(33);
+    sha3(&node).encode(&mut out);
+    out
+}
+
+/// RLP encodes the node spanning `entries`, whose keys all share their first `depth` nibbles.
+///
+/// `entries` must be sorted by key and contain no duplicate keys.
+fn encode_node(entries: &[(Vec<u8>, Vec<u8>)], depth: usize) -> Vec<u8> {
+    let mut out = Vec::new();
+
+    if let [(key, value)] = entries {
+        let path = encode_path(&key[depth..], true);
+        let payload_length = path.as_slice().length() + value.as_slice().length();
+        alloy_rlp::Header { list: true, payload_length }.encode(&mut out);
+        path.as_slice().encode(&mut out);
+        value.as_slice().encode(&mut out);
+        return out;
+    }
+
+    // the keys are sorted, so the prefix shared by the first and last key is shared by all
+    let first = &entries[0].0[depth..];
+    let last = &entries[entries.len() - 1].0[depth..];
+    let shared = first.iter().zip(last).take_while(|(a, b)| a == b).count();
+    if shared > 0 {
+        let path = encode_path(&first[..shared], false);
+        let child = node_reference(encode_node(entries, depth + shared));
+        let payload_length = path.as_slice().length() + child.len();
+        alloy_rlp::Header { list: true, payload_length }.encode(&mut out);
+        path.as_slice().encode(&mut out);
+        out.extend_from_slice(&child);
+        return out;
+    }
+
+    let mut rest = entries;
+    let mut value = None;
+    if rest[0].0.len() == depth {
+        value = Some(rest[0].1.as_slice());
+        rest = &rest[1..];
+    }
+
+    let mut payload = Vec::new();
+    for nibble in 0..16 {
+        let end = rest.iter().position(|(key, _)| key[depth] != nibble).unwrap_or(rest.len());
+        if end == 0 {
+            payload.push(EMPTY_STRING_CODE);
+        } else {
+            payload.extend(node_reference(encode_node(&rest[..end], depth + 1)));
+            rest = &rest[end..];
+        }
+    }
+    match value {
+        Some(value) => value.encode(&mut payload),
+        None => payload.push(EMPTY_STRING_CODE),
+    }
+
+    alloy_rlp::Header { list: true, payload_length: payload.len() }.encode(&mut out);
+    out.extend(payload);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Receipt;
+    use base_primitives::{b256, Log};
+
+    fn receipts(count: u128) -> Vec<ReceiptWithBloom> {
+        (1..=count)
+            .map(|i| {
+                Receipt::<Log> { status: true, cumulative_energy_used: 21000 * i, logs: vec![] }
+                    .with_bloom()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn empty_root() {
+        assert_eq!(ordered_trie_root::<u64>(&[]), EMPTY_ROOT_HASH);
+        assert_eq!(calculate_transaction_root(&[]), EMPTY_ROOT_HASH);
+        assert_eq!(calculate_receipt_root::<Log>(&[]), EMPTY_ROOT_HASH);
+        assert_eq!(sha3([EMPTY_STRING_CODE]), EMPTY_ROOT_HASH);
+        assert_eq!(calculate_ommers_root(&[]), crate::EMPTY_OMMER_ROOT_HASH);
+    }
+
+    // the receipts roots also match parity's `triehash` over SHA3-256
+    #[test]
+    fn single_receipt_root() {
+        assert_eq!(
+            calculate_receipt_root(&receipts(1)),
+            b256!("42a28dfc3e54d19d55eebddc6e12d57e08856e6449e0b39d721dcea6cd768b2d")
+        );
+    }
+
+    #[test]
+    fn receipts_root() {
+        assert_eq!(
+            calculate_receipt_root(&receipts(3)),
+            b256!("f51c5bdb6d384519a953f167eda740efcd58ccddd2e98ff2ffbc042168072d18")
+        );
+        // more than 127 items, so the keys of the trie have different lengths
+        assert_eq!(
+            calculate_receipt_root(&receipts(200)),
+            b256!("e52f101829ed66c6738f4a9b54e749772e6c218953f62b2851269e8164728ae9")
+        );
+    }
+
+    // The expected roots below were computed with parity's `triehash` over SHA3-256, an
+    // implementation independent of this one. The values are short enough for some nodes to be
+    // inlined into their parent, which receipts never are.
+    #[test]
+    fn matches_reference_trie() {
+        let root = |count: usize| {
+            let items = (0..count).map(|i| vec![i as u8; 1 + i % 40]).collect::<Vec<_>>();
+            ordered_trie_root_with_encoder(&items, |item, buf| buf.extend_from_slice(item))
+        };
+        assert_eq!(
+            root(1),
+            b256!("e38c5c84fab2e7164286fba25a3223f13b8dd53598cd36d5d58d82420d87e3a3")
+        );
+        assert_eq!(
+            root(2),
+            b256!("9c6839568af0d72e941e5d4186b5408b30b76566a0f47c7fc284ea2cb8bc22a1")
+        );
+        assert_eq!(
+            root(16),
+            b256!("1ac84f5af5268273e13b351bf953e085a0da0d2f7d86ea77952b4f06607d8528")
+        );
+        assert_eq!(
+            root(17),
+            b256!("778c547784473c01adff85696e7096d4243d7bbc4303c4857e405c343481b0a5")
+        );
+        assert_eq!(
+            root(300),
+            b256!("3f543d43b3db5d25ac2ca3661f7f6e07cf6ff50f3afdbbfd5468a6adb745f678")
+        );
+    }
+
+    #[test]
+    fn encode_path_compact() {
+        assert_eq!(encode_path(&[1, 2, 3, 4, 5], false), [0x11, 0x23, 0x45]);
+        assert_eq!(encode_path(&[0, 1, 2, 3, 4, 5], false), [0x00, 0x01, 0x23, 0x45]);
+        assert_eq!(encode_path(&[0, 15, 1, 12, 11, 8], true), [0x20, 0x0f, 0x1c, 0xb8]);
+        assert_eq!(encode_path(&[15, 1, 12, 11, 8], true), [0x3f, 0x1c, 0xb8]);
+    }
+}
@@ -95,6 +95,39 @@ mod tests {
         assert_eq!(receipt, expected);
     }
 
+    #[test]
+    fn receipt_storage_roundtrip() {
+        let expected = hex!("f8658001f861f85f9600000000000000000000000000000000000000000011f842a0000000000000000000000000000000000000000000000000000000000000deada0000000000000000000000000000000000000000000000000000000000000beef830100ff");
+
+        let receipt = Receipt {
+            cumulative_energy_used: 0x1u128,
+            logs: vec![Log {
+                address: cAddress!("00000000000000000000000000000000000000000011"),
+                data: LogData::new_unchecked(
+                    vec![
+                        b256!("000000000000000000000000000000000000000000000000000000000000dead"),
+                        b256!("000000000000000000000000000000000000000000000000000000000000beef"),
+                    ],
+                    bytes!("0100ff"),
+                ),
+            }],
+            status: false,
+        };
+
+        let mut data = vec![];
+        receipt.encode(&mut data);
+        assert_eq!(receipt.length(), expected.len());
+        assert_eq!(data, expected);
+
+        let decoded = Receipt::<Log>::decode(&mut &data[..]).unwrap();
+        assert_eq!(decoded, receipt);
+
+        let with_bloom = decoded.with_bloom();
+        assert!(with_bloom.is_bloom_valid());
+        assert_ne!(with_bloom.logs_bloom, Bloom::ZERO);
+        assert!(!ReceiptWithBloom { logs_bloom: Bloom::ZERO, ..with_bloom }.is_bloom_valid());
+    }
+
     #[test]
     fn gigantic_receipt() {
         let receipt = Receipt {
@@ -45,6 +45,17 @@ where
     }
 }
 
+impl<T: Encodable> Receipt<T> {
+    /// Returns the rlp header for the receipt payload.
+    fn receipt_rlp_header(&self) -> alloy_rlp::Header {
+        alloy_rlp::Header { list: true, payload_length: self.payload_len() }
+    }
+
+    fn payload_len(&self) -> usize {
+        self.status.length() + self.cumulative_energy_used.length() + self.logs.length()
+    }
+}
+
 impl<T> TxReceipt<T> for Receipt<T>
 where
     T: Borrow<Log>,
@@ -66,6 +77,47 @@ where
     }
 }
 
+/// Encodes the receipt the way go-core stores it in its database: the status, the cumulative
+/// energy used and the logs. The bloom is omitted, as it can be recomputed from the logs.
+impl<T: Encodable> Encodable for Receipt<T> {
+    fn encode(&self, out: &mut dyn BufMut) {
+        self.receipt_rlp_header().encode(out);
+        self.status.encode(out);
+        self.cumulative_energy_used.encode(out);
+        self.logs.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_len();
+        payload_length + length_of_length(payload_length)
+    }
+}
+
+impl<T: Decodable> Decodable for Receipt<T> {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let rlp_head = alloy_rlp::Header::decode(buf)?;
+        if !rlp_head.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        let started_len = buf.len();
+
+        let this = Self {
+            status: Decodable::decode(buf)?,
+            cumulative_energy_used: Decodable::decode(buf)?,
+            logs: Decodable::decode(buf)?,
+        };
+
+        let consumed = started_len - buf.len();
+        if consumed != rlp_head.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: rlp_head.payload_length,
+                got: consumed,
+            });
+        }
+        Ok(this)
+    }
+}
+
 impl<T> From<ReceiptWithBloom<T>> for Receipt<T> {
     /// Consume the structure, returning only the receipt
     fn from(receipt_with_bloom: ReceiptWithBloom<T>) -> Self {
@@ -146,6 +198,18 @@ impl<T: Encodable> ReceiptWithBloom<T> {
     }
 }
 
+impl<T> ReceiptWithBloom<T>
+where
+    T: Borrow<Log>,
+{
+    /// Returns `true` if the bloom filter is the one computed from the logs of the receipt.
+    ///
+    /// This is useful to check a bloom reported by a node before relying on it to match logs.
+    pub fn is_bloom_valid(&self) -> bool {
+        self.logs_bloom == self.receipt.bloom_slow()
+    }
+}
+
 impl<T> ReceiptWithBloom<T> {
     /// Create new [ReceiptWithBloom]
     pub const fn new(receipt: Receipt<T>, logs_bloom: Bloom) -> Self {
@@ -0,0 +1,219 @@
+use crate::{SignableTransaction, Signed, TxEnvelope};
+use base_primitives::{IcanAddress, Signature, SignatureError};
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A signed item together with the address of its signer, recovered from the signature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Recovered<T> {
+    /// The inner item.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    inner: T,
+    /// The recovered signer.
+    signer: IcanAddress,
+}
+
+impl<T> core::ops::Deref for Recovered<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.inner()
+    }
+}
+
+impl<T> Recovered<T> {
+    /// Instantiate without recovering the signer. This should be used carefully.
+    pub const fn new_unchecked(inner: T, signer: IcanAddress) -> Self {
+        Self { inner, signer }
+    }
+
+    /// Get the inner item.
+    #[inline(always)]
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Get the recovered signer.
+    #[inline(always)]
+    pub const fn signer(&self) -> IcanAddress {
+        self.signer
+    }
+
+    /// Discard the signer and return the inner item.
+    #[allow(clippy::missing_const_for_fn)] // false positive
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Decompose into parts.
+    #[allow(clippy::missing_const_for_fn)] // false positive
+    pub fn into_parts(self) -> (T, IcanAddress) {
+        (self.inner, self.signer)
+    }
+}
+
+/// A signed item whose signer can be recovered from its signature.
+pub trait SignerRecoverable {
+    /// Recovers the signer of the item.
+    fn recover_signer(&self, network_id: u64) -> Result<IcanAddress, SignatureError>;
+}
+
+impl<T: SignableTransaction<Signature>> SignerRecoverable for Signed<T> {
+    fn recover_signer(&self, network_id: u64) -> Result<IcanAddress, SignatureError> {
+        Self::recover_signer(self, network_id)
+    }
+}
+
+impl SignerRecoverable for TxEnvelope {
+    fn recover_signer(&self, network_id: u64) -> Result<IcanAddress, SignatureError> {
+        Self::recover_signer(self, network_id)
+    }
+}
+
+/// Failure to recover the signer of one transaction of a batch.
+#[derive(Debug)]
+pub struct RecoveryError {
+    /// The index of the transaction in the batch.
+    pub index: usize,
+    /// The signature error.
+    pub error: SignatureError,
+}
+
+impl fmt::Display for RecoveryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to recover the signer of transaction {}: {}", self.index, self.error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RecoveryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Recovers the signers of a batch of transactions, in order.
+///
+/// With the `rayon` feature enabled the signatures are verified in parallel on the current rayon
+/// thread pool; use [`rayon::ThreadPool::install`] to run on a dedicated pool. Otherwise they are
+/// verified sequentially.
+///
+/// Returns the errors of all the transactions whose signer could not be recovered.
+///
+/// [`rayon::ThreadPool::install`]: https://docs.rs/rayon/latest/rayon/struct.ThreadPool.html#method.install
+pub fn recover_signers<T>(
+    transactions: &[T],
+    network_id: u64,
+) -> Result<Vec<IcanAddress>, Vec<RecoveryError>>
+where
+    T: SignerRecoverable + Sync,
+{
+    #[cfg(feature = "rayon")]
+    let results: Vec<_> = {
+        use rayon::prelude::*;
+        transactions.par_iter().map(|tx| tx.recover_signer(network_id)).collect()
+    };
+    #[cfg(not(feature = "rayon"))]
+    let results: Vec<_> = transactions.iter().map(|tx| tx.recover_signer(network_id)).collect();
+
+    let mut signers = Vec::with_capacity(results.len());
+    let mut errors = Vec::new();
+    for (index, result) in results.into_iter().enumerate() {
+        match result {
+            Ok(signer) => signers.push(signer),
+            Err(error) => errors.push(RecoveryError { index, error }),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(signers)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Recovers the signers of a batch of transactions, pairing each transaction with its signer.
+///
+/// See [`recover_signers`].
+pub fn recover_transactions<T>(
+    transactions: Vec<T>,
+    network_id: u64,
+) -> Result<Vec<Recovered<T>>, Vec<RecoveryError>>
+where
+    T: SignerRecoverable + Sync,
+{
+    let signers = recover_signers(&transactions, network_id)?;
+    Ok(transactions
+        .into_iter()
+        .zip(signers)
+        .map(|(tx, signer)| Recovered::new_unchecked(tx, signer))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TxLegacy;
+    use base_primitives::{cAddress, TxKind, U256};
+    use core::str::FromStr;
+    use libgoldilocks::{PrehashSigner, SigningKey};
+
+    const KEY: &str = "7d6231471b5dbb6204fe5129617082792ae468d01a3f3623184c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    fn signed_tx(key: &SigningKey, nonce: u64) -> Signed<TxLegacy> {
+        let tx = TxLegacy {
+            network_id: 1,
+            nonce,
+            energy_price: 1_000_000_000,
+            energy_limit: 21000,
+            to: TxKind::Call(cAddress!("0000d8dA6BF26964aF9D7eEd9e03E53415D37aA96045")),
+            value: U256::from(100),
+            input: Default::default(),
+        };
+        let signature = key.sign_prehash(tx.signature_hash().as_ref()).unwrap();
+        tx.into_signed(signature)
+    }
+
+    #[test]
+    fn recover_single() {
+        let key = SigningKey::from_str(KEY);
+        let signer = IcanAddress::from_private_key(&key, 1);
+
+        let recovered = signed_tx(&key, 0).try_into_recovered(1).unwrap();
+        assert_eq!(recovered.signer(), signer);
+        assert_eq!(recovered.tx().nonce, 0);
+    }
+
+    #[test]
+    fn recover_batch() {
+        let key = SigningKey::from_str(KEY);
+        let signer = IcanAddress::from_private_key(&key, 1);
+        let transactions = (0..16).map(|nonce| signed_tx(&key, nonce)).collect::<Vec<_>>();
+
+        let signers = recover_signers(&transactions, 1).unwrap();
+        assert_eq!(signers, vec![signer; 16]);
+
+        let recovered = recover_transactions(transactions, 1).unwrap();
+        for (nonce, tx) in recovered.iter().enumerate() {
+            assert_eq!(tx.signer(), signer);
+            assert_eq!(tx.tx().nonce, nonce as u64);
+        }
+    }
+
+    #[test]
+    fn recover_batch_reports_failing_indices() {
+        let key = SigningKey::from_str(KEY);
+        let mut transactions = (0..4).map(|nonce| signed_tx(&key, nonce)).collect::<Vec<_>>();
+
+        for index in [1, 3] {
+            let invalid = Signature::from_str(&format!("0x{}", "00".repeat(171))).unwrap();
+            transactions[index] = transactions[index].tx().clone().into_signed(invalid);
+        }
+
+        let errors = recover_signers(&transactions, 1).unwrap_err();
+        assert_eq!(errors.iter().map(|e| e.index).collect::<Vec<_>>(), [1, 3]);
+    }
+}
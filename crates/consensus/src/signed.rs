@@ -1,4 +1,4 @@
-use crate::transaction::SignableTransaction;
+use crate::{transaction::SignableTransaction, Recovered};
 use base_primitives::{Signature, B256};
 
 /// A transaction with a signature and hash seal.
@@ -60,4 +60,13 @@ impl<T: SignableTransaction<Signature>> Signed<T, Signature> {
         let sighash = self.tx.signature_hash();
         self.signature.recover_address_from_prehash(&sighash, network_id)
     }
+
+    /// Recover the signer of the transaction, keeping it alongside the transaction.
+    pub fn try_into_recovered(
+        self,
+        network_id: u64,
+    ) -> Result<Recovered<Self>, base_primitives::SignatureError> {
+        let signer = self.recover_signer(network_id)?;
+        Ok(Recovered::new_unchecked(self, signer))
+    }
 }
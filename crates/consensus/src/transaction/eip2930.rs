@@ -0,0 +1,321 @@
+use crate::{calculate_intrinsic_energy, SignableTransaction, Signed, Transaction, TxType};
+use alloy_rlp::{length_of_length, BufMut, Decodable, Encodable, Header};
+use atoms_eips::eip2930::AccessList;
+use base_primitives::{sha3, Bytes, ChainId, Signature, TxKind, U256};
+use core::mem;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Transaction with an [`AccessList`] ([EIP-2930](https://eips.ethereum.org/EIPS/eip-2930)).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TxEip2930 {
+    /// Added as EIP-155: Simple replay attack protection
+    #[cfg_attr(feature = "serde", serde(with = "atoms_serde::u64_via_ruint"))]
+    pub network_id: ChainId,
+    /// A scalar value equal to the number of transactions sent by the sender; formally Tn.
+    #[cfg_attr(feature = "serde", serde(with = "atoms_serde::u64_via_ruint"))]
+    pub nonce: u64,
+    /// A scalar value equal to the number of
+    /// Ore to be paid per unit of energy for all computation
+    /// costs incurred as a result of the execution of this transaction; formally Tp.
+    #[cfg_attr(feature = "serde", serde(with = "atoms_serde::u128_via_ruint"))]
+    pub energy_price: u128,
+    /// A scalar value equal to the maximum
+    /// amount of energy that should be used in executing
+    /// this transaction. This is paid up-front, before any
+    /// computation is done and may not be increased
+    /// later; formally Tg.
+    #[cfg_attr(feature = "serde", serde(with = "atoms_serde::u128_via_ruint"))]
+    pub energy_limit: u128,
+    /// The address of the message call’s recipient or, for a contract creation
+    /// transaction, ∅, used here to denote the only member of B0 ; formally Tt.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "TxKind::is_create"))]
+    pub to: TxKind,
+    /// A scalar value equal to the number of Ore to
+    /// be transferred to the message call’s recipient or,
+    /// in the case of contract creation, as an endowment
+    /// to the newly created account; formally Tv.
+    pub value: U256,
+    /// The accessList specifies a list of addresses and storage keys;
+    /// these addresses and storage keys are added into the `accessed_addresses`
+    /// and `accessed_storage_keys` global sets (introduced in EIP-2929).
+    /// A energy cost is charged, though at a discount relative to the cost of
+    /// accessing outside the list.
+    pub access_list: AccessList,
+    /// Input has two uses depending if transaction is Create or Call (if `to` field is None or
+    /// Some). pub init: An unlimited size byte array specifying the
+    /// EVM-code for the account initialisation procedure CREATE,
+    /// data: An unlimited size byte array specifying the
+    /// input data of the message call, formally Td.
+    pub input: Bytes,
+}
+
+impl TxEip2930 {
+    /// The EIP-2718 transaction type.
+    pub const TX_TYPE: isize = 1;
+
+    /// Get the transaction type.
+    pub const fn tx_type(&self) -> TxType {
+        TxType::Eip2930
+    }
+
+    /// Calculates a heuristic for the in-memory size of the [TxEip2930] transaction.
+    #[inline]
+    pub fn size(&self) -> usize {
+        mem::size_of::<ChainId>() + // network_id
+        mem::size_of::<u64>() + // nonce
+        mem::size_of::<u128>() + // energy_price
+        mem::size_of::<u128>() + // energy_limit
+        self.to.size() + // to
+        mem::size_of::<U256>() + // value
+        self.access_list.size() + // access_list
+        self.input.len() // input
+    }
+
+    /// Outputs the length of the transaction's fields, without a RLP header.
+    #[doc(hidden)]
+    pub fn fields_len(&self) -> usize {
+        let mut len = 0;
+        len += self.network_id.length();
+        len += self.nonce.length();
+        len += self.energy_price.length();
+        len += self.energy_limit.length();
+        len += self.to.length();
+        len += self.value.length();
+        len += self.input.0.length();
+        len += self.access_list.length();
+        len
+    }
+
+    /// Encodes only the transaction's fields into the desired buffer, without a RLP header.
+    pub(crate) fn encode_fields(&self, out: &mut dyn BufMut) {
+        self.network_id.encode(out);
+        self.nonce.encode(out);
+        self.energy_price.encode(out);
+        self.energy_limit.encode(out);
+        self.to.encode(out);
+        self.value.encode(out);
+        self.input.0.encode(out);
+        self.access_list.encode(out);
+    }
+
+    /// Decodes the inner [TxEip2930] fields from RLP bytes.
+    ///
+    /// NOTE: This assumes a RLP header has already been decoded, and _just_ decodes the following
+    /// RLP fields in the following order:
+    ///
+    /// - `network_id`
+    /// - `nonce`
+    /// - `energy_price`
+    /// - `energy_limit`
+    /// - `to`
+    /// - `value`
+    /// - `input`
+    /// - `access_list`
+    pub(crate) fn decode_fields(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Ok(Self {
+            network_id: Decodable::decode(buf)?,
+            nonce: Decodable::decode(buf)?,
+            energy_price: Decodable::decode(buf)?,
+            energy_limit: Decodable::decode(buf)?,
+            to: Decodable::decode(buf)?,
+            value: Decodable::decode(buf)?,
+            input: Decodable::decode(buf)?,
+            access_list: Decodable::decode(buf)?,
+        })
+    }
+
+    /// Encodes the transaction from RLP bytes, including the signature. This __does not__ encode a
+    /// tx type byte or string header.
+    ///
+    /// This __does__ encode a list header and include a signature.
+    pub fn encode_with_signature_fields(&self, signature: &Signature, out: &mut dyn BufMut) {
+        let payload_length = self.fields_len() + signature.rlp_len();
+        let header = Header { list: true, payload_length };
+        header.encode(out);
+        self.encode_fields(out);
+        signature.write_rlp(out);
+    }
+
+    /// Returns what the encoded length should be, if the transaction were RLP encoded with the
+    /// given signature, without the type byte.
+    pub(crate) fn encoded_len_with_signature(&self, signature: &Signature) -> usize {
+        let payload_length = self.fields_len() + signature.rlp_len();
+        length_of_length(payload_length) + payload_length
+    }
+
+    /// Encodes the transaction with the signature and the EIP-2718 type byte, as it is sent over
+    /// `xcb_sendRawTransaction` and committed to in the transactions trie.
+    pub fn encode_2718_with_signature(&self, signature: &Signature, out: &mut dyn BufMut) {
+        out.put_u8(self.tx_type() as u8);
+        self.encode_with_signature_fields(signature, out);
+    }
+
+    /// Decodes the transaction from RLP bytes, including the signature.
+    ///
+    /// This __does not__ expect the bytes to start with a transaction type byte or string
+    /// header.
+    ///
+    /// This __does__ expect the bytes to start with a list header and include a signature.
+    #[doc(hidden)]
+    pub fn decode_signed_fields(buf: &mut &[u8]) -> alloy_rlp::Result<Signed<Self>> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+
+        // record original length so we can check encoding
+        let original_len = buf.len();
+
+        let tx = Self::decode_fields(buf)?;
+        let signature = Signature::decode_rlp_sig(buf)?;
+
+        let signed = tx.into_signed(signature);
+        if buf.len() + header.payload_length != original_len {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: original_len - buf.len(),
+            });
+        }
+
+        Ok(signed)
+    }
+}
+
+impl Transaction for TxEip2930 {
+    fn input(&self) -> &[u8] {
+        &self.input
+    }
+
+    fn to(&self) -> TxKind {
+        self.to
+    }
+
+    fn value(&self) -> U256 {
+        self.value
+    }
+
+    fn chain_id(&self) -> ChainId {
+        self.network_id
+    }
+
+    fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    fn gas_limit(&self) -> u128 {
+        self.energy_limit
+    }
+
+    fn gas_price(&self) -> Option<u128> {
+        Some(self.energy_price)
+    }
+
+    fn intrinsic_energy(&self) -> u128 {
+        calculate_intrinsic_energy(&self.input, self.to.is_create(), &self.access_list)
+    }
+}
+
+impl SignableTransaction<Signature> for TxEip2930 {
+    fn set_chain_id(&mut self, chain_id: ChainId) {
+        self.network_id = chain_id;
+    }
+
+    fn encode_for_signing(&self, out: &mut dyn BufMut) {
+        out.put_u8(self.tx_type() as u8);
+        Header { list: true, payload_length: self.fields_len() }.encode(out);
+        self.encode_fields(out);
+    }
+
+    fn payload_len_for_signature(&self) -> usize {
+        let payload_length = self.fields_len();
+        // 'tx type' + 'header length' + 'payload length'
+        1 + length_of_length(payload_length) + payload_length
+    }
+
+    fn into_signed(self, signature: Signature) -> Signed<Self> {
+        let mut buf = Vec::with_capacity(1 + self.encoded_len_with_signature(&signature));
+        self.encode_2718_with_signature(&signature, &mut buf);
+        let hash = sha3(&buf);
+        Signed::new_unchecked(self, signature, hash)
+    }
+}
+
+impl Encodable for TxEip2930 {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.fields_len() }.encode(out);
+        self.encode_fields(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.fields_len();
+        length_of_length(payload_length) + payload_length
+    }
+}
+
+impl Decodable for TxEip2930 {
+    fn decode(data: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(data)?;
+        let remaining_len = data.len();
+
+        if header.payload_length > remaining_len {
+            return Err(alloy_rlp::Error::InputTooShort);
+        }
+
+        let tx = Self::decode_fields(data)?;
+
+        if remaining_len - data.len() != header.payload_length {
+            return Err(alloy_rlp::Error::UnexpectedLength);
+        }
+
+        Ok(tx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atoms_eips::eip2930::AccessListItem;
+    use base_primitives::{cAddress, hex, B256};
+
+    fn tx() -> TxEip2930 {
+        TxEip2930 {
+            network_id: 1,
+            nonce: 2,
+            energy_price: 1_000_000_000,
+            energy_limit: 50_000,
+            to: TxKind::Call(cAddress!("0000d8dA6BF26964aF9D7eEd9e03E53415D37aA96045")),
+            value: U256::from(100),
+            access_list: AccessList(vec![AccessListItem {
+                address: cAddress!("0000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+                storage_keys: vec![B256::ZERO, B256::with_last_byte(1)],
+            }]),
+            input: hex!("c0ffee").into(),
+        }
+    }
+
+    #[test]
+    fn encode_decode_eip2930() {
+        let tx = tx();
+
+        let mut encoded = Vec::new();
+        tx.encode(&mut encoded);
+        assert_eq!(encoded.len(), tx.length());
+        assert_eq!(TxEip2930::decode(&mut encoded.as_slice()).unwrap(), tx);
+
+        // the signing payload is prefixed by the transaction type
+        let for_signing = tx.encoded_for_signing();
+        assert_eq!(for_signing.len(), tx.payload_len_for_signature());
+        assert_eq!(for_signing[0], 1);
+        assert_eq!(&for_signing[1..], encoded);
+    }
+
+    #[test]
+    fn intrinsic_energy_with_access_list() {
+        let tx = tx();
+        assert_eq!(tx.intrinsic_energy(), 21000 + 3 * 16 + 2400 + 2 * 1900);
+    }
+}
@@ -0,0 +1,328 @@
+use crate::{Recovered, Signed, Transaction, TxEip2930, TxLegacy};
+use alloy_rlp::{Decodable, Encodable, Header};
+use atoms_eips::eip2718::{Decodable2718, Eip2718Error, Encodable2718};
+use base_primitives::{IcanAddress, SignatureError, TxKind, B256, U256};
+
+/// Core `TransactionType` flags as specified in EIPs [2718] and [2930].
+///
+/// [2718]: https://eips.ethereum.org/EIPS/eip-2718
+/// [2930]: https://eips.ethereum.org/EIPS/eip-2930
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TxType {
+    /// Legacy transaction type.
+    #[default]
+    Legacy = 0,
+    /// EIP-2930 transaction type.
+    Eip2930 = 1,
+}
+
+impl From<TxType> for u8 {
+    fn from(value: TxType) -> Self {
+        value as Self
+    }
+}
+
+impl TryFrom<u8> for TxType {
+    type Error = Eip2718Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Legacy),
+            1 => Ok(Self::Eip2930),
+            _ => Err(Eip2718Error::UnexpectedType(value)),
+        }
+    }
+}
+
+/// The Core [EIP-2718] Transaction Envelope.
+///
+/// # Note:
+///
+/// This enum distinguishes between tagged and untagged legacy transactions, as
+/// the in-protocol merkle tree may commit to EITHER 0-prefixed or raw.
+/// Therefore we must ensure that encoding returns the precise byte-array that
+/// was decoded, preserving the presence or absence of the `TransactionType`
+/// flag.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub enum TxEnvelope {
+    /// An untagged [`TxLegacy`].
+    #[cfg_attr(feature = "serde", serde(rename = "0x00", alias = "0x0"))]
+    Legacy(Signed<TxLegacy>),
+    /// A [`TxEip2930`] tagged with type 1.
+    #[cfg_attr(feature = "serde", serde(rename = "0x01", alias = "0x1"))]
+    Eip2930(Signed<TxEip2930>),
+}
+
+impl From<Signed<TxLegacy>> for TxEnvelope {
+    fn from(v: Signed<TxLegacy>) -> Self {
+        Self::Legacy(v)
+    }
+}
+
+impl From<Signed<TxEip2930>> for TxEnvelope {
+    fn from(v: Signed<TxEip2930>) -> Self {
+        Self::Eip2930(v)
+    }
+}
+
+impl TxEnvelope {
+    /// Return the [`TxType`] of the inner txn.
+    pub const fn tx_type(&self) -> TxType {
+        match self {
+            Self::Legacy(_) => TxType::Legacy,
+            Self::Eip2930(_) => TxType::Eip2930,
+        }
+    }
+
+    /// Return the hash of the inner Signed
+    pub const fn tx_hash(&self) -> &B256 {
+        match self {
+            Self::Legacy(tx) => tx.hash(),
+            Self::Eip2930(tx) => tx.hash(),
+        }
+    }
+
+    /// Return the signature hash of the inner Signed
+    pub fn signature_hash(&self) -> B256 {
+        match self {
+            Self::Legacy(tx) => tx.signature_hash(),
+            Self::Eip2930(tx) => tx.signature_hash(),
+        }
+    }
+
+    /// Recover the signer of the transaction.
+    pub fn recover_signer(&self, network_id: u64) -> Result<IcanAddress, SignatureError> {
+        match self {
+            Self::Legacy(tx) => tx.recover_signer(network_id),
+            Self::Eip2930(tx) => tx.recover_signer(network_id),
+        }
+    }
+
+    /// Recover the signer of the transaction, keeping it alongside the transaction.
+    pub fn try_into_recovered(self, network_id: u64) -> Result<Recovered<Self>, SignatureError> {
+        let signer = self.recover_signer(network_id)?;
+        Ok(Recovered::new_unchecked(self, signer))
+    }
+
+    /// Return the reference to the inner legacy transaction, if it is one.
+    pub const fn as_legacy(&self) -> Option<&Signed<TxLegacy>> {
+        match self {
+            Self::Legacy(tx) => Some(tx),
+            _ => None,
+        }
+    }
+
+    /// Return the reference to the inner EIP-2930 transaction, if it is one.
+    pub const fn as_eip2930(&self) -> Option<&Signed<TxEip2930>> {
+        match self {
+            Self::Eip2930(tx) => Some(tx),
+            _ => None,
+        }
+    }
+
+    /// Return the length of the inner txn, __without a type byte__.
+    pub fn inner_length(&self) -> usize {
+        match self {
+            Self::Legacy(t) => t.tx().encoded_len_with_signature(t.signature()),
+            Self::Eip2930(t) => t.tx().encoded_len_with_signature(t.signature()),
+        }
+    }
+
+    /// Return the RLP payload length of the network-serialized wrapper
+    fn rlp_payload_length(&self) -> usize {
+        if let Self::Legacy(t) = self {
+            return t.tx().encoded_len_with_signature(t.signature());
+        }
+        // length of inner tx body
+        let inner_length = self.inner_length();
+        // with tx type byte
+        inner_length + 1
+    }
+}
+
+impl Transaction for TxEnvelope {
+    fn input(&self) -> &[u8] {
+        match self {
+            Self::Legacy(tx) => tx.tx().input(),
+            Self::Eip2930(tx) => tx.tx().input(),
+        }
+    }
+
+    fn to(&self) -> TxKind {
+        match self {
+            Self::Legacy(tx) => tx.tx().to(),
+            Self::Eip2930(tx) => tx.tx().to(),
+        }
+    }
+
+    fn value(&self) -> U256 {
+        match self {
+            Self::Legacy(tx) => tx.tx().value(),
+            Self::Eip2930(tx) => tx.tx().value(),
+        }
+    }
+
+    fn chain_id(&self) -> base_primitives::ChainId {
+        match self {
+            Self::Legacy(tx) => tx.tx().chain_id(),
+            Self::Eip2930(tx) => tx.tx().chain_id(),
+        }
+    }
+
+    fn nonce(&self) -> u64 {
+        match self {
+            Self::Legacy(tx) => tx.tx().nonce(),
+            Self::Eip2930(tx) => tx.tx().nonce(),
+        }
+    }
+
+    fn gas_limit(&self) -> u128 {
+        match self {
+            Self::Legacy(tx) => tx.tx().gas_limit(),
+            Self::Eip2930(tx) => tx.tx().gas_limit(),
+        }
+    }
+
+    fn gas_price(&self) -> Option<u128> {
+        match self {
+            Self::Legacy(tx) => tx.tx().gas_price(),
+            Self::Eip2930(tx) => tx.tx().gas_price(),
+        }
+    }
+
+    fn intrinsic_energy(&self) -> u128 {
+        match self {
+            Self::Legacy(tx) => tx.tx().intrinsic_energy(),
+            Self::Eip2930(tx) => tx.tx().intrinsic_energy(),
+        }
+    }
+}
+
+impl Encodable for TxEnvelope {
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        self.network_encode(out)
+    }
+
+    fn length(&self) -> usize {
+        let mut payload_length = self.rlp_payload_length();
+        if !self.is_legacy() {
+            payload_length += Header { list: false, payload_length }.length();
+        }
+
+        payload_length
+    }
+}
+
+impl Decodable for TxEnvelope {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Self::network_decode(buf)
+    }
+}
+
+impl Decodable2718 for TxEnvelope {
+    fn typed_decode(ty: u8, buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        match ty.try_into().map_err(|_| alloy_rlp::Error::Custom("unexpected tx type"))? {
+            TxType::Eip2930 => Ok(Self::Eip2930(TxEip2930::decode_signed_fields(buf)?)),
+            TxType::Legacy => {
+                Err(alloy_rlp::Error::Custom("type-0 eip2718 transactions are not supported"))
+            }
+        }
+    }
+
+    fn fallback_decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Ok(Self::Legacy(TxLegacy::decode_signed_fields(buf)?))
+    }
+}
+
+impl Encodable2718 for TxEnvelope {
+    fn type_flag(&self) -> Option<u8> {
+        match self {
+            Self::Legacy(_) => None,
+            Self::Eip2930(_) => Some(TxType::Eip2930 as u8),
+        }
+    }
+
+    fn encode_2718_len(&self) -> usize {
+        self.rlp_payload_length()
+    }
+
+    fn encode_2718(&self, out: &mut dyn alloy_rlp::BufMut) {
+        match self {
+            Self::Legacy(tx) => tx.tx().encode_with_signature_fields(tx.signature(), out),
+            Self::Eip2930(tx) => tx.tx().encode_2718_with_signature(tx.signature(), out),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SignableTransaction;
+    use atoms_eips::eip2930::{AccessList, AccessListItem};
+    use base_primitives::{cAddress, hex, Signature};
+    use core::str::FromStr;
+    use libgoldilocks::{PrehashSigner, SigningKey};
+
+    const KEY: &str = "7d6231471b5dbb6204fe5129617082792ae468d01a3f3623184c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    fn test_encode_decode_roundtrip(envelope: TxEnvelope) {
+        let encoded = envelope.encoded_2718();
+        assert_eq!(encoded.len(), envelope.encode_2718_len());
+        let decoded = TxEnvelope::decode_2718(&mut encoded.as_ref()).unwrap();
+        assert_eq!(decoded, envelope);
+        assert_eq!(decoded.tx_hash(), envelope.tx_hash());
+
+        let mut network_encoded = Vec::new();
+        envelope.encode(&mut network_encoded);
+        assert_eq!(network_encoded.len(), envelope.length());
+        assert_eq!(TxEnvelope::decode(&mut network_encoded.as_slice()).unwrap(), envelope);
+    }
+
+    #[test]
+    fn encode_decode_legacy() {
+        let tx = TxLegacy {
+            network_id: 1,
+            nonce: 2,
+            energy_price: 1_000_000_000,
+            energy_limit: 21000,
+            to: TxKind::Call(cAddress!("0000d8dA6BF26964aF9D7eEd9e03E53415D37aA96045")),
+            value: U256::from(100),
+            input: Default::default(),
+        };
+        let signature = Signature::from_str(&format!("0x{}", "ab".repeat(171))).unwrap();
+        let envelope: TxEnvelope = tx.into_signed(signature).into();
+        assert!(envelope.is_legacy());
+        test_encode_decode_roundtrip(envelope);
+    }
+
+    #[test]
+    fn encode_decode_eip2930() {
+        let key = SigningKey::from_str(KEY);
+        let tx = TxEip2930 {
+            network_id: 1,
+            nonce: 2,
+            energy_price: 1_000_000_000,
+            energy_limit: 50_000,
+            to: TxKind::Call(cAddress!("0000d8dA6BF26964aF9D7eEd9e03E53415D37aA96045")),
+            value: U256::from(100),
+            access_list: AccessList(vec![AccessListItem {
+                address: cAddress!("0000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+                storage_keys: vec![B256::ZERO],
+            }]),
+            input: hex!("c0ffee").into(),
+        };
+        let signature = key.sign_prehash(tx.signature_hash().as_ref()).unwrap();
+        let envelope: TxEnvelope = tx.into_signed(signature).into();
+
+        assert_eq!(envelope.tx_type(), TxType::Eip2930);
+        assert_eq!(envelope.encoded_2718()[0], 1);
+        assert_eq!(*envelope.tx_hash(), envelope.trie_hash());
+        assert_eq!(envelope.recover_signer(1).unwrap(), IcanAddress::from_private_key(&key, 1));
+        test_encode_decode_roundtrip(envelope);
+    }
+}
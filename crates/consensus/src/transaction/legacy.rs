@@ -153,12 +153,18 @@ impl TxLegacy {
         // record original length so we can check encoding
         let original_len = buf.len();
 
-        let mut tx = Self::decode_fields(buf)?;
+        // the network id is part of the signed fields, in the same position as it is encoded
+        let tx = Self {
+            nonce: Decodable::decode(buf)?,
+            energy_price: Decodable::decode(buf)?,
+            energy_limit: Decodable::decode(buf)?,
+            network_id: Decodable::decode(buf)?,
+            to: Decodable::decode(buf)?,
+            value: Decodable::decode(buf)?,
+            input: Decodable::decode(buf)?,
+        };
         let signature = Signature::decode_rlp_sig(buf)?;
 
-        // extract chain id from signature
-        tx.network_id = 0;
-
         let signed = tx.into_signed(signature);
         if buf.len() + header.payload_length != original_len {
             return Err(alloy_rlp::Error::ListLengthMismatch {
@@ -285,8 +291,10 @@ impl Decodable for TxLegacy {
 mod tests {
     use core::str::FromStr;
 
-    use crate::{SignableTransaction, TxLegacy};
-    use base_primitives::{address, b256, cAddress, hex, Address, IcanAddress, Signature, TxKind, B256, U256};
+    use crate::{SignableTransaction, Transaction, TxLegacy, TypedTransaction};
+    use base_primitives::{
+        address, b256, cAddress, hex, Address, IcanAddress, Signature, TxKind, B256, U256,
+    };
 
     #[test]
     fn recover_signer_legacy() {
@@ -328,4 +336,18 @@ mod tests {
         assert_eq!(tx.tx().network_id, 1, "Expected same chain id");
         assert_eq!(expected, recovered, "Expected same signer");
     }
+
+    #[test]
+    fn intrinsic_energy() {
+        let tx = TxLegacy {
+            to: TxKind::Call(IcanAddress::ZERO),
+            input: hex!("00ff0000c0de").into(),
+            ..Default::default()
+        };
+        assert_eq!(tx.intrinsic_energy(), 21000 + 3 * 4 + 3 * 16);
+
+        let tx = TxLegacy { to: TxKind::Create, ..tx };
+        assert_eq!(tx.intrinsic_energy(), 53000 + 3 * 4 + 3 * 16);
+        assert_eq!(TypedTransaction::Legacy(tx).intrinsic_energy(), 53000 + 3 * 4 + 3 * 16);
+    }
 }
@@ -1,10 +1,23 @@
-use crate::Signed;
+use crate::{
+    constants::{
+        TX_ACCESS_LIST_ADDRESS_ENERGY, TX_ACCESS_LIST_STORAGE_KEY_ENERGY, TX_DATA_NON_ZERO_ENERGY,
+        TX_DATA_ZERO_ENERGY, TX_ENERGY, TX_ENERGY_CONTRACT_CREATION,
+    },
+    Signed,
+};
+use atoms_eips::eip2930::AccessListItem;
 use base_primitives::{sha3, ChainId, TxKind, B256, U256};
 use core::any;
 
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
+mod eip2930;
+pub use eip2930::TxEip2930;
+
+mod envelope;
+pub use envelope::{TxEnvelope, TxType};
+
 mod legacy;
 pub use legacy::TxLegacy;
 
@@ -33,6 +46,33 @@ pub trait Transaction: any::Any + Send + Sync + 'static {
 
     /// Get `gas_price`.
     fn gas_price(&self) -> Option<u128>;
+
+    /// Calculate the intrinsic energy of the transaction, see [`calculate_intrinsic_energy`].
+    fn intrinsic_energy(&self) -> u128 {
+        calculate_intrinsic_energy(self.input(), self.to().is_create(), &[])
+    }
+}
+
+/// Calculates the intrinsic energy of a transaction: the energy charged before execution, and so
+/// the minimum energy limit the transaction can be sent with.
+///
+/// This is the base transaction cost, or the contract creation cost if `is_create`, plus the cost
+/// of each zero and non-zero byte of `input` and of each address and storage key of the
+/// `access_list`.
+pub fn calculate_intrinsic_energy(
+    input: &[u8],
+    is_create: bool,
+    access_list: &[AccessListItem],
+) -> u128 {
+    let base = if is_create { TX_ENERGY_CONTRACT_CREATION } else { TX_ENERGY };
+    let zero_bytes = input.iter().filter(|byte| **byte == 0).count() as u128;
+    let non_zero_bytes = input.len() as u128 - zero_bytes;
+    let storage_keys =
+        access_list.iter().map(|item| item.storage_keys.len()).sum::<usize>() as u128;
+    base + zero_bytes * TX_DATA_ZERO_ENERGY
+        + non_zero_bytes * TX_DATA_NON_ZERO_ENERGY
+        + access_list.len() as u128 * TX_ACCESS_LIST_ADDRESS_ENERGY
+        + storage_keys * TX_ACCESS_LIST_STORAGE_KEY_ENERGY
 }
 
 /// A signable transaction.
@@ -1,4 +1,7 @@
-use crate::{transaction::TxLegacy, Transaction};
+use crate::{
+    transaction::{TxEip2930, TxLegacy},
+    Transaction, TxEnvelope, TxType,
+};
 use base_primitives::TxKind;
 
 /// The TypedTransaction enum represents all Core transaction request types.
@@ -15,6 +18,9 @@ pub enum TypedTransaction {
     /// Legacy transaction
     #[cfg_attr(feature = "serde", serde(rename = "0x00", alias = "0x0"))]
     Legacy(TxLegacy),
+    /// EIP-2930 transaction
+    #[cfg_attr(feature = "serde", serde(rename = "0x01", alias = "0x1"))]
+    Eip2930(TxEip2930),
 }
 
 impl From<TxLegacy> for TypedTransaction {
@@ -23,11 +29,43 @@ impl From<TxLegacy> for TypedTransaction {
     }
 }
 
+impl From<TxEip2930> for TypedTransaction {
+    fn from(tx: TxEip2930) -> Self {
+        Self::Eip2930(tx)
+    }
+}
+
+impl From<TxEnvelope> for TypedTransaction {
+    fn from(envelope: TxEnvelope) -> Self {
+        match envelope {
+            TxEnvelope::Legacy(tx) => tx.strip_signature().into(),
+            TxEnvelope::Eip2930(tx) => tx.strip_signature().into(),
+        }
+    }
+}
+
 impl TypedTransaction {
+    /// Return the [`TxType`] of the inner txn.
+    pub const fn tx_type(&self) -> TxType {
+        match self {
+            Self::Legacy(_) => TxType::Legacy,
+            Self::Eip2930(_) => TxType::Eip2930,
+        }
+    }
+
     /// Return the inner legacy transaction if it exists.
     pub const fn legacy(&self) -> Option<&TxLegacy> {
         match self {
             Self::Legacy(tx) => Some(tx),
+            _ => None,
+        }
+    }
+
+    /// Return the inner EIP-2930 transaction if it exists.
+    pub const fn eip2930(&self) -> Option<&TxEip2930> {
+        match self {
+            Self::Eip2930(tx) => Some(tx),
+            _ => None,
         }
     }
 }
@@ -36,42 +74,56 @@ impl Transaction for TypedTransaction {
     fn chain_id(&self) -> base_primitives::ChainId {
         match self {
             Self::Legacy(tx) => tx.chain_id(),
+            Self::Eip2930(tx) => tx.chain_id(),
         }
     }
 
     fn gas_limit(&self) -> u128 {
         match self {
             Self::Legacy(tx) => tx.gas_limit(),
+            Self::Eip2930(tx) => tx.gas_limit(),
         }
     }
 
     fn gas_price(&self) -> Option<u128> {
         match self {
             Self::Legacy(tx) => tx.gas_price(),
+            Self::Eip2930(tx) => tx.gas_price(),
         }
     }
 
     fn input(&self) -> &[u8] {
         match self {
             Self::Legacy(tx) => tx.input(),
+            Self::Eip2930(tx) => tx.input(),
         }
     }
 
     fn nonce(&self) -> u64 {
         match self {
             Self::Legacy(tx) => tx.nonce(),
+            Self::Eip2930(tx) => tx.nonce(),
         }
     }
 
     fn to(&self) -> TxKind {
         match self {
             Self::Legacy(tx) => tx.to(),
+            Self::Eip2930(tx) => tx.to(),
         }
     }
 
     fn value(&self) -> base_primitives::U256 {
         match self {
             Self::Legacy(tx) => tx.value(),
+            Self::Eip2930(tx) => tx.value(),
+        }
+    }
+
+    fn intrinsic_energy(&self) -> u128 {
+        match self {
+            Self::Legacy(tx) => tx.intrinsic_energy(),
+            Self::Eip2930(tx) => tx.intrinsic_energy(),
         }
     }
 }
@@ -0,0 +1,525 @@
+//! Validation of a [`Header`] against its parent, following the rules enforced by go-core.
+
+use crate::{
+    clique,
+    constants::{
+        CLIQUE_DIFF_IN_TURN, CLIQUE_DIFF_NO_TURN, CLIQUE_EXTRA_SEAL, CLIQUE_EXTRA_VANITY,
+        ENERGY_LIMIT_BOUND_DIVISOR, MAXIMUM_ENERGY_LIMIT, MAXIMUM_EXTRA_DATA_SIZE,
+        MINIMUM_ENERGY_LIMIT,
+    },
+    Header, Sealed, EMPTY_OMMER_ROOT_HASH,
+};
+use atoms_genesis::{ChainConfig, CliqueConfig};
+use base_primitives::{IcanAddress, B256, B64, U256};
+use core::fmt;
+
+/// Nonce of a Clique header voting to add a signer.
+pub const CLIQUE_NONCE_AUTH_VOTE: B64 = B64::repeat_byte(0xff);
+
+/// Nonce of a Clique header voting to remove a signer.
+pub const CLIQUE_NONCE_DROP_VOTE: B64 = B64::ZERO;
+
+/// Error returned when a header is invalid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeaderValidationError {
+    /// The header number does not follow the parent number.
+    NumberMismatch {
+        /// The parent block number.
+        parent: u64,
+        /// The header block number.
+        number: u64,
+    },
+    /// The header parent hash is not the hash of the parent.
+    ParentHashMismatch {
+        /// The hash of the parent.
+        parent: B256,
+        /// The parent hash of the header.
+        parent_hash: B256,
+    },
+    /// The header timestamp is not after the parent timestamp.
+    TimestampNotAfterParent {
+        /// The parent timestamp.
+        parent: u64,
+        /// The header timestamp.
+        timestamp: u64,
+    },
+    /// The energy used exceeds the energy limit.
+    EnergyUsedExceedsLimit {
+        /// The energy used.
+        energy_used: u128,
+        /// The energy limit.
+        energy_limit: u128,
+    },
+    /// The energy limit is above [`MAXIMUM_ENERGY_LIMIT`].
+    EnergyLimitTooHigh(u128),
+    /// The energy limit is below [`MINIMUM_ENERGY_LIMIT`].
+    EnergyLimitTooLow(u128),
+    /// The energy limit changed by more than allowed from the parent.
+    EnergyLimitChangeTooLarge {
+        /// The parent energy limit.
+        parent: u128,
+        /// The header energy limit.
+        energy_limit: u128,
+    },
+    /// The extra data is longer than [`MAXIMUM_EXTRA_DATA_SIZE`].
+    ExtraDataTooLong(usize),
+    /// The Clique extra data is missing the signer vanity.
+    CliqueMissingVanity,
+    /// The Clique extra data is missing the signer seal.
+    CliqueMissingSeal,
+    /// The Clique extra data contains signers outside of a checkpoint block.
+    CliqueExtraSigners,
+    /// The Clique checkpoint signer list is not a whole number of addresses.
+    CliqueInvalidCheckpointSigners,
+    /// The Clique checkpoint block has a non-zero beneficiary.
+    CliqueInvalidCheckpointBeneficiary,
+    /// The Clique nonce is neither an authorization nor a drop vote.
+    CliqueInvalidVote,
+    /// The Clique checkpoint block votes to add a signer.
+    CliqueInvalidCheckpointVote,
+    /// The Clique mix hash is not zero.
+    CliqueInvalidMixHash,
+    /// The Clique ommers hash is not the empty ommers hash.
+    CliqueInvalidOmmersHash,
+    /// The Clique difficulty is neither the in-turn nor the out-of-turn difficulty.
+    CliqueInvalidDifficulty,
+    /// The Clique header was produced before the period elapsed since its parent.
+    CliqueTimestampTooEarly {
+        /// The earliest allowed timestamp.
+        earliest: u64,
+        /// The header timestamp.
+        timestamp: u64,
+    },
+}
+
+impl fmt::Display for HeaderValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NumberMismatch { parent, number } => {
+                write!(f, "block number {number} does not follow parent number {parent}")
+            }
+            Self::ParentHashMismatch { parent, parent_hash } => {
+                write!(f, "parent hash {parent_hash} does not match parent {parent}")
+            }
+            Self::TimestampNotAfterParent { parent, timestamp } => {
+                write!(f, "timestamp {timestamp} is not after parent timestamp {parent}")
+            }
+            Self::EnergyUsedExceedsLimit { energy_used, energy_limit } => {
+                write!(f, "energy used {energy_used} exceeds energy limit {energy_limit}")
+            }
+            Self::EnergyLimitTooHigh(limit) => {
+                write!(f, "energy limit {limit} is above maximum {MAXIMUM_ENERGY_LIMIT}")
+            }
+            Self::EnergyLimitTooLow(limit) => {
+                write!(f, "energy limit {limit} is below minimum {MINIMUM_ENERGY_LIMIT}")
+            }
+            Self::EnergyLimitChangeTooLarge { parent, energy_limit } => {
+                write!(f, "energy limit {energy_limit} changed too much from parent limit {parent}")
+            }
+            Self::ExtraDataTooLong(len) => {
+                write!(f, "extra data of {len} bytes exceeds {MAXIMUM_EXTRA_DATA_SIZE} bytes")
+            }
+            Self::CliqueMissingVanity => f.write_str("extra data is missing the signer vanity"),
+            Self::CliqueMissingSeal => f.write_str("extra data is missing the signer seal"),
+            Self::CliqueExtraSigners => f.write_str("non-checkpoint block contains signers"),
+            Self::CliqueInvalidCheckpointSigners => {
+                f.write_str("invalid signer list on checkpoint block")
+            }
+            Self::CliqueInvalidCheckpointBeneficiary => {
+                f.write_str("checkpoint block has a non-zero beneficiary")
+            }
+            Self::CliqueInvalidVote => f.write_str("nonce is not an authorization or drop vote"),
+            Self::CliqueInvalidCheckpointVote => {
+                f.write_str("checkpoint block votes to add a signer")
+            }
+            Self::CliqueInvalidMixHash => f.write_str("non-zero mix hash"),
+            Self::CliqueInvalidOmmersHash => f.write_str("non-empty ommers hash"),
+            Self::CliqueInvalidDifficulty => f.write_str("invalid difficulty"),
+            Self::CliqueTimestampTooEarly { earliest, timestamp } => {
+                write!(f, "timestamp {timestamp} is before the end of the period at {earliest}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HeaderValidationError {}
+
+/// Validates a header against its parent, following the consensus engine of the chain.
+///
+/// For Clique chains, this runs [`validate_parent_link`] and [`validate_clique_header`], which
+/// enforces the block period. Otherwise, the chain is a Cryptore chain and this runs
+/// [`validate_against_parent`] and [`validate_extra_data`].
+pub fn validate_header(
+    header: &Header,
+    parent: &Sealed<Header>,
+    config: &ChainConfig,
+) -> Result<(), HeaderValidationError> {
+    match &config.clique {
+        Some(clique) => {
+            validate_parent_link(header, parent)?;
+            validate_clique_header(header, parent, clique)
+        }
+        None => {
+            validate_against_parent(header, parent)?;
+            validate_extra_data(header)
+        }
+    }
+}
+
+/// Validates the fields of a Cryptore header against its parent: the ones checked by
+/// [`validate_parent_link`], and a timestamp strictly after the parent's.
+///
+/// Clique headers may share the timestamp of their parent on chains without a block period, see
+/// [`validate_clique_header`].
+pub fn validate_against_parent(
+    header: &Header,
+    parent: &Sealed<Header>,
+) -> Result<(), HeaderValidationError> {
+    validate_parent_link(header, parent)?;
+
+    if header.timestamp <= parent.timestamp {
+        return Err(HeaderValidationError::TimestampNotAfterParent {
+            parent: parent.timestamp,
+            timestamp: header.timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+/// Validates the fields of a header that are common to all consensus engines against its
+/// parent: number and parent hash continuity and energy limit bounds.
+pub fn validate_parent_link(
+    header: &Header,
+    parent: &Sealed<Header>,
+) -> Result<(), HeaderValidationError> {
+    if parent.number.checked_add(1) != Some(header.number) {
+        return Err(HeaderValidationError::NumberMismatch {
+            parent: parent.number,
+            number: header.number,
+        });
+    }
+
+    if header.parent_hash != parent.hash() {
+        return Err(HeaderValidationError::ParentHashMismatch {
+            parent: parent.hash(),
+            parent_hash: header.parent_hash,
+        });
+    }
+
+    validate_energy_limit(header, parent)
+}
+
+/// Validates the energy used and energy limit of a header against its parent.
+///
+/// The energy limit must stay within [`MINIMUM_ENERGY_LIMIT`] and [`MAXIMUM_ENERGY_LIMIT`], and
+/// may only change by less than `parent_limit / ENERGY_LIMIT_BOUND_DIVISOR` from the parent.
+pub fn validate_energy_limit(
+    header: &Header,
+    parent: &Header,
+) -> Result<(), HeaderValidationError> {
+    if header.gas_used > header.gas_limit {
+        return Err(HeaderValidationError::EnergyUsedExceedsLimit {
+            energy_used: header.gas_used,
+            energy_limit: header.gas_limit,
+        });
+    }
+
+    if header.gas_limit > MAXIMUM_ENERGY_LIMIT {
+        return Err(HeaderValidationError::EnergyLimitTooHigh(header.gas_limit));
+    }
+
+    if header.gas_limit < MINIMUM_ENERGY_LIMIT {
+        return Err(HeaderValidationError::EnergyLimitTooLow(header.gas_limit));
+    }
+
+    if header.gas_limit.abs_diff(parent.gas_limit) >= parent.gas_limit / ENERGY_LIMIT_BOUND_DIVISOR
+    {
+        return Err(HeaderValidationError::EnergyLimitChangeTooLarge {
+            parent: parent.gas_limit,
+            energy_limit: header.gas_limit,
+        });
+    }
+
+    Ok(())
+}
+
+/// Validates that the extra data of a header fits in [`MAXIMUM_EXTRA_DATA_SIZE`].
+///
+/// This does not apply to Clique chains, which store the signers and seal in the extra data.
+pub fn validate_extra_data(header: &Header) -> Result<(), HeaderValidationError> {
+    if header.extra_data.len() > MAXIMUM_EXTRA_DATA_SIZE {
+        return Err(HeaderValidationError::ExtraDataTooLong(header.extra_data.len()));
+    }
+    Ok(())
+}
+
+/// Validates the Clique specific fields of a header against its parent.
+///
+/// This checks the layout of the extra data (vanity, checkpoint signers and seal), the vote in
+/// the nonce, the zeroed mix hash, the empty ommers, the difficulty and the block period. It
+/// does not verify the seal itself.
+pub fn validate_clique_header(
+    header: &Header,
+    parent: &Header,
+    config: &CliqueConfig,
+) -> Result<(), HeaderValidationError> {
+    let checkpoint = clique::is_checkpoint(header.number, config);
+
+    if checkpoint && header.beneficiary != IcanAddress::ZERO {
+        return Err(HeaderValidationError::CliqueInvalidCheckpointBeneficiary);
+    }
+
+    if header.nonce != CLIQUE_NONCE_AUTH_VOTE && header.nonce != CLIQUE_NONCE_DROP_VOTE {
+        return Err(HeaderValidationError::CliqueInvalidVote);
+    }
+    if checkpoint && header.nonce != CLIQUE_NONCE_DROP_VOTE {
+        return Err(HeaderValidationError::CliqueInvalidCheckpointVote);
+    }
+
+    let extra_len = header.extra_data.len();
+    if extra_len < CLIQUE_EXTRA_VANITY {
+        return Err(HeaderValidationError::CliqueMissingVanity);
+    }
+    if extra_len < CLIQUE_EXTRA_VANITY + CLIQUE_EXTRA_SEAL {
+        return Err(HeaderValidationError::CliqueMissingSeal);
+    }
+    let signers_len = extra_len - CLIQUE_EXTRA_VANITY - CLIQUE_EXTRA_SEAL;
+    if !checkpoint && signers_len != 0 {
+        return Err(HeaderValidationError::CliqueExtraSigners);
+    }
+    if checkpoint && signers_len % IcanAddress::len_bytes() != 0 {
+        return Err(HeaderValidationError::CliqueInvalidCheckpointSigners);
+    }
+
+    if header.mix_hash != B256::ZERO {
+        return Err(HeaderValidationError::CliqueInvalidMixHash);
+    }
+
+    if header.ommers_hash != EMPTY_OMMER_ROOT_HASH {
+        return Err(HeaderValidationError::CliqueInvalidOmmersHash);
+    }
+
+    if header.number > 0
+        && header.difficulty != U256::from(CLIQUE_DIFF_IN_TURN)
+        && header.difficulty != U256::from(CLIQUE_DIFF_NO_TURN)
+    {
+        return Err(HeaderValidationError::CliqueInvalidDifficulty);
+    }
+
+    let earliest = parent.timestamp.saturating_add(config.period.unwrap_or_default());
+    if header.timestamp < earliest {
+        return Err(HeaderValidationError::CliqueTimestampTooEarly {
+            earliest,
+            timestamp: header.timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Sealable;
+    use base_primitives::{cAddress, Bytes};
+
+    fn parent() -> Sealed<Header> {
+        Header { number: 15, gas_limit: 8_000_000, timestamp: 1_000, ..Default::default() }
+            .seal_slow()
+    }
+
+    fn child(parent: &Sealed<Header>) -> Header {
+        Header {
+            parent_hash: parent.hash(),
+            number: parent.number + 1,
+            gas_limit: parent.gas_limit,
+            gas_used: 21000,
+            timestamp: parent.timestamp + 15,
+            ..Default::default()
+        }
+    }
+
+    fn clique_extra_data(signers: usize) -> Bytes {
+        vec![0u8; CLIQUE_EXTRA_VANITY + signers * IcanAddress::len_bytes() + CLIQUE_EXTRA_SEAL]
+            .into()
+    }
+
+    #[test]
+    fn valid_child() {
+        let parent = parent();
+        let header = child(&parent);
+        assert_eq!(validate_header(&header, &parent, &ChainConfig::default()), Ok(()));
+    }
+
+    #[test]
+    fn continuity() {
+        let parent = parent();
+
+        let header = Header { number: parent.number + 2, ..child(&parent) };
+        assert_eq!(
+            validate_against_parent(&header, &parent),
+            Err(HeaderValidationError::NumberMismatch { parent: 15, number: 17 })
+        );
+
+        let header = Header { parent_hash: B256::ZERO, ..child(&parent) };
+        assert!(matches!(
+            validate_against_parent(&header, &parent),
+            Err(HeaderValidationError::ParentHashMismatch { .. })
+        ));
+
+        let header = Header { timestamp: parent.timestamp, ..child(&parent) };
+        assert_eq!(
+            validate_against_parent(&header, &parent),
+            Err(HeaderValidationError::TimestampNotAfterParent { parent: 1_000, timestamp: 1_000 })
+        );
+    }
+
+    #[test]
+    fn energy_limit() {
+        let parent = parent();
+
+        let header = Header { gas_used: parent.gas_limit + 1, ..child(&parent) };
+        assert!(matches!(
+            validate_against_parent(&header, &parent),
+            Err(HeaderValidationError::EnergyUsedExceedsLimit { .. })
+        ));
+
+        let bound = parent.gas_limit / ENERGY_LIMIT_BOUND_DIVISOR;
+        let header = Header { gas_limit: parent.gas_limit + bound - 1, ..child(&parent) };
+        assert_eq!(validate_against_parent(&header, &parent), Ok(()));
+        let header = Header { gas_limit: parent.gas_limit + bound, ..child(&parent) };
+        assert!(matches!(
+            validate_against_parent(&header, &parent),
+            Err(HeaderValidationError::EnergyLimitChangeTooLarge { .. })
+        ));
+        let header = Header { gas_limit: parent.gas_limit - bound, ..child(&parent) };
+        assert!(matches!(
+            validate_against_parent(&header, &parent),
+            Err(HeaderValidationError::EnergyLimitChangeTooLarge { .. })
+        ));
+
+        let header = Header { gas_limit: MINIMUM_ENERGY_LIMIT - 1, gas_used: 0, ..child(&parent) };
+        assert_eq!(
+            validate_energy_limit(&header, &header),
+            Err(HeaderValidationError::EnergyLimitTooLow(MINIMUM_ENERGY_LIMIT - 1))
+        );
+    }
+
+    #[test]
+    fn extra_data() {
+        let parent = parent();
+        let header =
+            Header { extra_data: vec![0; MAXIMUM_EXTRA_DATA_SIZE + 1].into(), ..child(&parent) };
+        assert_eq!(
+            validate_header(&header, &parent, &ChainConfig::default()),
+            Err(HeaderValidationError::ExtraDataTooLong(MAXIMUM_EXTRA_DATA_SIZE + 1))
+        );
+    }
+
+    #[test]
+    fn clique() {
+        let config = CliqueConfig { period: Some(15), epoch: Some(16) };
+        let chain = ChainConfig { clique: Some(config), ..Default::default() };
+        let parent = parent();
+
+        // block 16 is a checkpoint
+        let checkpoint = Header {
+            extra_data: clique_extra_data(2),
+            difficulty: U256::from(CLIQUE_DIFF_IN_TURN),
+            ..child(&parent)
+        };
+        assert_eq!(validate_header(&checkpoint, &parent, &chain), Ok(()));
+
+        let header = Header { extra_data: clique_extra_data(0), ..checkpoint.clone() };
+        assert_eq!(validate_clique_header(&header, &parent, &config), Ok(()));
+
+        let mut extra_data = clique_extra_data(1).to_vec();
+        extra_data.push(0);
+        let header = Header { extra_data: extra_data.into(), ..checkpoint.clone() };
+        assert_eq!(
+            validate_clique_header(&header, &parent, &config),
+            Err(HeaderValidationError::CliqueInvalidCheckpointSigners)
+        );
+
+        let header = Header {
+            beneficiary: cAddress!("0000d8dA6BF26964aF9D7eEd9e03E53415D37aA96045"),
+            ..checkpoint.clone()
+        };
+        assert_eq!(
+            validate_clique_header(&header, &parent, &config),
+            Err(HeaderValidationError::CliqueInvalidCheckpointBeneficiary)
+        );
+
+        let header = Header { nonce: CLIQUE_NONCE_AUTH_VOTE, ..checkpoint.clone() };
+        assert_eq!(
+            validate_clique_header(&header, &parent, &config),
+            Err(HeaderValidationError::CliqueInvalidCheckpointVote)
+        );
+
+        let header =
+            Header { extra_data: vec![0; CLIQUE_EXTRA_VANITY].into(), ..checkpoint.clone() };
+        assert_eq!(
+            validate_clique_header(&header, &parent, &config),
+            Err(HeaderValidationError::CliqueMissingSeal)
+        );
+
+        let header = Header { difficulty: U256::from(3), ..checkpoint.clone() };
+        assert_eq!(
+            validate_clique_header(&header, &parent, &config),
+            Err(HeaderValidationError::CliqueInvalidDifficulty)
+        );
+
+        let header = Header { timestamp: parent.timestamp + 14, ..checkpoint.clone() };
+        assert_eq!(
+            validate_clique_header(&header, &parent, &config),
+            Err(HeaderValidationError::CliqueTimestampTooEarly {
+                earliest: parent.timestamp + 15,
+                timestamp: parent.timestamp + 14
+            })
+        );
+
+        // block 17 is not a checkpoint
+        let parent = checkpoint.seal_slow();
+        let header = Header {
+            nonce: CLIQUE_NONCE_AUTH_VOTE,
+            beneficiary: cAddress!("0000d8dA6BF26964aF9D7eEd9e03E53415D37aA96045"),
+            extra_data: clique_extra_data(0),
+            difficulty: U256::from(CLIQUE_DIFF_NO_TURN),
+            ..child(&parent)
+        };
+        assert_eq!(validate_header(&header, &parent, &chain), Ok(()));
+
+        let header = Header { extra_data: clique_extra_data(1), ..header };
+        assert_eq!(
+            validate_clique_header(&header, &parent, &config),
+            Err(HeaderValidationError::CliqueExtraSigners)
+        );
+    }
+
+    #[test]
+    fn clique_without_period() {
+        let config = CliqueConfig { period: Some(0), epoch: Some(30_000) };
+        let chain = ChainConfig { clique: Some(config), ..Default::default() };
+        let parent = parent();
+
+        // dev chains seal blocks on demand, several in the same second
+        let header = Header {
+            extra_data: clique_extra_data(0),
+            difficulty: U256::from(CLIQUE_DIFF_IN_TURN),
+            timestamp: parent.timestamp,
+            ..child(&parent)
+        };
+        assert_eq!(validate_header(&header, &parent, &chain), Ok(()));
+
+        let header = Header { timestamp: parent.timestamp - 1, ..header };
+        assert_eq!(
+            validate_header(&header, &parent, &chain),
+            Err(HeaderValidationError::CliqueTimestampTooEarly {
+                earliest: parent.timestamp,
+                timestamp: parent.timestamp - 1
+            })
+        );
+    }
+}
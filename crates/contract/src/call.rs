@@ -358,7 +358,7 @@ impl<T: Transport + Clone, P: Provider<T, N>, D: CallDecoder, N: Network> CallBu
 
     /// Sets the `access_list` in the transaction to the provided value
     pub fn access_list(mut self, access_list: AccessList) -> Self {
-        // self.request.set_access_list(access_list);
+        self.request.set_access_list(access_list);
         self
     }
 
@@ -547,7 +547,7 @@ mod tests {
     use atoms_rpc_client::RpcClient;
     use atoms_rpc_types::AccessListItem;
     use atoms_transport_http::Http;
-    use base_primitives::{address, b256, bytes, cAddress, hex, utils::parse_units, Address, B256};
+    use base_primitives::{address, b256, bytes, cAddress, hex, utils::parse_units, B256};
     use base_ylm_types::ylm;
     use reqwest::{Client, Url};
 
@@ -650,15 +650,15 @@ mod tests {
     #[test]
     fn change_access_list() {
         let access_list = AccessList::from(vec![AccessListItem {
-            address: Address::ZERO,
+            address: IcanAddress::ZERO,
             storage_keys: vec![B256::ZERO],
         }]);
         let call_builder = build_call_builder().access_list(access_list.clone());
-        // assert_eq!(
-        //     call_builder.request.access_list.expect("access_list should be set"),
-        //     access_list,
-        //     "Access list of the transaction should have been set to our access list"
-        // )
+        assert_eq!(
+            call_builder.request.access_list.expect("access_list should be set"),
+            access_list,
+            "Access list of the transaction should have been set to our access list"
+        )
     }
 
     #[test]
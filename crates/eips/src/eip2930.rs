@@ -7,12 +7,13 @@
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
-use base_primitives::{Address, B256, U256};
+use alloy_rlp::{RlpDecodable, RlpDecodableWrapper, RlpEncodable, RlpEncodableWrapper};
+use base_primitives::{IcanAddress, B256, U256};
 use core::{mem, ops::Deref};
 
 /// A list of addresses and storage keys that the transaction plans to access.
 /// Accesses outside the list are possible, but become more expensive.
-#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, RlpDecodable, RlpEncodable)]
 #[cfg_attr(
     any(test, feature = "arbitrary"),
     derive(proptest_derive::Arbitrary, arbitrary::Arbitrary)
@@ -21,7 +22,7 @@ use core::{mem, ops::Deref};
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct AccessListItem {
     /// Account addresses that would be loaded at the start of execution
-    pub address: Address,
+    pub address: IcanAddress,
     /// Keys of storage that would be loaded at the start of execution
     #[cfg_attr(
         any(test, feature = "arbitrary"),
@@ -36,12 +37,12 @@ impl AccessListItem {
     /// Calculates a heuristic for the in-memory size of the [AccessListItem].
     #[inline]
     pub fn size(&self) -> usize {
-        mem::size_of::<Address>() + self.storage_keys.capacity() * mem::size_of::<B256>()
+        mem::size_of::<IcanAddress>() + self.storage_keys.capacity() * mem::size_of::<B256>()
     }
 }
 
 /// AccessList as defined in EIP-2930
-#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, RlpDecodableWrapper, RlpEncodableWrapper)]
 // #[cfg_attr(
 //     any(test, feature = "arbitrary"),
 //     derive(proptest_derive::Arbitrary, arbitrary::Arbitrary)
@@ -79,17 +80,17 @@ impl Deref for AccessList {
 
 impl AccessList {
     /// Converts the list into a vec, expected by revm
-    pub fn flattened(&self) -> Vec<(Address, Vec<U256>)> {
+    pub fn flattened(&self) -> Vec<(IcanAddress, Vec<U256>)> {
         self.flatten().collect()
     }
 
     /// Consumes the type and converts the list into a vec, expected by revm
-    pub fn into_flattened(self) -> Vec<(Address, Vec<U256>)> {
+    pub fn into_flattened(self) -> Vec<(IcanAddress, Vec<U256>)> {
         self.into_flatten().collect()
     }
 
     /// Consumes the type and returns an iterator over the list's addresses and storage keys.
-    pub fn into_flatten(self) -> impl Iterator<Item = (Address, Vec<U256>)> {
+    pub fn into_flatten(self) -> impl Iterator<Item = (IcanAddress, Vec<U256>)> {
         self.0.into_iter().map(|item| {
             (
                 item.address,
@@ -99,7 +100,7 @@ impl AccessList {
     }
 
     /// Returns an iterator over the list's addresses and storage keys.
-    pub fn flatten(&self) -> impl Iterator<Item = (Address, Vec<U256>)> + '_ {
+    pub fn flatten(&self) -> impl Iterator<Item = (IcanAddress, Vec<U256>)> + '_ {
         self.0.iter().map(|item| {
             (
                 item.address,
@@ -109,20 +110,20 @@ impl AccessList {
     }
 
     /// Returns the position of the given address in the access list, if present.
-    fn index_of_address(&self, address: Address) -> Option<usize> {
+    fn index_of_address(&self, address: IcanAddress) -> Option<usize> {
         self.iter().position(|item| item.address == address)
     }
 
     /// Checks if a specific storage slot within an account is present in the access list.
     ///
     /// Returns a tuple with flags for the presence of the account and the slot.
-    pub fn contains_storage(&self, address: Address, slot: B256) -> (bool, bool) {
+    pub fn contains_storage(&self, address: IcanAddress, slot: B256) -> (bool, bool) {
         self.index_of_address(address)
             .map_or((false, false), |idx| (true, self.contains_storage_key_at_index(slot, idx)))
     }
 
     /// Checks if the access list contains the specified address.
-    pub fn contains_address(&self, address: Address) -> bool {
+    pub fn contains_address(&self, address: IcanAddress) -> bool {
         self.iter().any(|item| item.address == address)
     }
 
@@ -136,7 +137,7 @@ impl AccessList {
 
     /// Adds an address to the access list and returns `true` if the operation results in a change,
     /// indicating that the address was not previously present.
-    pub fn add_address(&mut self, address: Address) -> bool {
+    pub fn add_address(&mut self, address: IcanAddress) -> bool {
         !self.contains_address(address) && {
             self.0.push(AccessListItem { address, storage_keys: Vec::new() });
             true
@@ -170,8 +171,8 @@ mod tests {
     #[test]
     fn access_list_serde() {
         let list = AccessList(vec![
-            AccessListItem { address: Address::ZERO, storage_keys: vec![B256::ZERO] },
-            AccessListItem { address: Address::ZERO, storage_keys: vec![B256::ZERO] },
+            AccessListItem { address: IcanAddress::ZERO, storage_keys: vec![B256::ZERO] },
+            AccessListItem { address: IcanAddress::ZERO, storage_keys: vec![B256::ZERO] },
         ]);
         let json = serde_json::to_string(&list).unwrap();
         let list2 = serde_json::from_str::<AccessList>(&json).unwrap();
@@ -182,8 +183,8 @@ mod tests {
     fn access_list_with_gas_used() {
         let list = AccessListWithGasUsed {
             access_list: AccessList(vec![
-                AccessListItem { address: Address::ZERO, storage_keys: vec![B256::ZERO] },
-                AccessListItem { address: Address::ZERO, storage_keys: vec![B256::ZERO] },
+                AccessListItem { address: IcanAddress::ZERO, storage_keys: vec![B256::ZERO] },
+                AccessListItem { address: IcanAddress::ZERO, storage_keys: vec![B256::ZERO] },
             ]),
             gas_used: U256::from(100),
         };
@@ -90,11 +90,11 @@ impl Genesis {
         // Clique EIP: https://eips.ethereum.org/EIPS/eip-225
         //
         // The first 32 bytes are vanity data, so we will populate it with zeros
-        // This is followed by the signer address, which is 20 bytes
-        // There are 65 bytes of zeros after the signer address, which is usually populated with the
-        // proposer signature. Because the genesis does not have a proposer signature, it will be
-        // populated with zeros.
-        let extra_data_bytes = [&[0u8; 32][..], signer_addr.as_slice(), &[0u8; 65][..]].concat();
+        // This is followed by the signer address, which is 22 bytes
+        // There are 171 bytes of zeros after the signer address, which is usually populated with
+        // the proposer ed448 signature and public key. Because the genesis does not have a
+        // proposer signature, it will be populated with zeros.
+        let extra_data_bytes = [&[0u8; 32][..], signer_addr.as_slice(), &[0u8; 171][..]].concat();
         let extra_data = Bytes::from(extra_data_bytes);
 
         Genesis {
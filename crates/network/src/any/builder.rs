@@ -1,9 +1,9 @@
 use std::ops::{Deref, DerefMut};
 
-use atoms_consensus::{Signed, TxLegacy, TypedTransaction};
-use base_primitives::Bytes;
+use atoms_consensus::{TxEnvelope, TypedTransaction};
+use atoms_eips::eip2718::Eip2718Error;
 use atoms_rpc_types::{AccessList, TransactionRequest, WithOtherFields};
-use atoms_signer::Signature;
+use base_primitives::Bytes;
 
 use crate::{any::AnyNetwork, BuildResult, Network, TransactionBuilder, TransactionBuilderError};
 
@@ -108,15 +108,15 @@ impl TransactionBuilder<AnyNetwork> for WithOtherFields<TransactionRequest> {
         self.deref_mut().set_energy_price(gas_price);
     }
 
-    // /// Get the EIP-2930 access list for the transaction.
-    // fn access_list(&self) -> Option<&AccessList> {
-    //     self.deref().access_list()
-    // }
+    /// Get the EIP-2930 access list for the transaction.
+    fn access_list(&self) -> Option<&AccessList> {
+        self.deref().access_list.as_ref()
+    }
 
-    // /// Sets the EIP-2930 access list.
-    // fn set_access_list(&mut self, access_list: AccessList) {
-    //     self.deref_mut().set_access_list(access_list)
-    // }
+    /// Sets the EIP-2930 access list.
+    fn set_access_list(&mut self, access_list: AccessList) {
+        self.deref_mut().access_list = Some(access_list);
+    }
 
     // fn blob_sidecar(&self) -> Option<&BlobTransactionSidecar> {
     //     self.deref().blob_sidecar()
@@ -143,16 +143,19 @@ impl TransactionBuilder<AnyNetwork> for WithOtherFields<TransactionRequest> {
     }
 
     fn build_unsigned(self) -> BuildResult<TypedTransaction, AnyNetwork> {
-        if let Err(missing) = self.complete_legacy() {
+        if let Err(Eip2718Error::UnexpectedType(ty)) = self.preferred_type() {
+            return Err((self, TransactionBuilderError::UnsupportedTransactionType(ty)));
+        }
+        if let Err(missing) = self.deref().complete_type() {
             return Err((self, TransactionBuilderError::InvalidTransactionRequest(missing)));
         }
-        Ok(self.inner.build_typed_tx().expect("checked by complete_legacy"))
+        Ok(self.inner.build_typed_tx().expect("checked by complete_type"))
     }
 
     async fn build<S: crate::NetworkSigner<AnyNetwork>>(
         self,
         signer: &S,
-    ) -> Result<Signed<TxLegacy, Signature>, TransactionBuilderError> {
+    ) -> Result<TxEnvelope, TransactionBuilderError> {
         Ok(signer.sign_request(self).await?)
     }
 }
@@ -18,6 +18,10 @@ pub struct AnyNetwork {
 }
 
 impl Network for AnyNetwork {
+    type TxEnvelope = atoms_consensus::TxEnvelope;
+
+    type UnsignedTx = atoms_consensus::TypedTransaction;
+
     type ReceiptEnvelope = atoms_consensus::AnyReceiptEnvelope;
 
     type Header = atoms_consensus::Header;
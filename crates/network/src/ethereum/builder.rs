@@ -1,8 +1,8 @@
 use crate::{BuildResult, Ethereum, NetworkSigner, TransactionBuilder, TransactionBuilderError};
-use atoms_consensus::{Signed, TxLegacy, TypedTransaction};
+use atoms_consensus::{TxEnvelope, TypedTransaction};
+use atoms_eips::eip2718::Eip2718Error;
+use atoms_rpc_types::{request::TransactionRequest, AccessList, TransactionInput};
 use base_primitives::{Bytes, ChainId, IcanAddress, TxKind, U256};
-use atoms_rpc_types::{request::TransactionRequest, TransactionInput};
-use atoms_signer::Signature;
 
 impl TransactionBuilder<Ethereum> for TransactionRequest {
     fn network_id(&self) -> ChainId {
@@ -105,13 +105,13 @@ impl TransactionBuilder<Ethereum> for TransactionRequest {
         self.energy = Some(energy_limit);
     }
 
-    // fn access_list(&self) -> Option<&AccessList> {
-    //     self.access_list.as_ref()
-    // }
+    fn access_list(&self) -> Option<&AccessList> {
+        self.access_list.as_ref()
+    }
 
-    // fn set_access_list(&mut self, access_list: AccessList) {
-    //     self.access_list = Some(access_list);
-    // }
+    fn set_access_list(&mut self, access_list: AccessList) {
+        self.access_list = Some(access_list);
+    }
 
     // fn blob_sidecar(&self) -> Option<&BlobTransactionSidecar> {
     //     self.sidecar.as_ref()
@@ -123,7 +123,7 @@ impl TransactionBuilder<Ethereum> for TransactionRequest {
     // }
 
     fn complete_type(&self) -> Result<(), Vec<&'static str>> {
-        self.complete_legacy()
+        TransactionRequest::complete_preferred(self)
     }
 
     fn can_submit(&self) -> bool {
@@ -151,16 +151,19 @@ impl TransactionBuilder<Ethereum> for TransactionRequest {
     fn prep_for_submission(&mut self) {}
 
     fn build_unsigned(self) -> BuildResult<TypedTransaction, Ethereum> {
-        if let Err(missing) = self.complete_legacy() {
+        if let Err(Eip2718Error::UnexpectedType(ty)) = self.preferred_type() {
+            return Err((self, TransactionBuilderError::UnsupportedTransactionType(ty)));
+        }
+        if let Err(missing) = TransactionRequest::complete_preferred(&self) {
             return Err((self, TransactionBuilderError::InvalidTransactionRequest(missing)));
         }
-        Ok(self.build_typed_tx().expect("checked by complete_legacy"))
+        Ok(self.build_typed_tx().expect("checked by complete_preferred"))
     }
 
     async fn build<S: NetworkSigner<Ethereum>>(
         self,
         signer: &S,
-    ) -> Result<Signed<TxLegacy, Signature>, TransactionBuilderError> {
+    ) -> Result<TxEnvelope, TransactionBuilderError> {
         Ok(signer.sign_request(self).await?)
     }
 }
@@ -169,8 +172,8 @@ impl TransactionBuilder<Ethereum> for TransactionRequest {
 mod tests {
     use crate::{TransactionBuilder, TransactionBuilderError};
     use atoms_consensus::{TxLegacy, TypedTransaction};
-    use base_primitives::{Address, IcanAddress};
     use atoms_rpc_types::{AccessList, TransactionRequest};
+    use base_primitives::{Address, IcanAddress};
 
     #[test]
     fn from_legacy_to_tx_req() {
@@ -202,4 +205,20 @@ mod tests {
         assert!(errors.contains(&"energy_limit"));
         assert!(errors.contains(&"network_id"));
     }
+
+    #[test]
+    fn unsupported_transaction_type() {
+        let tx = TxLegacy {
+            network_id: 1,
+            nonce: 0,
+            energy_limit: 21_000,
+            to: IcanAddress::ZERO.into(),
+            energy_price: 1,
+            ..Default::default()
+        };
+        let request = TransactionRequest::from(tx).transaction_type(2);
+
+        let error = request.build_unsigned().unwrap_err();
+        assert!(matches!(error, (_, TransactionBuilderError::UnsupportedTransactionType(2))));
+    }
 }
@@ -3,7 +3,7 @@ use crate::{Network, ReceiptResponse};
 mod builder;
 
 mod signer;
-pub use signer::CoreSigner;
+pub use signer::{ArcTxSigner, CoreSigner};
 
 /// Types for a mainnet-like Core network.
 #[derive(Clone, Copy, Debug)]
@@ -12,6 +12,10 @@ pub struct Ethereum {
 }
 
 impl Network for Ethereum {
+    type TxEnvelope = atoms_consensus::TxEnvelope;
+
+    type UnsignedTx = atoms_consensus::TypedTransaction;
+
     type ReceiptEnvelope = atoms_consensus::AnyReceiptEnvelope;
 
     type Header = atoms_consensus::Header;
@@ -1,22 +1,39 @@
 use crate::{Network, NetworkSigner, TxSigner};
 use async_trait::async_trait;
-use atoms_consensus::{SignableTransaction, Signed, TxLegacy, TypedTransaction};
+use atoms_consensus::{SignableTransaction, TxEnvelope, TypedTransaction};
 use atoms_signer::Signature;
 use base_primitives::IcanAddress;
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+/// A shared, type-erased transaction signer.
+pub type ArcTxSigner = Arc<dyn TxSigner<Signature> + Send + Sync + 'static>;
 
 /// A signer capable of signing any transaction for the Core network.
+///
+/// The set of signers is shared between all clones of a `CoreSigner`, and can be modified while
+/// it is in use: signers registered or removed through one clone, e.g. the one held by a provider,
+/// are immediately visible to all the others.
 #[derive(Clone, Default)]
 pub struct CoreSigner {
+    inner: Arc<RwLock<SignerSet>>,
+}
+
+/// The signers of a [`CoreSigner`], indexed by address.
+#[derive(Default)]
+struct SignerSet {
     default: IcanAddress,
-    secp_signers: BTreeMap<IcanAddress, Arc<dyn TxSigner<Signature> + Send + Sync>>,
+    signers: BTreeMap<IcanAddress, ArcTxSigner>,
 }
 
 impl std::fmt::Debug for CoreSigner {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let set = self.read();
         f.debug_struct("CoreSigner")
-            .field("default_signer", &self.default)
-            .field("credentials", &self.secp_signers.len())
+            .field("default_signer", &set.default)
+            .field("credentials", &set.signers.len())
             .finish()
     }
 }
@@ -36,21 +53,33 @@ impl CoreSigner {
     where
         S: TxSigner<Signature> + Send + Sync + 'static,
     {
-        let mut this = Self::default();
+        let this = Self::default();
         this.register_default_signer(signer);
         this
     }
 
+    // The lock is never held while calling into a signer, so it can't be poisoned by one.
+    fn read(&self) -> RwLockReadGuard<'_, SignerSet> {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, SignerSet> {
+        self.inner.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
     /// Register a new signer on this object. This signer will be used to sign
     /// [`TransactionRequest`] and [`TypedTransaction`] object that specify the
     /// signer's address in the `from` field.
     ///
+    /// Replaces and returns the signer previously registered for the same address, if any.
+    ///
     /// [`TransactionRequest`]: atoms_rpc_types::TransactionRequest
-    pub fn register_signer<S>(&mut self, signer: S)
+    pub fn register_signer<S>(&self, signer: S) -> Option<ArcTxSigner>
     where
         S: TxSigner<Signature> + Send + Sync + 'static,
     {
-        self.secp_signers.insert(signer.address(), Arc::new(signer));
+        let address = signer.address();
+        self.write().signers.insert(address, Arc::new(signer))
     }
 
     /// Register a new signer on this object, and set it as the default signer.
@@ -58,26 +87,49 @@ impl CoreSigner {
     /// [`TypedTransaction`] objects that do not specify a signer address in the
     /// `from` field.
     ///
+    /// Replaces and returns the signer previously registered for the same address, if any.
+    ///
     /// [`TransactionRequest`]: atoms_rpc_types::TransactionRequest
-    pub fn register_default_signer<S>(&mut self, signer: S)
+    pub fn register_default_signer<S>(&self, signer: S) -> Option<ArcTxSigner>
     where
         S: TxSigner<Signature> + Send + Sync + 'static,
     {
-        self.default = signer.address();
-        self.register_signer(signer);
+        let address = signer.address();
+        let mut set = self.write();
+        set.default = address;
+        set.signers.insert(address, Arc::new(signer))
     }
 
-    /// Get the default signer.
-    pub fn default_signer(&self) -> Arc<dyn TxSigner<Signature> + Send + Sync + 'static> {
-        self.secp_signers.get(&self.default).cloned().expect("invalid signer")
+    /// Remove the signer for the given address, returning it if it was registered.
+    ///
+    /// If it was the default signer, the default address is kept, and transactions that do not
+    /// specify a `from` address can't be signed until a new default signer is set.
+    pub fn remove_signer(&self, address: &IcanAddress) -> Option<ArcTxSigner> {
+        self.write().signers.remove(address)
+    }
+
+    /// Set the registered signer for the given address as the default signer.
+    ///
+    /// Returns `false`, leaving the default signer unchanged, if there is no signer registered for
+    /// the address.
+    pub fn set_default_signer(&self, address: IcanAddress) -> bool {
+        let mut set = self.write();
+        if !set.signers.contains_key(&address) {
+            return false;
+        }
+        set.default = address;
+        true
+    }
+
+    /// Get the default signer, if it is registered.
+    pub fn default_signer(&self) -> Option<ArcTxSigner> {
+        let set = self.read();
+        set.signers.get(&set.default).cloned()
     }
 
     /// Get the signer for the given address.
-    pub fn signer_by_address(
-        &self,
-        address: IcanAddress,
-    ) -> Option<Arc<dyn TxSigner<Signature> + Send + Sync + 'static>> {
-        self.secp_signers.get(&address).cloned()
+    pub fn signer_by_address(&self, address: IcanAddress) -> Option<ArcTxSigner> {
+        self.read().signers.get(&address).cloned()
     }
 
     async fn sign_transaction_inner(
@@ -98,30 +150,94 @@ impl CoreSigner {
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl<N> NetworkSigner<N> for CoreSigner
 where
-    N: Network,
+    N: Network<UnsignedTx = TypedTransaction, TxEnvelope = TxEnvelope>,
 {
     fn default_signer_address(&self) -> IcanAddress {
-        self.default
+        self.read().default
     }
 
     fn has_signer_for(&self, address: &IcanAddress) -> bool {
-        self.secp_signers.contains_key(address)
+        self.read().signers.contains_key(address)
     }
 
     fn signer_addresses(&self) -> impl Iterator<Item = IcanAddress> {
-        self.secp_signers.keys().copied()
+        // collected, as the set may change while the iterator is in use
+        self.read().signers.keys().copied().collect::<Vec<_>>().into_iter()
     }
 
     async fn sign_transaction_from(
         &self,
         sender: IcanAddress,
         tx: TypedTransaction,
-    ) -> atoms_signer::Result<Signed<TxLegacy, Signature>> {
+    ) -> atoms_signer::Result<TxEnvelope> {
         match tx {
             TypedTransaction::Legacy(mut t) => {
                 let sig = self.sign_transaction_inner(sender, &mut t).await?;
                 Ok(t.into_signed(sig).into())
             }
+            TypedTransaction::Eip2930(mut t) => {
+                let sig = self.sign_transaction_inner(sender, &mut t).await?;
+                Ok(t.into_signed(sig).into())
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ethereum;
+    use atoms_consensus::TxLegacy;
+    use atoms_signer_wallet::LocalWallet;
+
+    fn tx() -> TypedTransaction {
+        TxLegacy {
+            network_id: 1,
+            nonce: 0,
+            energy_price: 1,
+            energy_limit: 21_000,
+            to: IcanAddress::ZERO.into(),
+            ..Default::default()
+        }
+        .into()
+    }
+
+    #[tokio::test]
+    async fn shared_between_clones() {
+        let first = LocalWallet::random(1);
+        let second = LocalWallet::random(1);
+
+        let signer = CoreSigner::new(first.clone());
+        let clone = signer.clone();
+        assert!(clone.register_signer(second.clone()).is_none());
+        assert!(NetworkSigner::<Ethereum>::has_signer_for(&signer, &second.address()));
+
+        let envelope =
+            NetworkSigner::<Ethereum>::sign_transaction_from(&signer, second.address(), tx())
+                .await
+                .unwrap();
+        assert_eq!(envelope.recover_signer(1).unwrap(), second.address());
+
+        assert!(clone.set_default_signer(second.address()));
+        assert_eq!(NetworkSigner::<Ethereum>::default_signer_address(&signer), second.address());
+        assert!(!clone.set_default_signer(IcanAddress::ZERO));
+        assert_eq!(NetworkSigner::<Ethereum>::default_signer_address(&signer), second.address());
+    }
+
+    #[tokio::test]
+    async fn remove_signer() {
+        let wallet = LocalWallet::random(1);
+        let signer = CoreSigner::new(wallet.clone());
+
+        assert!(signer.clone().remove_signer(&wallet.address()).is_some());
+        assert!(signer.remove_signer(&wallet.address()).is_none());
+        assert!(signer.default_signer().is_none());
+        assert_eq!(NetworkSigner::<Ethereum>::signer_addresses(&signer).count(), 0);
+        assert!(NetworkSigner::<Ethereum>::sign_transaction(&signer, tx()).await.is_err());
+
+        let replacement = LocalWallet::random(1);
+        assert!(signer.register_default_signer(replacement.clone()).is_none());
+        let envelope = NetworkSigner::<Ethereum>::sign_transaction(&signer, tx()).await.unwrap();
+        assert_eq!(envelope.recover_signer(1).unwrap(), replacement.address());
+    }
+}
@@ -24,12 +24,12 @@ use core::fmt::{Debug, Display};
 
 mod transaction;
 pub use transaction::{
-    BuildResult, NetworkSigner, TransactionBuilder, TransactionBuilderError, TxSigner,
-    TxSignerSync, Unbuilt,
+    BuildResult, NetworkSigner, PolicySigner, SigningPolicy, TransactionBuilder,
+    TransactionBuilderError, TxSigner, TxSignerSync, Unbuilt,
 };
 
 mod ethereum;
-pub use ethereum::{CoreSigner, Ethereum};
+pub use ethereum::{ArcTxSigner, CoreSigner, Ethereum};
 
 mod any;
 pub use any::AnyNetwork;
@@ -54,6 +54,15 @@ pub trait ReceiptResponse {
 pub trait Network: Debug + Clone + Copy + Sized + Send + Sync + 'static {
     // -- Consensus types --
 
+    /// The network transaction envelope type, i.e. a signed transaction of any of the network's
+    /// transaction types.
+    #[doc(alias = "TransactionEnvelope")]
+    type TxEnvelope: Eip2718Envelope + Clone + Debug + PartialEq + Eq + Send + Sync;
+
+    /// An enum over the various unsigned transaction types of the network.
+    #[doc(alias = "UnsignedTransaction")]
+    type UnsignedTx: From<Self::TxEnvelope> + Debug + Send + Sync;
+
     /// The network receipt envelope type.
     type ReceiptEnvelope: TxReceipt;
 
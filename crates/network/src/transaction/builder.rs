@@ -1,8 +1,7 @@
 use super::signer::NetworkSigner;
 use crate::Network;
-use atoms_consensus::{Signed, TxLegacy, TypedTransaction};
+use atoms_consensus::calculate_intrinsic_energy;
 use atoms_rpc_types::AccessList;
-use atoms_signer::Signature;
 use base_primitives::{Bytes, ChainId, IcanAddress, TxKind, B1368, U256};
 use base_ylm_types::YlmCall;
 use futures_utils_wasm::impl_future;
@@ -20,6 +19,10 @@ pub enum TransactionBuilderError {
     #[error("Transaction can't be built due to missing keys: {0:?}")]
     InvalidTransactionRequest(Vec<&'static str>),
 
+    /// The transaction type of the request is not supported.
+    #[error("Unsupported transaction type: {0}")]
+    UnsupportedTransactionType(u8),
+
     /// Signer cannot produce signature type required for transaction.
     #[error("Signer cannot produce signature type required for transaction")]
     UnsupportedSignatureType,
@@ -271,17 +274,30 @@ pub trait TransactionBuilder<N: Network>: Default + Sized + Send + Sync + 'stati
         self
     }
 
-    // /// Get the EIP-2930 access list for the transaction.
-    // fn access_list(&self) -> Option<&AccessList>;
+    /// Calculate the intrinsic energy of the transaction: the minimum energy limit it can be sent
+    /// with.
+    ///
+    /// See [`calculate_intrinsic_energy`].
+    fn intrinsic_energy(&self) -> u128 {
+        let is_create = self.kind().is_some_and(|kind| kind.is_create());
+        calculate_intrinsic_energy(
+            self.input().map_or(&[][..], |input| &input[..]),
+            is_create,
+            self.access_list().map_or(&[][..], |access_list| &access_list[..]),
+        )
+    }
 
-    // /// Sets the EIP-2930 access list.
-    // fn set_access_list(&mut self, access_list: AccessList);
+    /// Get the EIP-2930 access list for the transaction.
+    fn access_list(&self) -> Option<&AccessList>;
 
-    // /// Builder-pattern method for setting the access list.
-    // fn with_access_list(mut self, access_list: AccessList) -> Self {
-    //     self.set_access_list(access_list);
-    //     self
-    // }
+    /// Sets the EIP-2930 access list.
+    fn set_access_list(&mut self, access_list: AccessList);
+
+    /// Builder-pattern method for setting the access list.
+    fn with_access_list(mut self, access_list: AccessList) -> Self {
+        self.set_access_list(access_list);
+        self
+    }
 
     // /// Gets the EIP-4844 blob sidecar of the transaction.
     // fn blob_sidecar(&self) -> Option<&BlobTransactionSidecar>;
@@ -326,11 +342,11 @@ pub trait TransactionBuilder<N: Network>: Default + Sized + Send + Sync + 'stati
     fn prep_for_submission(&mut self);
 
     /// Build an unsigned, but typed, transaction.
-    fn build_unsigned(self) -> BuildResult<TypedTransaction, N>;
+    fn build_unsigned(self) -> BuildResult<N::UnsignedTx, N>;
 
     /// Build a signed transaction.
     fn build<S: NetworkSigner<N>>(
         self,
         signer: &S,
-    ) -> impl_future!(<Output = Result<Signed<TxLegacy, Signature>, TransactionBuilderError>>);
+    ) -> impl_future!(<Output = Result<N::TxEnvelope, TransactionBuilderError>>);
 }
@@ -3,3 +3,6 @@ pub use builder::{BuildResult, TransactionBuilder, TransactionBuilderError, Unbu
 
 mod signer;
 pub use signer::{NetworkSigner, TxSigner, TxSignerSync};
+
+mod policy;
+pub use policy::{PolicySigner, SigningPolicy};
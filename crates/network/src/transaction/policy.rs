@@ -0,0 +1,468 @@
+use crate::{Network, NetworkSigner, TxSigner};
+use async_trait::async_trait;
+use atoms_consensus::{SignableTransaction, Transaction};
+use atoms_signer::{PolicyViolation, Signature};
+use base_primitives::{ChainId, IcanAddress, Selector, TxKind, U256};
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// Rules enforced by a [`PolicySigner`] before signing a transaction.
+///
+/// All rules are disabled by default.
+#[derive(Clone, Debug, Default)]
+pub struct SigningPolicy {
+    /// The allowed destinations, if restricted.
+    destinations: Option<HashSet<IcanAddress>>,
+    /// Whether contract creations are allowed when destinations are restricted.
+    allow_create: bool,
+    /// The per-transaction value cap.
+    max_value: Option<U256>,
+    /// The value cap of the rolling window, and its duration.
+    window_cap: Option<(U256, Duration)>,
+    /// The energy price ceiling.
+    max_energy_price: Option<u128>,
+    /// The allowed function selectors, if restricted.
+    selectors: Option<HashSet<Selector>>,
+    /// The pinned network ID.
+    network_id: Option<ChainId>,
+}
+
+impl SigningPolicy {
+    /// Creates a new policy, allowing all transactions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows transactions to the given address. Once a destination is allowed, transactions to
+    /// other addresses and contract creations are rejected.
+    pub fn allow_destination(mut self, address: IcanAddress) -> Self {
+        self.destinations.get_or_insert_with(Default::default).insert(address);
+        self
+    }
+
+    /// Allows transactions to the given addresses. See
+    /// [`allow_destination`](Self::allow_destination).
+    pub fn allow_destinations(mut self, addresses: impl IntoIterator<Item = IcanAddress>) -> Self {
+        self.destinations.get_or_insert_with(Default::default).extend(addresses);
+        self
+    }
+
+    /// Allows contract creations when destinations are restricted.
+    pub const fn allow_create(mut self) -> Self {
+        self.allow_create = true;
+        self
+    }
+
+    /// Caps the value of each transaction, in ore.
+    pub const fn max_value(mut self, max: U256) -> Self {
+        self.max_value = Some(max);
+        self
+    }
+
+    /// Caps the total value of the transactions signed in any rolling window of the given
+    /// duration, in ore.
+    ///
+    /// The value of a transaction counts towards the cap once it is signed, even if it is never
+    /// sent.
+    pub const fn window_cap(mut self, cap: U256, window: Duration) -> Self {
+        self.window_cap = Some((cap, window));
+        self
+    }
+
+    /// Caps the energy price of each transaction.
+    pub const fn max_energy_price(mut self, max: u128) -> Self {
+        self.max_energy_price = Some(max);
+        self
+    }
+
+    /// Allows calls of the function with the given selector. Once a selector is allowed, calls of
+    /// other functions are rejected. Transactions without input, i.e. plain transfers, are always
+    /// allowed.
+    ///
+    /// The input of contract creations is init code, not a call, so this rule doesn't apply to
+    /// them: use [`allow_destination`](Self::allow_destination) to reject them.
+    pub fn allow_selector(mut self, selector: impl Into<Selector>) -> Self {
+        self.selectors.get_or_insert_with(Default::default).insert(selector.into());
+        self
+    }
+
+    /// Only allows transactions for the given network.
+    pub const fn pin_network_id(mut self, network_id: ChainId) -> Self {
+        self.network_id = Some(network_id);
+        self
+    }
+
+    /// Checks the stateless rules of the policy against the transaction.
+    pub fn check<T: Transaction + ?Sized>(&self, tx: &T) -> Result<(), PolicyViolation> {
+        if let Some(pinned) = self.network_id {
+            if tx.chain_id() != pinned {
+                return Err(PolicyViolation::NetworkIdMismatch { pinned, tx: tx.chain_id() });
+            }
+        }
+
+        if let Some(destinations) = &self.destinations {
+            match tx.to() {
+                TxKind::Call(to) if !destinations.contains(&to) => {
+                    return Err(PolicyViolation::DestinationNotAllowed(to));
+                }
+                TxKind::Create if !self.allow_create => {
+                    return Err(PolicyViolation::CreateNotAllowed);
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(max) = self.max_value {
+            if tx.value() > max {
+                return Err(PolicyViolation::ValueTooHigh { value: tx.value(), max });
+            }
+        }
+
+        if let (Some(max), Some(energy_price)) = (self.max_energy_price, tx.gas_price()) {
+            if energy_price > max {
+                return Err(PolicyViolation::EnergyPriceTooHigh { energy_price, max });
+            }
+        }
+
+        // the input of contract creations is init code, which has no selector
+        if let (Some(selectors), TxKind::Call(_)) = (&self.selectors, tx.to()) {
+            let input = tx.input();
+            if !input.is_empty() {
+                let selector = input
+                    .get(..4)
+                    .map(Selector::from_slice)
+                    .ok_or(PolicyViolation::MissingSelector)?;
+                if !selectors.contains(&selector) {
+                    return Err(PolicyViolation::SelectorNotAllowed(selector));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A signer enforcing a [`SigningPolicy`] before delegating to an inner [`TxSigner`] or
+/// [`NetworkSigner`].
+///
+/// Transactions violating the policy are rejected with
+/// [`Error::PolicyViolation`](atoms_signer::Error::PolicyViolation). The rolling window of the
+/// value cap is shared between clones of the signer.
+///
+/// # Examples
+///
+/// ```
+/// use atoms_network::{CoreSigner, PolicySigner, SigningPolicy};
+/// use base_primitives::U256;
+/// use std::time::Duration;
+///
+/// # fn f(signer: CoreSigner) {
+/// let policy = SigningPolicy::new()
+///     .max_value(U256::from(10).pow(U256::from(18)))
+///     .window_cap(U256::from(10).pow(U256::from(19)), Duration::from_secs(3600))
+///     .pin_network_id(1);
+/// let signer = PolicySigner::new(signer, policy);
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PolicySigner<S> {
+    /// The inner signer.
+    inner: S,
+    /// The enforced policy.
+    policy: SigningPolicy,
+    /// The values signed in the current window.
+    window: Arc<Mutex<Window>>,
+}
+
+/// The values signed in the rolling window of a [`PolicySigner`].
+#[derive(Debug, Default)]
+struct Window {
+    /// The ID of the next reservation.
+    next_id: u64,
+    /// The reserved values, oldest first.
+    spent: VecDeque<Reservation>,
+}
+
+/// A value reserved in the rolling window for a transaction.
+#[derive(Debug)]
+struct Reservation {
+    /// The ID of the reservation, used to release it.
+    id: u64,
+    /// The time the transaction was checked.
+    time: Instant,
+    /// The value of the transaction.
+    value: U256,
+}
+
+impl<S> PolicySigner<S> {
+    /// Wraps the signer, enforcing the policy.
+    pub fn new(inner: S, policy: SigningPolicy) -> Self {
+        Self { inner, policy, window: Default::default() }
+    }
+
+    /// Returns the inner signer.
+    pub const fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Returns the enforced policy.
+    pub const fn policy(&self) -> &SigningPolicy {
+        &self.policy
+    }
+
+    /// Checks the transaction against the policy, and reserves its value in the rolling window,
+    /// returning the ID of the reservation.
+    fn check<T: Transaction + ?Sized>(&self, tx: &T) -> Result<Option<u64>, PolicyViolation> {
+        self.policy.check(tx)?;
+
+        let Some((cap, duration)) = self.policy.window_cap else { return Ok(None) };
+        let now = Instant::now();
+        let value = tx.value();
+        let mut window = self.window.lock().unwrap_or_else(PoisonError::into_inner);
+        while window.spent.front().is_some_and(|spent| now.duration_since(spent.time) >= duration) {
+            window.spent.pop_front();
+        }
+        let total =
+            window.spent.iter().fold(U256::ZERO, |total, spent| total.saturating_add(spent.value));
+        if total.saturating_add(value) > cap {
+            return Err(PolicyViolation::WindowCapExceeded { value, spent: total, cap });
+        }
+        let id = window.next_id;
+        window.next_id += 1;
+        window.spent.push_back(Reservation { id, time: now, value });
+        Ok(Some(id))
+    }
+
+    /// Releases the value reserved for a transaction which could not be signed.
+    fn release(&self, reservation: Option<u64>) {
+        let Some(id) = reservation else { return };
+        let mut window = self.window.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(index) = window.spent.iter().position(|spent| spent.id == id) {
+            window.spent.remove(index);
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<S> TxSigner<Signature> for PolicySigner<S>
+where
+    S: TxSigner<Signature> + Send + Sync,
+{
+    fn address(&self) -> IcanAddress {
+        self.inner.address()
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<Signature>,
+    ) -> atoms_signer::Result<Signature> {
+        let reservation = self.check(&*tx)?;
+        let result = self.inner.sign_transaction(tx).await;
+        if result.is_err() {
+            self.release(reservation);
+        }
+        result
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<N, S> NetworkSigner<N> for PolicySigner<S>
+where
+    N: Network,
+    N::UnsignedTx: Transaction,
+    S: NetworkSigner<N>,
+{
+    fn default_signer_address(&self) -> IcanAddress {
+        self.inner.default_signer_address()
+    }
+
+    fn has_signer_for(&self, address: &IcanAddress) -> bool {
+        self.inner.has_signer_for(address)
+    }
+
+    fn signer_addresses(&self) -> impl Iterator<Item = IcanAddress> {
+        self.inner.signer_addresses()
+    }
+
+    async fn sign_transaction_from(
+        &self,
+        sender: IcanAddress,
+        tx: N::UnsignedTx,
+    ) -> atoms_signer::Result<N::TxEnvelope> {
+        let reservation = self.check(&tx)?;
+        let result = self.inner.sign_transaction_from(sender, tx).await;
+        if result.is_err() {
+            self.release(reservation);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CoreSigner, Ethereum};
+    use atoms_consensus::{TxLegacy, TypedTransaction};
+    use atoms_signer::Error;
+    use atoms_signer_wallet::LocalWallet;
+    use base_primitives::{bytes, cAddress};
+
+    fn to() -> IcanAddress {
+        cAddress!("0000d8dA6BF26964aF9D7eEd9e03E53415D37aA96045")
+    }
+
+    fn tx(value: u64) -> TxLegacy {
+        TxLegacy {
+            network_id: 1,
+            nonce: 0,
+            energy_price: 10,
+            energy_limit: 50_000,
+            to: to().into(),
+            value: U256::from(value),
+            input: Default::default(),
+        }
+    }
+
+    fn violation(result: atoms_signer::Result<impl std::fmt::Debug>) -> PolicyViolation {
+        match result.unwrap_err() {
+            Error::PolicyViolation(violation) => violation,
+            err => panic!("unexpected error: {err}"),
+        }
+    }
+
+    #[test]
+    fn check_rules() {
+        let policy = SigningPolicy::new()
+            .allow_destination(to())
+            .max_value(U256::from(100))
+            .max_energy_price(10)
+            .allow_selector([0xa9, 0x05, 0x9c, 0xbb])
+            .pin_network_id(1);
+        policy.check(&tx(100)).unwrap();
+
+        let other = IcanAddress::ZERO;
+        assert_eq!(
+            policy.check(&TxLegacy { to: other.into(), ..tx(1) }),
+            Err(PolicyViolation::DestinationNotAllowed(other))
+        );
+        assert_eq!(
+            policy.check(&TxLegacy { to: TxKind::Create, ..tx(1) }),
+            Err(PolicyViolation::CreateNotAllowed)
+        );
+        assert_eq!(
+            policy.check(&tx(101)),
+            Err(PolicyViolation::ValueTooHigh { value: U256::from(101), max: U256::from(100) })
+        );
+        assert_eq!(
+            policy.check(&TxLegacy { energy_price: 11, ..tx(1) }),
+            Err(PolicyViolation::EnergyPriceTooHigh { energy_price: 11, max: 10 })
+        );
+        assert_eq!(
+            policy.check(&TxLegacy { network_id: 3, ..tx(1) }),
+            Err(PolicyViolation::NetworkIdMismatch { pinned: 1, tx: 3 })
+        );
+
+        policy.check(&TxLegacy { input: bytes!("a9059cbb0000"), ..tx(1) }).unwrap();
+        assert_eq!(
+            policy.check(&TxLegacy { input: bytes!("095ea7b30000"), ..tx(1) }),
+            Err(PolicyViolation::SelectorNotAllowed(Selector::new([0x09, 0x5e, 0xa7, 0xb3])))
+        );
+        assert_eq!(
+            policy.check(&TxLegacy { input: bytes!("a905"), ..tx(1) }),
+            Err(PolicyViolation::MissingSelector)
+        );
+    }
+
+    #[test]
+    fn selectors_skip_creates() {
+        let policy = SigningPolicy::new().allow_selector([0xa9, 0x05, 0x9c, 0xbb]);
+
+        // the init code is not a call, whatever its first bytes
+        let create = TxLegacy { to: TxKind::Create, input: bytes!("6080604052"), ..tx(0) };
+        policy.check(&create).unwrap();
+        assert_eq!(
+            policy.check(&TxLegacy { input: bytes!("6080604052"), ..tx(0) }),
+            Err(PolicyViolation::SelectorNotAllowed(Selector::new([0x60, 0x80, 0x60, 0x40])))
+        );
+
+        // creations are still rejected by restricted destinations
+        let policy = policy.allow_destination(to());
+        assert_eq!(policy.check(&create), Err(PolicyViolation::CreateNotAllowed));
+    }
+
+    #[test]
+    fn release_by_id() {
+        let policy = SigningPolicy::new().window_cap(U256::from(100), Duration::from_secs(60));
+        let signer = PolicySigner::new((), policy);
+
+        // reservations of the same value, possibly in the same clock tick
+        let first = signer.check(&tx(30)).unwrap();
+        let second = signer.check(&tx(30)).unwrap();
+        assert_ne!(first, second);
+
+        signer.release(second);
+        let window = signer.window.lock().unwrap();
+        assert_eq!(window.spent.iter().map(|spent| Some(spent.id)).collect::<Vec<_>>(), [first]);
+    }
+
+    #[tokio::test]
+    async fn window_cap() {
+        let wallet = LocalWallet::random(1);
+        let policy = SigningPolicy::new().window_cap(U256::from(100), Duration::from_millis(200));
+        let signer = PolicySigner::new(CoreSigner::new(wallet.clone()), policy);
+        let clone = signer.clone();
+
+        let sign = |signer: &PolicySigner<CoreSigner>, value| {
+            let signer = signer.clone();
+            async move {
+                NetworkSigner::<Ethereum>::sign_transaction(
+                    &signer,
+                    TypedTransaction::from(tx(value)),
+                )
+                .await
+            }
+        };
+
+        let envelope = sign(&signer, 60).await.unwrap();
+        assert_eq!(envelope.recover_signer(1).unwrap(), wallet.address());
+        assert_eq!(
+            violation(sign(&clone, 50).await),
+            PolicyViolation::WindowCapExceeded {
+                value: U256::from(50),
+                spent: U256::from(60),
+                cap: U256::from(100)
+            }
+        );
+        sign(&clone, 40).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        sign(&signer, 100).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn release_on_failure() {
+        let wallet = LocalWallet::random(1);
+        let policy = SigningPolicy::new().window_cap(U256::from(100), Duration::from_secs(60));
+        let signer = PolicySigner::new(wallet, policy);
+
+        // the wallet rejects transactions for another network
+        let mut tx = TxLegacy { network_id: 3, ..tx(100) };
+        assert!(matches!(
+            TxSigner::sign_transaction(&signer, &mut tx).await,
+            Err(Error::TransactionNetworkIdMismatch { .. })
+        ));
+
+        let mut tx = TxLegacy { network_id: 1, ..tx };
+        TxSigner::sign_transaction(&signer, &mut tx).await.unwrap();
+        let mut tx = TxLegacy { value: U256::from(1), ..tx };
+        assert!(matches!(
+            violation(TxSigner::sign_transaction(&signer, &mut tx).await),
+            PolicyViolation::WindowCapExceeded { .. }
+        ));
+    }
+}
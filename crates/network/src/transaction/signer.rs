@@ -1,10 +1,11 @@
 use crate::{Network, TransactionBuilder};
 use async_trait::async_trait;
-use atoms_consensus::{SignableTransaction, Signed, TxLegacy, TypedTransaction};
+use atoms_consensus::SignableTransaction;
 use base_primitives::IcanAddress;
-use atoms_signer::Signature;
 use futures_utils_wasm::impl_future;
 
+pub use atoms_signer::TxSignerSync;
+
 /// A signer capable of signing any transaction for the given network.
 ///
 /// Network crate authors should implement this trait on a type capable of
@@ -34,14 +35,14 @@ pub trait NetworkSigner<N: Network>: std::fmt::Debug + Send + Sync {
     async fn sign_transaction_from(
         &self,
         sender: IcanAddress,
-        tx: TypedTransaction,
-    ) -> atoms_signer::Result<Signed<TxLegacy, Signature>>;
+        tx: N::UnsignedTx,
+    ) -> atoms_signer::Result<N::TxEnvelope>;
 
     /// Asynchronously sign an unsigned transaction.
     fn sign_transaction(
         &self,
-        tx: TypedTransaction,
-    ) -> impl_future!(<Output = atoms_signer::Result<Signed<TxLegacy, Signature>>>) {
+        tx: N::UnsignedTx,
+    ) -> impl_future!(<Output = atoms_signer::Result<N::TxEnvelope>>) {
         self.sign_transaction_from(self.default_signer_address(), tx)
     }
 
@@ -50,7 +51,7 @@ pub trait NetworkSigner<N: Network>: std::fmt::Debug + Send + Sync {
     async fn sign_request(
         &self,
         request: N::TransactionRequest,
-    ) -> atoms_signer::Result<Signed<TxLegacy, Signature>> {
+    ) -> atoms_signer::Result<N::TxEnvelope> {
         let sender = request.from().unwrap_or_else(|| self.default_signer_address());
         let tx = request.build_unsigned().map_err(|(_, e)| atoms_signer::Error::other(e))?;
         self.sign_transaction_from(sender, tx).await
@@ -83,29 +84,3 @@ pub trait TxSigner<Signature> {
         tx: &mut dyn SignableTransaction<Signature>,
     ) -> atoms_signer::Result<Signature>;
 }
-
-/// Synchronous transaction signer,  capable of signing any [`SignableTransaction`] for the given
-/// `Signature` type.
-///
-/// A signer should hold an optional [`ChainId`] value, which is used for [EIP-155] replay
-/// protection.
-///
-/// If `chain_id` is Some, [EIP-155] should be applied to the input transaction in
-/// [`sign_transaction_sync`](Self::sign_transaction_sync), and to the resulting signature in all
-/// the methods. If `chain_id` is None, [EIP-155] should not be applied.
-///
-/// Synchronous signers should also implement [`TxSigner`], as they are always able to by delegating
-/// the asynchronous methods to the synchronous ones.
-///
-/// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
-/// [`ChainId`]: base_primitives::ChainId
-pub trait TxSignerSync<Signature> {
-    /// Get the address of the signer.
-    fn address(&self) -> IcanAddress;
-
-    /// Synchronously sign an unsigned transaction.
-    fn sign_transaction_sync(
-        &self,
-        tx: &mut dyn SignableTransaction<Signature>,
-    ) -> atoms_signer::Result<Signature>;
-}
@@ -486,7 +486,7 @@ impl Gocore {
 
                 // set the extraData field
                 let extra_data_bytes =
-                    [&[0u8; 32][..], clique_addr.as_ref(), &[0u8; 65][..]].concat();
+                    [&[0u8; 32][..], clique_addr.as_ref(), &[0u8; 171][..]].concat();
                 genesis.extra_data = extra_data_bytes.into();
 
                 // we must set the corebase if using clique
@@ -1,14 +1,14 @@
 use crate::{
     fillers::{
-        EnergyFiller, FillerControlFlow, JoinFill, NetworkIdFiller, NonceFiller, SignerFiller,
-        TxFiller,
+        EnergyFiller, EnergyOracle, FillerControlFlow, JoinFill, NetworkIdFiller, NonceFiller,
+        SignerFiller, TxFiller,
     },
     provider::SendableTx,
     Provider, RootProvider,
 };
+use atoms_network::{Ethereum, Network};
 use atoms_rpc_client::{BuiltInConnectionString, ClientBuilder, RpcClient};
 use atoms_transport::{BoxTransport, Transport, TransportError, TransportResult};
-use atoms_network::{Ethereum, Network};
 use libgoldilocks::SigningKey;
 use std::marker::PhantomData;
 
@@ -132,14 +132,27 @@ impl<L, N> ProviderBuilder<L, Identity, N> {
     /// Add preconfigured set of layers handling energy estimation, nonce
     /// management, and network-id fetching.
     pub fn with_recommended_fillers(self) -> ProviderBuilder<L, RecommendFiller, N> {
-        self.filler(EnergyFiller).filler(NonceFiller::default()).filler(NetworkIdFiller::default())
+        self.filler(EnergyFiller::default())
+            .filler(NonceFiller::default())
+            .filler(NetworkIdFiller::default())
     }
 
     /// Add energy estimation to the stack being built.
     ///
     /// See [`EnergyFiller`]
     pub fn with_energy_estimation(self) -> ProviderBuilder<L, JoinFill<Identity, EnergyFiller>, N> {
-        self.filler(EnergyFiller)
+        self.filler(EnergyFiller::default())
+    }
+
+    /// Add energy estimation to the stack being built, pricing transactions with the given
+    /// [`EnergyOracle`].
+    ///
+    /// See [`EnergyFiller`]
+    pub fn with_energy_oracle<O: EnergyOracle>(
+        self,
+        oracle: O,
+    ) -> ProviderBuilder<L, JoinFill<Identity, EnergyFiller<O>>, N> {
+        self.filler(EnergyFiller::with_oracle(oracle))
     }
 
     /// Add nonce management to the stack being built.
@@ -0,0 +1,370 @@
+//! This module extends the Core JSON-RPC provider with the Anvil namespace's RPC methods.
+use crate::Provider;
+use atoms_network::Network;
+use atoms_rpc_types_anvil::{Forking, Metadata, MineOptions, NodeInfo};
+use atoms_transport::{Transport, TransportResult};
+use base_primitives::{Bytes, IcanAddress, TxHash, B256, U256};
+
+/// Anvil namespace rpc interface that gives access to several non-standard RPC methods, used to
+/// control a development node.
+///
+/// The Core fork of Anvil, `shuttle`, serves them under the `shuttle` namespace, alongside the
+/// `evm` namespace shared with other development nodes.
+#[allow(unused, unreachable_pub)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait AnvilApi<N, T>: Send + Sync {
+    /* ---------------------------------------- mining ---------------------------------------- */
+
+    /// Mines a series of blocks.
+    ///
+    /// `interval` is the number of seconds between the timestamps of consecutive blocks, 1 if
+    /// not set.
+    async fn anvil_mine(
+        &self,
+        num_blocks: Option<U256>,
+        interval: Option<U256>,
+    ) -> TransportResult<()>;
+
+    /// Mines a single block, or as many blocks as given in the options.
+    async fn evm_mine(&self, opts: Option<MineOptions>) -> TransportResult<String>;
+
+    /// Returns `true` if automatic mining is enabled, and `false` otherwise.
+    async fn anvil_get_auto_mine(&self) -> TransportResult<bool>;
+
+    /// Enables or disables automatic mining of new blocks with each new transaction submitted to
+    /// the network.
+    async fn anvil_set_auto_mine(&self, enabled: bool) -> TransportResult<()>;
+
+    /// Sets the mining behavior to interval with the given interval in seconds, or disables
+    /// interval mining if `0`.
+    async fn anvil_set_interval_mining(&self, secs: u64) -> TransportResult<()>;
+
+    /// Removes a transaction from the pool, returning its hash if it was in the pool.
+    async fn anvil_drop_transaction(&self, tx_hash: TxHash) -> TransportResult<Option<TxHash>>;
+
+    /// Removes all transactions from the pool.
+    async fn anvil_drop_all_transactions(&self) -> TransportResult<()>;
+
+    /// Removes all transactions sent by the given address from the pool.
+    async fn anvil_remove_pool_transactions(&self, address: IcanAddress) -> TransportResult<()>;
+
+    /* ------------------------------------- account state ------------------------------------ */
+
+    /// Sets the balance of the given address.
+    async fn anvil_set_balance(&self, address: IcanAddress, balance: U256) -> TransportResult<()>;
+
+    /// Sets the nonce of the given address.
+    async fn anvil_set_nonce(&self, address: IcanAddress, nonce: U256) -> TransportResult<()>;
+
+    /// Sets the code of the given address.
+    async fn anvil_set_code(&self, address: IcanAddress, code: Bytes) -> TransportResult<()>;
+
+    /// Writes a single slot of the storage of the given address, returning `true` on success.
+    async fn anvil_set_storage_at(
+        &self,
+        address: IcanAddress,
+        slot: U256,
+        value: B256,
+    ) -> TransportResult<bool>;
+
+    /// Sets the coinbase address of the mined blocks.
+    async fn anvil_set_coinbase(&self, address: IcanAddress) -> TransportResult<()>;
+
+    /* ------------------------------------- impersonation ------------------------------------ */
+
+    /// Sends transactions impersonating the given address, without its private key.
+    async fn anvil_impersonate_account(&self, address: IcanAddress) -> TransportResult<()>;
+
+    /// Stops impersonating the given address.
+    async fn anvil_stop_impersonating_account(&self, address: IcanAddress) -> TransportResult<()>;
+
+    /// Enables or disables the impersonation of all addresses.
+    async fn anvil_auto_impersonate_account(&self, enabled: bool) -> TransportResult<()>;
+
+    /* -------------------------------------- snapshots --------------------------------------- */
+
+    /// Snapshots the state of the blockchain at the current block, returning the ID of the
+    /// snapshot.
+    async fn anvil_snapshot(&self) -> TransportResult<U256>;
+
+    /// Reverts the state of the blockchain to a previous snapshot, returning `true` on success.
+    ///
+    /// The snapshot and all the ones taken after it are deleted.
+    async fn anvil_revert(&self, id: U256) -> TransportResult<bool>;
+
+    /// Serializes the current state of the node, to be restored with
+    /// [`anvil_load_state`](Self::anvil_load_state).
+    async fn anvil_dump_state(&self) -> TransportResult<Bytes>;
+
+    /// Merges a state serialized by [`anvil_dump_state`](Self::anvil_dump_state) into the current
+    /// state of the node, returning `true` on success.
+    async fn anvil_load_state(&self, state: Bytes) -> TransportResult<bool>;
+
+    /* ------------------------------------- time travel -------------------------------------- */
+
+    /// Jumps forward in time by the given number of seconds, returning the total time adjustment.
+    async fn anvil_increase_time(&self, seconds: U256) -> TransportResult<i64>;
+
+    /// Sets the timestamp of the next block.
+    async fn anvil_set_next_block_timestamp(&self, timestamp: u64) -> TransportResult<()>;
+
+    /// Sets the current time of the node, returning the time adjustment.
+    async fn anvil_set_time(&self, timestamp: u64) -> TransportResult<u64>;
+
+    /// Sets an interval, in seconds, between the timestamps of consecutive blocks.
+    async fn anvil_set_block_timestamp_interval(&self, seconds: u64) -> TransportResult<()>;
+
+    /// Removes the interval set by
+    /// [`anvil_set_block_timestamp_interval`](Self::anvil_set_block_timestamp_interval),
+    /// returning `true` if there was one.
+    async fn anvil_remove_block_timestamp_interval(&self) -> TransportResult<bool>;
+
+    /* ----------------------------------- reset and forking ---------------------------------- */
+
+    /// Resets the node to its initial state, or to a fork of another network.
+    async fn anvil_reset(&self, forking: Option<Forking>) -> TransportResult<()>;
+
+    /// Sets the URL of the forked network, without resetting the state of the node.
+    async fn anvil_set_rpc_url(&self, url: String) -> TransportResult<()>;
+
+    /* ----------------------------------------- node ----------------------------------------- */
+
+    /// Returns information about the node.
+    async fn anvil_node_info(&self) -> TransportResult<NodeInfo>;
+
+    /// Returns metadata about the node, such as its instance ID and its snapshots.
+    async fn anvil_metadata(&self) -> TransportResult<Metadata>;
+
+    /// Enables or disables the logging of the node.
+    async fn anvil_set_logging(&self, enabled: bool) -> TransportResult<()>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> AnvilApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn anvil_mine(
+        &self,
+        num_blocks: Option<U256>,
+        interval: Option<U256>,
+    ) -> TransportResult<()> {
+        self.client().request("shuttle_mine", (num_blocks, interval)).await
+    }
+
+    async fn evm_mine(&self, opts: Option<MineOptions>) -> TransportResult<String> {
+        self.client().request("evm_mine", (opts,)).await
+    }
+
+    async fn anvil_get_auto_mine(&self) -> TransportResult<bool> {
+        self.client().request("shuttle_getAutomine", ()).await
+    }
+
+    async fn anvil_set_auto_mine(&self, enabled: bool) -> TransportResult<()> {
+        self.client().request("evm_setAutomine", (enabled,)).await
+    }
+
+    async fn anvil_set_interval_mining(&self, secs: u64) -> TransportResult<()> {
+        self.client().request("evm_setIntervalMining", (secs,)).await
+    }
+
+    async fn anvil_drop_transaction(&self, tx_hash: TxHash) -> TransportResult<Option<TxHash>> {
+        self.client().request("shuttle_dropTransaction", (tx_hash,)).await
+    }
+
+    async fn anvil_drop_all_transactions(&self) -> TransportResult<()> {
+        self.client().request("shuttle_dropAllTransactions", ()).await
+    }
+
+    async fn anvil_remove_pool_transactions(&self, address: IcanAddress) -> TransportResult<()> {
+        self.client().request("shuttle_removePoolTransactions", (address,)).await
+    }
+
+    async fn anvil_set_balance(&self, address: IcanAddress, balance: U256) -> TransportResult<()> {
+        self.client().request("shuttle_setBalance", (address, balance)).await
+    }
+
+    async fn anvil_set_nonce(&self, address: IcanAddress, nonce: U256) -> TransportResult<()> {
+        self.client().request("shuttle_setNonce", (address, nonce)).await
+    }
+
+    async fn anvil_set_code(&self, address: IcanAddress, code: Bytes) -> TransportResult<()> {
+        self.client().request("shuttle_setCode", (address, code)).await
+    }
+
+    async fn anvil_set_storage_at(
+        &self,
+        address: IcanAddress,
+        slot: U256,
+        value: B256,
+    ) -> TransportResult<bool> {
+        self.client().request("shuttle_setStorageAt", (address, slot, value)).await
+    }
+
+    async fn anvil_set_coinbase(&self, address: IcanAddress) -> TransportResult<()> {
+        self.client().request("shuttle_setCoinbase", (address,)).await
+    }
+
+    async fn anvil_impersonate_account(&self, address: IcanAddress) -> TransportResult<()> {
+        self.client().request("shuttle_impersonateAccount", (address,)).await
+    }
+
+    async fn anvil_stop_impersonating_account(&self, address: IcanAddress) -> TransportResult<()> {
+        self.client().request("shuttle_stopImpersonatingAccount", (address,)).await
+    }
+
+    async fn anvil_auto_impersonate_account(&self, enabled: bool) -> TransportResult<()> {
+        self.client().request("shuttle_autoImpersonateAccount", (enabled,)).await
+    }
+
+    async fn anvil_snapshot(&self) -> TransportResult<U256> {
+        self.client().request("evm_snapshot", ()).await
+    }
+
+    async fn anvil_revert(&self, id: U256) -> TransportResult<bool> {
+        self.client().request("evm_revert", (id,)).await
+    }
+
+    async fn anvil_dump_state(&self) -> TransportResult<Bytes> {
+        self.client().request("shuttle_dumpState", ()).await
+    }
+
+    async fn anvil_load_state(&self, state: Bytes) -> TransportResult<bool> {
+        self.client().request("shuttle_loadState", (state,)).await
+    }
+
+    async fn anvil_increase_time(&self, seconds: U256) -> TransportResult<i64> {
+        self.client().request("evm_increaseTime", (seconds,)).await
+    }
+
+    async fn anvil_set_next_block_timestamp(&self, timestamp: u64) -> TransportResult<()> {
+        self.client().request("evm_setNextBlockTimestamp", (timestamp,)).await
+    }
+
+    async fn anvil_set_time(&self, timestamp: u64) -> TransportResult<u64> {
+        self.client().request("evm_setTime", (timestamp,)).await
+    }
+
+    async fn anvil_set_block_timestamp_interval(&self, seconds: u64) -> TransportResult<()> {
+        self.client().request("shuttle_setBlockTimestampInterval", (seconds,)).await
+    }
+
+    async fn anvil_remove_block_timestamp_interval(&self) -> TransportResult<bool> {
+        self.client().request("shuttle_removeBlockTimestampInterval", ()).await
+    }
+
+    async fn anvil_reset(&self, forking: Option<Forking>) -> TransportResult<()> {
+        self.client().request("shuttle_reset", (forking,)).await
+    }
+
+    async fn anvil_set_rpc_url(&self, url: String) -> TransportResult<()> {
+        self.client().request("shuttle_setRpcUrl", (url,)).await
+    }
+
+    async fn anvil_node_info(&self) -> TransportResult<NodeInfo> {
+        self.client().request("shuttle_nodeInfo", ()).await
+    }
+
+    async fn anvil_metadata(&self) -> TransportResult<Metadata> {
+        self.client().request("shuttle_metadata", ()).await
+    }
+
+    async fn anvil_set_logging(&self, enabled: bool) -> TransportResult<()> {
+        self.client().request("shuttle_setLogging", (enabled,)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProviderBuilder;
+    use atoms_rpc_types::BlockId;
+
+    #[tokio::test]
+    async fn test_anvil_mine() {
+        let provider = ProviderBuilder::new().on_anvil();
+
+        let start = provider.get_block_number().await.unwrap();
+        provider.anvil_mine(Some(U256::from(10)), None).await.unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), start + 10);
+
+        provider.evm_mine(None).await.unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), start + 11);
+    }
+
+    #[tokio::test]
+    async fn test_anvil_auto_mine() {
+        let provider = ProviderBuilder::new().on_anvil();
+
+        assert!(provider.anvil_get_auto_mine().await.unwrap());
+        provider.anvil_set_auto_mine(false).await.unwrap();
+        assert!(!provider.anvil_get_auto_mine().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_anvil_set_account_state() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let address = IcanAddress::with_last_byte(16);
+
+        provider.anvil_set_balance(address, U256::from(1337)).await.unwrap();
+        let balance = provider.get_balance(address, BlockId::default()).await.unwrap();
+        assert_eq!(balance, U256::from(1337));
+
+        provider.anvil_set_nonce(address, U256::from(42)).await.unwrap();
+        let nonce = provider.get_transaction_count(address, BlockId::default()).await.unwrap();
+        assert_eq!(nonce, 42);
+
+        let code = Bytes::from_static(&[0xbe, 0xef]);
+        provider.anvil_set_code(address, code.clone()).await.unwrap();
+        assert_eq!(provider.get_code_at(address, BlockId::default()).await.unwrap(), code);
+
+        let value = B256::with_last_byte(1);
+        assert!(provider.anvil_set_storage_at(address, U256::ZERO, value).await.unwrap());
+        let storage = provider.get_storage_at(address, U256::ZERO, BlockId::default()).await;
+        assert_eq!(storage.unwrap(), U256::from(1));
+    }
+
+    #[tokio::test]
+    async fn test_anvil_snapshot_revert() {
+        let provider = ProviderBuilder::new().on_anvil();
+
+        let start = provider.get_block_number().await.unwrap();
+        let id = provider.anvil_snapshot().await.unwrap();
+        provider.anvil_mine(Some(U256::from(5)), None).await.unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), start + 5);
+
+        assert!(provider.anvil_revert(id).await.unwrap());
+        assert_eq!(provider.get_block_number().await.unwrap(), start);
+        assert!(!provider.anvil_revert(id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_anvil_time_travel() {
+        let provider = ProviderBuilder::new().on_anvil();
+
+        let timestamp = 4_000_000_000;
+        provider.anvil_set_next_block_timestamp(timestamp).await.unwrap();
+        provider.evm_mine(None).await.unwrap();
+        let info = provider.anvil_node_info().await.unwrap();
+        assert_eq!(info.current_block_timestamp, timestamp);
+
+        assert!(provider.anvil_increase_time(U256::from(100)).await.unwrap() >= 100);
+    }
+
+    #[tokio::test]
+    async fn test_anvil_reset() {
+        let provider = ProviderBuilder::new().on_anvil();
+
+        provider.anvil_mine(Some(U256::from(3)), None).await.unwrap();
+        provider.anvil_reset(None).await.unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), 0);
+
+        let metadata = provider.anvil_metadata().await.unwrap();
+        assert_eq!(metadata.latest_block_number, 0);
+        assert!(metadata.forked_network.is_none());
+    }
+}
@@ -0,0 +1,133 @@
+//! This module extends the Core JSON-RPC provider with the Miner namespace's RPC methods, and the
+//! `xcb_*` methods used by Cryptore miners.
+use crate::Provider;
+use atoms_network::Network;
+use atoms_rpc_types::Work;
+use atoms_transport::{Transport, TransportResult};
+use base_primitives::{IcanAddress, B256, B64, U128, U64};
+
+/// Gocore only Miner namespace rpc interface, controlling the miner of the node and serving work
+/// to external Cryptore miners.
+#[allow(unused, unreachable_pub)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait MinerApi<N, T>: Send + Sync {
+    /// Starts mining with the given number of threads, or the number of threads the node was
+    /// started with if `None`.
+    async fn miner_start(&self, threads: Option<usize>) -> TransportResult<()>;
+
+    /// Stops mining.
+    async fn miner_stop(&self) -> TransportResult<()>;
+
+    /// Sets the address credited with the rewards of the mined blocks.
+    async fn miner_set_corebase(&self, corebase: IcanAddress) -> TransportResult<bool>;
+
+    /// Sets the minimum energy price of the transactions included in the mined blocks.
+    async fn miner_set_energy_price(&self, energy_price: u128) -> TransportResult<bool>;
+
+    /// Sets the extra data included in the mined blocks.
+    async fn miner_set_extra(&self, extra: &str) -> TransportResult<bool>;
+
+    /// Returns the work of the block currently being mined, to be solved by an external miner.
+    async fn xcb_get_work(&self) -> TransportResult<Work>;
+
+    /// Submits a proof-of-work solution for the given [`Work::pow_hash`], returning whether it was
+    /// accepted.
+    async fn xcb_submit_work(
+        &self,
+        nonce: B64,
+        pow_hash: B256,
+        mix_digest: B256,
+    ) -> TransportResult<bool>;
+
+    /// Reports the hashrate of an external miner, identified by a random `id`.
+    async fn xcb_submit_hashrate(&self, hashrate: u64, id: B256) -> TransportResult<bool>;
+
+    /// Returns the number of hashes per second mined by the node and its external miners.
+    async fn xcb_hashrate(&self) -> TransportResult<u64>;
+
+    /// Returns whether the node is mining.
+    async fn xcb_mining(&self) -> TransportResult<bool>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> MinerApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn miner_start(&self, threads: Option<usize>) -> TransportResult<()> {
+        self.client().request("miner_start", (threads,)).await
+    }
+
+    async fn miner_stop(&self) -> TransportResult<()> {
+        self.client().request("miner_stop", ()).await
+    }
+
+    async fn miner_set_corebase(&self, corebase: IcanAddress) -> TransportResult<bool> {
+        self.client().request("miner_setCorebase", (corebase,)).await
+    }
+
+    async fn miner_set_energy_price(&self, energy_price: u128) -> TransportResult<bool> {
+        self.client().request("miner_setEnergyPrice", (U128::from(energy_price),)).await
+    }
+
+    async fn miner_set_extra(&self, extra: &str) -> TransportResult<bool> {
+        self.client().request("miner_setExtra", (extra,)).await
+    }
+
+    async fn xcb_get_work(&self) -> TransportResult<Work> {
+        self.client().request("xcb_getWork", ()).await
+    }
+
+    async fn xcb_submit_work(
+        &self,
+        nonce: B64,
+        pow_hash: B256,
+        mix_digest: B256,
+    ) -> TransportResult<bool> {
+        self.client().request("xcb_submitWork", (nonce, pow_hash, mix_digest)).await
+    }
+
+    async fn xcb_submit_hashrate(&self, hashrate: u64, id: B256) -> TransportResult<bool> {
+        self.client().request("xcb_submitHashrate", (U64::from(hashrate), id)).await
+    }
+
+    async fn xcb_hashrate(&self) -> TransportResult<u64> {
+        self.client().request("xcb_hashrate", ()).await.map(|rate: U64| rate.to::<u64>())
+    }
+
+    async fn xcb_mining(&self) -> TransportResult<bool> {
+        self.client().request("xcb_mining", ()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProviderBuilder;
+    use atoms_node_bindings::Gocore;
+
+    #[tokio::test]
+    async fn test_miner_settings() {
+        let temp_dir = tempfile::TempDir::with_prefix("gocore-test-").unwrap();
+        let gocore = Gocore::new().disable_discovery().data_dir(temp_dir.path()).spawn();
+        let provider = ProviderBuilder::new().on_http(gocore.endpoint_url());
+
+        assert!(provider.miner_set_corebase(IcanAddress::with_last_byte(1)).await.unwrap());
+        assert!(provider.miner_set_energy_price(1_000_000_000).await.unwrap());
+        assert!(provider.miner_set_extra("atoms").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_hashrate() {
+        let temp_dir = tempfile::TempDir::with_prefix("gocore-test-").unwrap();
+        let gocore = Gocore::new().disable_discovery().data_dir(temp_dir.path()).spawn();
+        let provider = ProviderBuilder::new().on_http(gocore.endpoint_url());
+
+        assert!(!provider.xcb_mining().await.unwrap());
+        assert_eq!(provider.xcb_hashrate().await.unwrap(), 0);
+    }
+}
@@ -3,6 +3,11 @@
 mod admin;
 pub use admin::AdminApi;
 
+#[cfg(feature = "anvil-api")]
+mod anvil;
+#[cfg(feature = "anvil-api")]
+pub use anvil::AnvilApi;
+
 #[cfg(feature = "engine-api")]
 mod engine;
 #[cfg(feature = "engine-api")]
@@ -11,5 +16,14 @@ pub use engine::EngineApi;
 mod debug;
 pub use debug::DebugApi;
 
+mod miner;
+pub use miner::MinerApi;
+
+mod personal;
+pub use personal::PersonalApi;
+
+mod trace;
+pub use trace::{TraceApi, TraceStream};
+
 mod txpool;
 pub use txpool::TxPoolApi;
@@ -0,0 +1,246 @@
+//! This module extends the Core JSON-RPC provider with the Personal namespace's RPC methods.
+use crate::{PendingTransactionBuilder, Provider};
+use atoms_network::Network;
+use atoms_rpc_types::personal::WalletInfo;
+use atoms_transport::{Transport, TransportResult};
+use base_primitives::{hex, Bytes, IcanAddress, Signature, TxHash};
+use std::time::Duration;
+
+/// Gocore only Personal namespace rpc interface, managing the accounts held by the node.
+///
+/// # Note
+///
+/// Go-core only serves these calls over HTTP when started with `--allow-insecure-unlock`.
+#[allow(unused, unreachable_pub)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait PersonalApi<N: Network, T>: Send + Sync {
+    /// Creates a new account in the keystore of the node, encrypted with the given password, and
+    /// returns its address.
+    async fn personal_new_account(&self, password: &str) -> TransportResult<IcanAddress>;
+
+    /// Imports the given raw private key into the keystore of the node, encrypted with the given
+    /// password, and returns the address of the account.
+    async fn personal_import_raw_key(
+        &self,
+        private_key: &[u8],
+        password: &str,
+    ) -> TransportResult<IcanAddress>;
+
+    /// Decrypts the key of the given account, allowing the node to sign with it.
+    ///
+    /// The account stays unlocked for `duration`, rounded up to whole seconds, or for the default
+    /// duration of the node if `None`. A zero duration keeps it unlocked until the node exits.
+    async fn personal_unlock_account(
+        &self,
+        address: IcanAddress,
+        password: &str,
+        duration: Option<Duration>,
+    ) -> TransportResult<bool>;
+
+    /// Removes the decrypted key of the given account from memory.
+    async fn personal_lock_account(&self, address: IcanAddress) -> TransportResult<bool>;
+
+    /// Returns the addresses of all the accounts held by the node.
+    async fn personal_list_accounts(&self) -> TransportResult<Vec<IcanAddress>>;
+
+    /// Returns the wallets managed by the node, along with their status and accounts.
+    async fn personal_list_wallets(&self) -> TransportResult<Vec<WalletInfo>>;
+
+    /// Signs the [EIP-191] prefixed message with the given account, unlocking it with the
+    /// password for the duration of the call.
+    ///
+    /// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+    async fn personal_sign(
+        &self,
+        message: &[u8],
+        address: IcanAddress,
+        password: &str,
+    ) -> TransportResult<Signature>;
+
+    /// Returns the address of the account that signed the [EIP-191] prefixed message with
+    /// [`personal_sign`](Self::personal_sign).
+    ///
+    /// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+    async fn personal_ec_recover(
+        &self,
+        message: &[u8],
+        signature: &Signature,
+    ) -> TransportResult<IcanAddress>;
+
+    /// Signs the transaction with the account of its `from` field, unlocking it with the password
+    /// for the duration of the call, and broadcasts it.
+    ///
+    /// Returns a [`PendingTransactionBuilder`] which can be used to configure how and when to
+    /// await the transaction's confirmation, as for
+    /// [`Provider::send_transaction`](crate::Provider::send_transaction).
+    async fn personal_send_transaction(
+        &self,
+        tx: N::TransactionRequest,
+        password: &str,
+    ) -> TransportResult<PendingTransactionBuilder<'_, T, N>>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> PersonalApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn personal_new_account(&self, password: &str) -> TransportResult<IcanAddress> {
+        self.client().request("personal_newAccount", (password,)).await
+    }
+
+    async fn personal_import_raw_key(
+        &self,
+        private_key: &[u8],
+        password: &str,
+    ) -> TransportResult<IcanAddress> {
+        // the node expects the key hex encoded without prefix
+        self.client().request("personal_importRawKey", (hex::encode(private_key), password)).await
+    }
+
+    async fn personal_unlock_account(
+        &self,
+        address: IcanAddress,
+        password: &str,
+        duration: Option<Duration>,
+    ) -> TransportResult<bool> {
+        let duration = duration.map(unlock_duration_secs);
+        self.client().request("personal_unlockAccount", (address, password, duration)).await
+    }
+
+    async fn personal_lock_account(&self, address: IcanAddress) -> TransportResult<bool> {
+        self.client().request("personal_lockAccount", (address,)).await
+    }
+
+    async fn personal_list_accounts(&self) -> TransportResult<Vec<IcanAddress>> {
+        self.client().request("personal_listAccounts", ()).await
+    }
+
+    async fn personal_list_wallets(&self) -> TransportResult<Vec<WalletInfo>> {
+        self.client().request("personal_listWallets", ()).await
+    }
+
+    async fn personal_sign(
+        &self,
+        message: &[u8],
+        address: IcanAddress,
+        password: &str,
+    ) -> TransportResult<Signature> {
+        let message = Bytes::copy_from_slice(message);
+        self.client().request("personal_sign", (message, address, password)).await
+    }
+
+    async fn personal_ec_recover(
+        &self,
+        message: &[u8],
+        signature: &Signature,
+    ) -> TransportResult<IcanAddress> {
+        let message = Bytes::copy_from_slice(message);
+        self.client().request("personal_ecRecover", (message, signature)).await
+    }
+
+    async fn personal_send_transaction(
+        &self,
+        mut tx: N::TransactionRequest,
+        password: &str,
+    ) -> TransportResult<PendingTransactionBuilder<'_, T, N>> {
+        atoms_network::TransactionBuilder::prep_for_submission(&mut tx);
+        let tx_hash: TxHash =
+            self.client().request("personal_sendTransaction", (tx, password)).await?;
+        Ok(PendingTransactionBuilder::new(self.root(), tx_hash))
+    }
+}
+
+/// Converts the unlock duration to the seconds expected by the node.
+///
+/// Sub-second durations are rounded up, as the node would otherwise treat a duration truncated to
+/// zero as unlocking the account until it exits.
+fn unlock_duration_secs(duration: Duration) -> u64 {
+    duration.as_secs().saturating_add(u64::from(duration.subsec_nanos() > 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProviderBuilder;
+    use atoms_network::TransactionBuilder;
+    use atoms_node_bindings::Gocore;
+    use atoms_rpc_types::{request::TransactionRequest, BlockId};
+    use base_primitives::U256;
+
+    #[test]
+    fn unlock_duration_rounds_up() {
+        assert_eq!(unlock_duration_secs(Duration::ZERO), 0);
+        assert_eq!(unlock_duration_secs(Duration::from_millis(500)), 1);
+        assert_eq!(unlock_duration_secs(Duration::from_secs(60)), 60);
+        assert_eq!(unlock_duration_secs(Duration::from_millis(60_001)), 61);
+        assert_eq!(unlock_duration_secs(Duration::MAX), u64::MAX);
+    }
+
+    #[tokio::test]
+    async fn test_personal_accounts() {
+        let temp_dir = tempfile::TempDir::with_prefix("gocore-test-").unwrap();
+        let gocore =
+            Gocore::new().disable_discovery().insecure_unlock().data_dir(temp_dir.path()).spawn();
+        let provider = ProviderBuilder::new().on_http(gocore.endpoint_url());
+
+        let address = provider.personal_new_account("password").await.unwrap();
+        assert!(provider.personal_list_accounts().await.unwrap().contains(&address));
+
+        let wallets = provider.personal_list_wallets().await.unwrap();
+        let wallet = wallets
+            .iter()
+            .find(|wallet| wallet.accounts.iter().any(|account| account.address == address))
+            .unwrap();
+        assert_eq!(wallet.status, "Locked");
+
+        assert!(provider.personal_unlock_account(address, "wrong", None).await.is_err());
+        let unlocked = provider
+            .personal_unlock_account(address, "password", Some(Duration::from_secs(60)))
+            .await
+            .unwrap();
+        assert!(unlocked);
+        assert!(provider.personal_lock_account(address).await.unwrap());
+
+        let imported = provider.personal_import_raw_key(&[0x42; 57], "password").await.unwrap();
+        assert!(provider.personal_list_accounts().await.unwrap().contains(&imported));
+    }
+
+    #[tokio::test]
+    async fn test_personal_sign_ec_recover() {
+        let temp_dir = tempfile::TempDir::with_prefix("gocore-test-").unwrap();
+        let gocore =
+            Gocore::new().disable_discovery().insecure_unlock().data_dir(temp_dir.path()).spawn();
+        let provider = ProviderBuilder::new().on_http(gocore.endpoint_url());
+
+        let address = provider.personal_new_account("password").await.unwrap();
+        let message = b"hello core";
+        let signature = provider.personal_sign(message, address, "password").await.unwrap();
+        let recovered = provider.personal_ec_recover(message, &signature).await.unwrap();
+        assert_eq!(recovered, address);
+    }
+
+    #[tokio::test]
+    async fn test_personal_send_transaction() {
+        let temp_dir = tempfile::TempDir::with_prefix("gocore-test-").unwrap();
+        let gocore =
+            Gocore::new().insecure_unlock().block_time(1u64).data_dir(temp_dir.path()).spawn();
+        let provider = ProviderBuilder::new().on_http(gocore.endpoint_url());
+
+        // the funded developer account is encrypted with an empty password
+        let from = provider.personal_list_accounts().await.unwrap()[0];
+        let to = provider.personal_new_account("password").await.unwrap();
+
+        let tx = TransactionRequest::default().from(from).to(to).value(U256::from(100));
+        let pending = provider.personal_send_transaction(tx, "").await.unwrap();
+        let tx_hash = *pending.tx_hash();
+        let receipt = pending.get_receipt().await.unwrap();
+        assert_eq!(receipt.transaction_hash, tx_hash);
+        assert_eq!(receipt.from, from);
+        assert_eq!(provider.get_balance(to, BlockId::default()).await.unwrap(), U256::from(100));
+    }
+}
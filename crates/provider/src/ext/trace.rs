@@ -0,0 +1,266 @@
+//! This module extends the Core JSON-RPC provider with the Trace namespace's RPC methods.
+use crate::Provider;
+use async_stream::try_stream;
+use atoms_network::Network;
+use atoms_rpc_types::BlockNumberOrTag;
+use atoms_rpc_types_trace::{
+    filter::TraceFilter,
+    parity::{LocalizedTransactionTrace, TraceResults, TraceResultsWithTransactionHash, TraceType},
+};
+use atoms_transport::{Transport, TransportResult};
+use base_primitives::{Bytes, TxHash};
+
+/// A stream of traces, as returned by [`TraceApi::trace_filter_stream`].
+#[cfg(not(target_arch = "wasm32"))]
+pub type TraceStream<'a> =
+    futures::stream::BoxStream<'a, TransportResult<LocalizedTransactionTrace>>;
+
+/// A stream of traces, as returned by [`TraceApi::trace_filter_stream`].
+#[cfg(target_arch = "wasm32")]
+pub type TraceStream<'a> =
+    futures::stream::LocalBoxStream<'a, TransportResult<LocalizedTransactionTrace>>;
+
+/// Trace namespace rpc interface that gives access to several non-standard RPC methods.
+///
+/// # Note
+///
+/// Not all nodes support these calls.
+#[allow(unused, unreachable_pub)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait TraceApi<N, T>: Send + Sync {
+    /// Returns the traces matching the given filter.
+    ///
+    /// Nodes usually cap the number of traces returned by a single call: use the `after` and
+    /// `count` fields of the filter to page through the results, or
+    /// [`trace_filter_stream`](Self::trace_filter_stream).
+    async fn trace_filter(
+        &self,
+        filter: &TraceFilter,
+    ) -> TransportResult<Vec<LocalizedTransactionTrace>>;
+
+    /// Returns a stream of the traces matching the given filter, requested `page_size` traces at a
+    /// time.
+    ///
+    /// The stream starts at the `after` offset of the filter, and yields at most `count` traces if
+    /// set. It ends after the first error, or once a page comes back empty. A `page_size` of `0`
+    /// is treated as `1`. If the node returns fewer traces than requested, e.g. because it caps the
+    /// results of each call, the next pages request that many traces instead.
+    fn trace_filter_stream(&self, filter: TraceFilter, page_size: u64) -> TraceStream<'_>;
+
+    /// Returns the traces of all the transactions in the given block.
+    async fn trace_block(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> TransportResult<Vec<LocalizedTransactionTrace>>;
+
+    /// Returns the trace at the given position of the trace tree of a transaction.
+    ///
+    /// The `index` is the path of the trace in the tree, e.g. `[0, 1]` for the second subcall of
+    /// the first call.
+    async fn trace_get(
+        &self,
+        hash: TxHash,
+        index: &[usize],
+    ) -> TransportResult<Option<LocalizedTransactionTrace>>;
+
+    /// Replays a transaction, returning the requested traces.
+    async fn trace_replay_transaction(
+        &self,
+        hash: TxHash,
+        trace_type: &[TraceType],
+    ) -> TransportResult<TraceResults>;
+
+    /// Replays all the transactions of the given block, returning the requested traces of each of
+    /// them.
+    async fn trace_replay_block_transactions(
+        &self,
+        block: BlockNumberOrTag,
+        trace_type: &[TraceType],
+    ) -> TransportResult<Vec<TraceResultsWithTransactionHash>>;
+
+    /// Traces a signed, RLP encoded transaction on top of the latest block, without broadcasting
+    /// it.
+    async fn trace_raw_transaction(
+        &self,
+        data: Bytes,
+        trace_type: &[TraceType],
+    ) -> TransportResult<TraceResults>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> TraceApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn trace_filter(
+        &self,
+        filter: &TraceFilter,
+    ) -> TransportResult<Vec<LocalizedTransactionTrace>> {
+        self.client().request("trace_filter", (filter,)).await
+    }
+
+    fn trace_filter_stream(&self, filter: TraceFilter, page_size: u64) -> TraceStream<'_> {
+        Box::pin(try_stream! {
+            let mut pages = Pages::new(&filter, page_size);
+            while let Some(page) = pages.next_filter(&filter) {
+                let traces = self.trace_filter(&page).await?;
+                pages.advance(traces.len() as u64);
+                for trace in traces {
+                    yield trace;
+                }
+            }
+        })
+    }
+
+    async fn trace_block(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> TransportResult<Vec<LocalizedTransactionTrace>> {
+        self.client().request("trace_block", (block,)).await
+    }
+
+    async fn trace_get(
+        &self,
+        hash: TxHash,
+        index: &[usize],
+    ) -> TransportResult<Option<LocalizedTransactionTrace>> {
+        self.client().request("trace_get", (hash, index)).await
+    }
+
+    async fn trace_replay_transaction(
+        &self,
+        hash: TxHash,
+        trace_type: &[TraceType],
+    ) -> TransportResult<TraceResults> {
+        self.client().request("trace_replayTransaction", (hash, trace_type)).await
+    }
+
+    async fn trace_replay_block_transactions(
+        &self,
+        block: BlockNumberOrTag,
+        trace_type: &[TraceType],
+    ) -> TransportResult<Vec<TraceResultsWithTransactionHash>> {
+        self.client().request("trace_replayBlockTransactions", (block, trace_type)).await
+    }
+
+    async fn trace_raw_transaction(
+        &self,
+        data: Bytes,
+        trace_type: &[TraceType],
+    ) -> TransportResult<TraceResults> {
+        self.client().request("trace_rawTransaction", (data, trace_type)).await
+    }
+}
+
+/// The pagination state of [`TraceApi::trace_filter_stream`].
+#[derive(Debug)]
+struct Pages {
+    /// The offset of the next page.
+    offset: u64,
+    /// The number of traces left to request, if capped.
+    remaining: Option<u64>,
+    /// The number of traces requested per page, lowered to the node's cap once a page comes back
+    /// short.
+    page_size: u64,
+    /// Whether the last page came back empty.
+    done: bool,
+}
+
+impl Pages {
+    fn new(filter: &TraceFilter, page_size: u64) -> Self {
+        Self {
+            offset: filter.after.unwrap_or_default(),
+            remaining: filter.count,
+            page_size: page_size.max(1),
+            done: false,
+        }
+    }
+
+    /// Returns the number of traces to request in the next page.
+    fn count(&self) -> u64 {
+        self.remaining.map_or(self.page_size, |remaining| remaining.min(self.page_size))
+    }
+
+    /// Returns the filter requesting the next page, or `None` if there are no more.
+    fn next_filter(&self, filter: &TraceFilter) -> Option<TraceFilter> {
+        let count = self.count();
+        if self.done || count == 0 {
+            return None;
+        }
+        Some(filter.clone().after(self.offset).count(count))
+    }
+
+    /// Records that a page of `received` traces was returned for the last requested filter.
+    fn advance(&mut self, received: u64) {
+        self.done = received == 0;
+        if received < self.page_size {
+            self.page_size = received.max(1);
+        }
+        self.offset += received;
+        if let Some(remaining) = &mut self.remaining {
+            *remaining = remaining.saturating_sub(received);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProviderBuilder;
+
+    #[test]
+    fn pages() {
+        let filter = TraceFilter::default().from_block(1).after(10).count(25);
+        let mut pages = Pages::new(&filter, 10);
+
+        let mut requested = Vec::new();
+        while let Some(page) = pages.next_filter(&filter) {
+            assert_eq!(page.from_block, Some(1));
+            requested.push((page.after.unwrap(), page.count.unwrap()));
+            pages.advance(page.count.unwrap());
+        }
+        assert_eq!(requested, [(10, 10), (20, 10), (30, 5)]);
+
+        // an empty page ends the stream
+        let filter = TraceFilter::default();
+        let mut pages = Pages::new(&filter, 0);
+        assert_eq!(pages.next_filter(&filter).unwrap().count, Some(1));
+        pages.advance(1);
+        assert_eq!(pages.next_filter(&filter).unwrap().after, Some(1));
+        pages.advance(0);
+        assert!(pages.next_filter(&filter).is_none());
+    }
+
+    #[test]
+    fn pages_capped_by_node() {
+        // the node holds 8 traces, and returns at most 3 per call
+        let node = |page: &TraceFilter| {
+            let after = page.after.unwrap();
+            page.count.unwrap().min(3).min(8u64.saturating_sub(after))
+        };
+
+        let filter = TraceFilter::default();
+        let mut pages = Pages::new(&filter, 5);
+        let mut requested = Vec::new();
+        let mut received = 0;
+        while let Some(page) = pages.next_filter(&filter) {
+            requested.push((page.after.unwrap(), page.count.unwrap()));
+            let traces = node(&page);
+            received += traces;
+            pages.advance(traces);
+        }
+        assert_eq!(received, 8);
+        assert_eq!(requested, [(0, 5), (3, 3), (6, 3), (8, 2)]);
+    }
+
+    #[tokio::test]
+    async fn test_trace_block() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let traces = provider.trace_block(BlockNumberOrTag::Latest).await.unwrap();
+        assert!(traces.is_empty());
+    }
+}
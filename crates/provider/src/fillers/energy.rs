@@ -1,5 +1,5 @@
 use crate::{
-    fillers::{FillerControlFlow, TxFiller},
+    fillers::{EnergyOracle, FillerControlFlow, NodeEnergyOracle, TxFiller},
     provider::SendableTx,
     utils::Eip1559Estimation,
     Provider,
@@ -26,9 +26,9 @@ pub enum EnergyFillable {
 /// Energy related fields are energy_price, energy_limit, max_fee_per_energy
 /// max_priority_fee_per_energy and max_fee_per_blob_energy.
 ///
-/// The layer fetches the estimations for these via the
-/// [`Provider::get_energy_price`], [`Provider::estimate_energy`] and
-/// [`Provider::estimate_eip1559_fees`] methods.
+/// The layer fetches the energy price from its [`EnergyOracle`], which defaults to
+/// [`Provider::get_energy_price`], and estimates the energy limit with
+/// [`Provider::estimate_energy`].
 ///
 /// ## Note:
 ///
@@ -44,6 +44,8 @@ pub enum EnergyFillable {
 ///  `max_fee_per_energy` and `max_priority_fee_per_energy` fields if unset.
 /// - If the network does not support EIP-1559, it will fallback to the legacy
 ///  tx and populate the `energy_limit` and `energy_price` fields if unset.
+/// - If `energy_limit` is set below the intrinsic energy of the transaction, see
+///  [`TransactionBuilder::intrinsic_energy`], it will be raised to the intrinsic energy.
 ///
 /// # Example
 ///
@@ -61,10 +63,46 @@ pub enum EnergyFillable {
 /// # Ok(())
 /// # }
 /// ```
+///
+/// The energy price can be tuned with another oracle, e.g. to bid 20% over the price of the node:
+///
+/// ```
+/// # use atoms_network::{NetworkSigner, EthereumSigner, Ethereum};
+/// # use atoms_rpc_types::TransactionRequest;
+/// # use atoms_provider::{fillers::{MultiplierEnergyOracle, NodeEnergyOracle}, ProviderBuilder, RootProvider, Provider};
+/// # async fn test<S: NetworkSigner<Ethereum> + Clone>(url: url::Url, signer: S) -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = ProviderBuilder::new()
+///     .with_energy_oracle(MultiplierEnergyOracle::new(NodeEnergyOracle, 120))
+///     .signer(signer)
+///     .on_http(url);
+///
+/// provider.send_transaction(TransactionRequest::default()).await;
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Clone, Copy, Debug, Default)]
-pub struct EnergyFiller;
+pub struct EnergyFiller<O = NodeEnergyOracle> {
+    oracle: O,
+}
 
 impl EnergyFiller {
+    /// Creates a new energy filler, using the energy price suggested by the node.
+    pub const fn new() -> Self {
+        Self { oracle: NodeEnergyOracle }
+    }
+}
+
+impl<O: EnergyOracle> EnergyFiller<O> {
+    /// Creates a new energy filler, using the energy price returned by the given oracle.
+    pub const fn with_oracle(oracle: O) -> Self {
+        Self { oracle }
+    }
+
+    /// Returns the oracle of the filler.
+    pub const fn oracle(&self) -> &O {
+        &self.oracle
+    }
+
     async fn prepare_legacy<P, T, N>(
         &self,
         provider: &P,
@@ -78,11 +116,12 @@ impl EnergyFiller {
         let energy_price_fut = if let Some(energy_price) = tx.energy_price() {
             async move { Ok(energy_price) }.left_future()
         } else {
-            async { provider.get_energy_price().await }.right_future()
+            async { self.oracle.energy_price::<P, T, N>(provider).await }.right_future()
         };
 
+        let intrinsic_energy = tx.intrinsic_energy();
         let energy_limit_fut = if let Some(energy_limit) = tx.energy_limit() {
-            async move { Ok(energy_limit) }.left_future()
+            async move { Ok(energy_limit.max(intrinsic_energy)) }.left_future()
         } else {
             async { provider.estimate_energy(tx, Default::default()).await }.right_future()
         };
@@ -174,12 +213,14 @@ impl EnergyFiller {
     //     }
 }
 
-impl<N: Network> TxFiller<N> for EnergyFiller {
+impl<N: Network, O: EnergyOracle> TxFiller<N> for EnergyFiller<O> {
     type Fillable = EnergyFillable;
 
     fn status(&self, tx: &<N as Network>::TransactionRequest) -> FillerControlFlow {
-        // legacy and eip2930 tx
-        if tx.energy_price().is_some() && tx.energy_limit().is_some() {
+        // legacy and eip2930 tx, with an energy limit covering the intrinsic energy
+        if tx.energy_price().is_some()
+            && tx.energy_limit().is_some_and(|limit| limit >= tx.intrinsic_energy())
+        {
             return FillerControlFlow::Finished;
         }
 
@@ -255,9 +296,12 @@ impl<N: Network> TxFiller<N> for EnergyFiller {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{ProviderBuilder, WalletProvider};
-    use base_primitives::{cAddress, U256};
+    use crate::{
+        fillers::{MultiplierEnergyOracle, NetworkIdFiller, NonceFiller},
+        ProviderBuilder, WalletProvider,
+    };
     use atoms_rpc_types::TransactionRequest;
+    use base_primitives::{cAddress, U256};
 
     #[tokio::test]
     async fn no_energy_price_or_limit() {
@@ -301,4 +345,51 @@ mod tests {
 
         assert_eq!(receipt.energy_used, 0x5208);
     }
+
+    #[tokio::test]
+    async fn energy_limit_below_intrinsic_energy() {
+        let provider = ProviderBuilder::new().with_recommended_fillers().on_anvil_with_signer();
+
+        let from = provider.default_signer_address();
+
+        let tx = TransactionRequest {
+            from: Some(from),
+            value: Some(U256::from(100)),
+            to: Some(cAddress!("0000d8dA6BF26964aF9D7eEd9e03E53415D37aA96045").into()),
+            energy: Some(1),
+            ..Default::default()
+        };
+
+        let tx = provider.send_transaction(tx).await.unwrap();
+
+        let receipt = tx.get_receipt().await.unwrap();
+
+        assert_eq!(receipt.energy_used, 0x5208);
+    }
+
+    #[tokio::test]
+    async fn energy_price_from_oracle() {
+        let provider = ProviderBuilder::new()
+            .with_energy_oracle(MultiplierEnergyOracle::new(NodeEnergyOracle, 200))
+            .filler(NonceFiller::default())
+            .filler(NetworkIdFiller::default())
+            .on_anvil_with_signer();
+
+        let from = provider.default_signer_address();
+        let energy_price = provider.get_energy_price().await.unwrap();
+
+        let tx = TransactionRequest {
+            from: Some(from),
+            value: Some(U256::from(100)),
+            to: Some(cAddress!("0000d8dA6BF26964aF9D7eEd9e03E53415D37aA96045").into()),
+            ..Default::default()
+        };
+
+        let tx = provider.send_transaction(tx).await.unwrap();
+
+        let receipt = tx.get_receipt().await.unwrap();
+        let tx = provider.get_transaction_by_hash(receipt.transaction_hash).await.unwrap().unwrap();
+
+        assert_eq!(tx.energy_price, Some(energy_price * 2));
+    }
 }
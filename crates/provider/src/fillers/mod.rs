@@ -18,6 +18,12 @@ pub use nonce::NonceFiller;
 mod energy;
 pub use energy::EnergyFiller;
 
+mod oracle;
+pub use oracle::{
+    CappedEnergyOracle, EnergyOracle, FixedEnergyOracle, MultiplierEnergyOracle, NodeEnergyOracle,
+    PercentileEnergyOracle,
+};
+
 mod join_fill;
 pub use join_fill::JoinFill;
 
@@ -0,0 +1,254 @@
+use crate::{utils, Provider};
+use atoms_network::Network;
+use atoms_rpc_types::{BlockNumberOrTag, BlockTransactions};
+use atoms_transport::{Transport, TransportResult};
+use futures_utils_wasm::impl_future;
+
+/// A source of energy prices, consulted by the [`EnergyFiller`] to price the transactions which
+/// don't set one.
+///
+/// Oracles can be composed: for example, the following oracle bids 20% over the price of the
+/// node, up to 50 gwei.
+///
+/// ```
+/// # use atoms_provider::fillers::{CappedEnergyOracle, MultiplierEnergyOracle, NodeEnergyOracle};
+/// let oracle =
+///     CappedEnergyOracle::new(MultiplierEnergyOracle::new(NodeEnergyOracle, 120), 50_000_000_000);
+/// ```
+///
+/// [`EnergyFiller`]: crate::fillers::EnergyFiller
+pub trait EnergyOracle: Clone + Send + Sync + std::fmt::Debug {
+    /// Returns the energy price to use for a new transaction.
+    fn energy_price<P, T, N>(&self, provider: &P) -> impl_future!(<Output = TransportResult<u128>>)
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network;
+}
+
+/// An [`EnergyOracle`] returning the energy price suggested by the node, with
+/// [`Provider::get_energy_price`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NodeEnergyOracle;
+
+impl EnergyOracle for NodeEnergyOracle {
+    async fn energy_price<P, T, N>(&self, provider: &P) -> TransportResult<u128>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        provider.get_energy_price().await
+    }
+}
+
+/// An [`EnergyOracle`] returning a fixed energy price.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FixedEnergyOracle(pub u128);
+
+impl EnergyOracle for FixedEnergyOracle {
+    async fn energy_price<P, T, N>(&self, _provider: &P) -> TransportResult<u128>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        Ok(self.0)
+    }
+}
+
+/// An [`EnergyOracle`] returning a percentile of the energy prices paid in the recent blocks,
+/// fetched with [`Provider::get_block_by_number`].
+///
+/// The price of each non-empty block is the given percentile of the energy prices of its
+/// transactions. The oracle returns the same percentile of these block prices, or the price
+/// suggested by the node if all the blocks are empty.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PercentileEnergyOracle {
+    /// The number of blocks to sample.
+    blocks: u64,
+    /// The percentile of the prices to return, between 0 and 100.
+    percentile: f64,
+}
+
+impl Default for PercentileEnergyOracle {
+    fn default() -> Self {
+        Self::new(
+            utils::EIP1559_FEE_ESTIMATION_PAST_BLOCKS,
+            utils::EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE,
+        )
+    }
+}
+
+impl PercentileEnergyOracle {
+    /// Creates an oracle returning the given percentile of the prices of the last `blocks`
+    /// blocks.
+    ///
+    /// At least one block is sampled, and `percentile` is clamped between 0 and 100, a NaN
+    /// percentile being treated as 0.
+    pub fn new(blocks: u64, percentile: f64) -> Self {
+        let percentile = if percentile.is_nan() { 0.0 } else { percentile.clamp(0.0, 100.0) };
+        Self { blocks: blocks.max(1), percentile }
+    }
+}
+
+impl EnergyOracle for PercentileEnergyOracle {
+    async fn energy_price<P, T, N>(&self, provider: &P) -> TransportResult<u128>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        let latest = provider.get_block_number().await?;
+        let blocks = futures::future::try_join_all(
+            (latest.saturating_sub(self.blocks - 1)..=latest)
+                .map(|number| provider.get_block_by_number(BlockNumberOrTag::Number(number), true)),
+        )
+        .await?;
+
+        let mut prices = blocks
+            .into_iter()
+            .flatten()
+            .filter_map(|block| match block.transactions {
+                BlockTransactions::Full(txs) => {
+                    let mut prices =
+                        txs.iter().filter_map(|tx| tx.energy_price).collect::<Vec<_>>();
+                    percentile(&mut prices, self.percentile)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        match percentile(&mut prices, self.percentile) {
+            Some(price) => Ok(price),
+            None => provider.get_energy_price().await,
+        }
+    }
+}
+
+/// Returns the given percentile of the values, or `None` if there are none.
+fn percentile(values: &mut [u128], percentile: f64) -> Option<u128> {
+    values.sort_unstable();
+    let last = values.len().checked_sub(1)?;
+    let index = (last as f64 * percentile / 100.0).round() as usize;
+    values.get(index.min(last)).copied()
+}
+
+/// An [`EnergyOracle`] scaling the energy price of another oracle, the node's one by default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MultiplierEnergyOracle<O = NodeEnergyOracle> {
+    inner: O,
+    /// The multiplier, in percent.
+    percent: u128,
+}
+
+impl<O> MultiplierEnergyOracle<O> {
+    /// Creates an oracle returning `percent`% of the price of `inner`, e.g. `120` to bid 20% over
+    /// it.
+    pub const fn new(inner: O, percent: u128) -> Self {
+        Self { inner, percent }
+    }
+}
+
+impl<O: EnergyOracle> EnergyOracle for MultiplierEnergyOracle<O> {
+    async fn energy_price<P, T, N>(&self, provider: &P) -> TransportResult<u128>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        let price = self.inner.energy_price::<P, T, N>(provider).await?;
+        Ok(price.saturating_mul(self.percent) / 100)
+    }
+}
+
+/// An [`EnergyOracle`] capping the energy price of another oracle, the node's one by default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CappedEnergyOracle<O = NodeEnergyOracle> {
+    inner: O,
+    /// The maximum energy price.
+    max: u128,
+}
+
+impl<O> CappedEnergyOracle<O> {
+    /// Creates an oracle returning the price of `inner`, up to `max`.
+    pub const fn new(inner: O, max: u128) -> Self {
+        Self { inner, max }
+    }
+}
+
+impl<O: EnergyOracle> EnergyOracle for CappedEnergyOracle<O> {
+    async fn energy_price<P, T, N>(&self, provider: &P) -> TransportResult<u128>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        let price = self.inner.energy_price::<P, T, N>(provider).await?;
+        Ok(price.min(self.max))
+    }
+}
+
+#[cfg(feature = "reqwest")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ProviderBuilder, WalletProvider};
+    use atoms_rpc_types::TransactionRequest;
+    use base_primitives::{cAddress, U256};
+
+    #[test]
+    fn percentiles() {
+        assert_eq!(percentile(&mut [], 50.0), None);
+        assert_eq!(percentile(&mut [7], 20.0), Some(7));
+        assert_eq!(percentile(&mut [5, 1, 4, 2, 3], 0.0), Some(1));
+        assert_eq!(percentile(&mut [5, 1, 4, 2, 3], 50.0), Some(3));
+        assert_eq!(percentile(&mut [5, 1, 4, 2, 3], 100.0), Some(5));
+    }
+
+    #[test]
+    fn percentile_clamped() {
+        assert_eq!(PercentileEnergyOracle::new(0, 150.0), PercentileEnergyOracle::new(1, 100.0));
+        assert_eq!(PercentileEnergyOracle::new(5, -1.0), PercentileEnergyOracle::new(5, 0.0));
+        assert_eq!(PercentileEnergyOracle::new(5, f64::NAN), PercentileEnergyOracle::new(5, 0.0));
+    }
+
+    #[tokio::test]
+    async fn composed_oracles() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let node_price = provider.get_energy_price().await.unwrap();
+
+        assert_eq!(NodeEnergyOracle.energy_price(&provider).await.unwrap(), node_price);
+        assert_eq!(FixedEnergyOracle(42).energy_price(&provider).await.unwrap(), 42);
+
+        let oracle = MultiplierEnergyOracle::new(NodeEnergyOracle, 150);
+        assert_eq!(oracle.energy_price(&provider).await.unwrap(), node_price * 3 / 2);
+
+        let oracle = CappedEnergyOracle::new(FixedEnergyOracle(42), 10);
+        assert_eq!(oracle.energy_price(&provider).await.unwrap(), 10);
+
+        // all the blocks are empty, so the node's price is returned
+        let oracle = PercentileEnergyOracle::default();
+        assert_eq!(oracle.energy_price(&provider).await.unwrap(), node_price);
+    }
+
+    #[tokio::test]
+    async fn percentile_of_block_prices() {
+        let provider = ProviderBuilder::new().with_recommended_fillers().on_anvil_with_signer();
+        let energy_price = provider.get_energy_price().await.unwrap() * 2;
+
+        let tx = TransactionRequest {
+            from: Some(provider.default_signer_address()),
+            value: Some(U256::from(100)),
+            to: Some(cAddress!("0000d8dA6BF26964aF9D7eEd9e03E53415D37aA96045").into()),
+            energy_price: Some(energy_price),
+            network_id: 1,
+            ..Default::default()
+        };
+        provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+
+        // the empty blocks are skipped
+        let oracle = PercentileEnergyOracle::new(10, 50.0);
+        assert_eq!(oracle.energy_price(&provider).await.unwrap(), energy_price);
+    }
+}
@@ -1,5 +1,3 @@
-use atoms_consensus::{Signed, TxLegacy};
-use atoms_signer::Signature;
 use atoms_network::Network;
 
 /// A transaction that can be sent. This is either a builder or an envelope.
@@ -15,7 +13,7 @@ pub enum SendableTx<N: Network> {
     /// A transaction that is not yet signed.
     Builder(N::TransactionRequest),
     /// A transaction that is signed and fully constructed.
-    Signed(Signed<TxLegacy, Signature>),
+    Signed(N::TxEnvelope),
 }
 
 impl<N: Network> SendableTx<N> {
@@ -46,7 +44,7 @@ impl<N: Network> SendableTx<N> {
     }
 
     /// Fallible cast to a built transaction envelope.
-    pub const fn as_envelope(&self) -> Option<&Signed<TxLegacy, Signature>> {
+    pub const fn as_envelope(&self) -> Option<&N::TxEnvelope> {
         match self {
             Self::Signed(tx) => Some(tx),
             _ => None,
@@ -7,6 +7,7 @@ use crate::{
 };
 use atoms_eips::eip2718::Encodable2718;
 use atoms_json_rpc::{RpcError, RpcParam, RpcReturn};
+use atoms_network::{Ethereum, Network};
 use atoms_rpc_client::{ClientRef, PollerBuilder, WeakClient};
 use atoms_rpc_types::{
     AccessListWithGasUsed, Block, BlockId, BlockNumberOrTag, EIP1186AccountProofResponse,
@@ -14,7 +15,6 @@ use atoms_rpc_types::{
 };
 use atoms_rpc_types_trace::parity::{LocalizedTransactionTrace, TraceResults, TraceType};
 use atoms_transport::{BoxTransport, Transport, TransportErrorKind, TransportResult};
-use atoms_network::{Ethereum, Network};
 use base_primitives::{
     hex, BlockHash, BlockNumber, Bytes, IcanAddress, StorageKey, StorageValue, TxHash, B256, U128,
     U256, U64,
@@ -494,8 +494,7 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
                 Ok(PendingTransactionBuilder::new(self.root(), tx_hash))
             }
             SendableTx::Signed(tx) => {
-                let mut encoded_tx = vec![];
-                tx.tx().encode_with_signature_fields(tx.signature(), &mut encoded_tx);
+                let encoded_tx = tx.encoded_2718();
                 self.send_raw_transaction(&encoded_tx).await
             }
         }
@@ -695,19 +694,21 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         XcbCall::new(self.weak_client(), tx)
     }
 
-    // /// Returns a collection of historical energy information [FeeHistory] which
-    // /// can be used to calculate the EIP1559 fields `maxFeePerEnergy` and `maxPriorityFeePerEnergy`.
-    // /// `block_count` can range from 1 to 1024 blocks in a single request.
-    // async fn get_fee_history(
-    //     &self,
-    //     block_count: u64,
-    //     last_block: BlockNumberOrTag,
-    //     reward_percentiles: &[f64],
-    // ) -> TransportResult<FeeHistory> {
-    //     self.client()
-    //         .request("xcb_feeHistory", (U64::from(block_count), last_block, reward_percentiles))
-    //         .await
-    // }
+    /// Returns a collection of historical energy information [FeeHistory] of the `block_count`
+    /// blocks up to `last_block`, including the energy prices paid at the given
+    /// `reward_percentiles` of each block.
+    ///
+    /// `block_count` can range from 1 to 1024 blocks in a single request.
+    async fn get_fee_history(
+        &self,
+        block_count: u64,
+        last_block: BlockNumberOrTag,
+        reward_percentiles: &[f64],
+    ) -> TransportResult<FeeHistory> {
+        self.client()
+            .request("xcb_feeHistory", (U64::from(block_count), last_block, reward_percentiles))
+            .await
+    }
 
     /// Estimate the energy needed for a transaction.
     async fn estimate_energy(
@@ -820,19 +821,6 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.client().request("trace_transaction", (hash,)).await
     }
 
-    // todo: move to extension trait
-    /// Trace all transactions in the given block.
-    ///
-    /// # Note
-    ///
-    /// Not all nodes support this call.
-    // async fn trace_block(
-    //     &self,
-    //     block: BlockNumberOrTag,
-    // ) -> TransportResult<Vec<LocalizedTransactionTrace>> {
-    //     self.client().request("trace_block", (block,)).await
-    // }
-
     /* ------------------------------------------ anvil ----------------------------------------- */
 
     /// Set the bytecode of a given account.
@@ -933,9 +921,9 @@ impl<T: Transport + Clone, N: Network> Provider<T, N> for RootProvider<T, N> {
 mod tests {
     use super::*;
     use crate::{ProviderBuilder, WalletProvider};
+    use atoms_network::TransactionBuilder;
     use atoms_node_bindings::Anvil;
     use atoms_rpc_types::request::TransactionRequest;
-    use atoms_network::TransactionBuilder;
     use base_primitives::{address, b256, bytes, cAddress};
 
     fn init_tracing() {
@@ -1276,21 +1264,21 @@ mod tests {
     //     let _fee = provider.get_max_priority_fee_per_energy().await.unwrap();
     // }
 
-    // #[tokio::test]
-    // async fn gets_fee_history() {
-    //     init_tracing();
-    //     let provider = ProviderBuilder::new().on_anvil();
-    //     let block_number = provider.get_block_number().await.unwrap();
-    //     let fee_history = provider
-    //         .get_fee_history(
-    //             utils::EIP1559_FEE_ESTIMATION_PAST_BLOCKS,
-    //             BlockNumberOrTag::Number(block_number),
-    //             &[utils::EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE],
-    //         )
-    //         .await
-    //         .unwrap();
-    //     assert_eq!(fee_history.oldest_block, 0_u64);
-    // }
+    #[tokio::test]
+    async fn gets_fee_history() {
+        init_tracing();
+        let provider = ProviderBuilder::new().on_anvil();
+        let block_number = provider.get_block_number().await.unwrap();
+        let fee_history = provider
+            .get_fee_history(
+                utils::EIP1559_FEE_ESTIMATION_PAST_BLOCKS,
+                BlockNumberOrTag::Number(block_number),
+                &[utils::EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE],
+            )
+            .await
+            .unwrap();
+        assert_eq!(fee_history.oldest_block, 0_u64);
+    }
 
     // #[tokio::test]
     // async fn gets_block_receipts() {
@@ -15,6 +15,9 @@ pub trait WalletProvider<N: Network = Ethereum> {
     fn signer(&self) -> &Self::Signer;
 
     /// Get a mutable reference to the underlying signer.
+    ///
+    /// Note that the signers of a [`CoreSigner`](atoms_network::CoreSigner) are shared between
+    /// its clones, and can be modified through [`signer`](Self::signer) instead.
     fn signer_mut(&mut self) -> &mut Self::Signer;
 
     /// Get the default signer address.
@@ -27,9 +30,7 @@ pub trait WalletProvider<N: Network = Ethereum> {
         self.signer().has_signer_for(address)
     }
 
-    /// Get an iterator of all signer addresses. Note that because the signer
-    /// always has at least one address, this iterator will always have at least
-    /// one element.
+    /// Get an iterator of all signer addresses.
     fn signer_addresses(&self) -> impl Iterator<Item = IcanAddress> {
         self.signer().signer_addresses()
     }
@@ -2,7 +2,8 @@
 
 #![allow(unknown_lints, non_local_definitions)]
 
-use crate::{other::OtherFields, Transaction, Withdrawal};
+use crate::{other::OtherFields, ConversionError, Transaction, Withdrawal};
+use atoms_consensus::{BlockBody, TxEnvelope};
 pub use atoms_eips::{
     calc_blob_gasprice, calc_excess_blob_gas, BlockHashOrNumber, BlockId, BlockNumHash,
     BlockNumberOrTag, ForkBlock, RpcBlockHash,
@@ -161,6 +162,62 @@ impl Header {
     }
 }
 
+impl TryFrom<Header> for atoms_consensus::Header {
+    type Error = ConversionError;
+
+    fn try_from(header: Header) -> Result<Self, Self::Error> {
+        Ok(Self {
+            parent_hash: header.parent_hash,
+            ommers_hash: header.uncles_hash,
+            beneficiary: header.miner,
+            state_root: header.state_root,
+            transactions_root: header.transactions_root,
+            receipts_root: header.receipts_root,
+            withdrawals_root: header.withdrawals_root,
+            logs_bloom: header.logs_bloom,
+            difficulty: header.difficulty,
+            number: header.number.ok_or(ConversionError::MissingBlockNumber)?,
+            gas_limit: header.energy_limit,
+            gas_used: header.energy_used,
+            timestamp: header.timestamp,
+            mix_hash: header.mix_hash.ok_or(ConversionError::MissingMixHash)?,
+            nonce: header.nonce.ok_or(ConversionError::MissingNonce)?,
+            base_fee_per_gas: header.base_fee_per_gas,
+            blob_gas_used: header.blob_gas_used,
+            excess_blob_gas: header.excess_blob_gas,
+            parent_beacon_block_root: header.parent_beacon_block_root,
+            requests_root: header.requests_root,
+            extra_data: header.extra_data,
+        })
+    }
+}
+
+impl TryFrom<Block> for atoms_consensus::Block {
+    type Error = ConversionError;
+
+    /// Converts an RPC block into a consensus block.
+    ///
+    /// The block must have been fetched with full transactions. Blocks with uncles cannot be
+    /// converted, as the RPC block only contains the uncle hashes.
+    fn try_from(block: Block) -> Result<Self, Self::Error> {
+        if !block.uncles.is_empty() {
+            return Err(ConversionError::MissingUncleHeaders);
+        }
+
+        let transactions = match block.transactions {
+            BlockTransactions::Full(transactions) => {
+                transactions.into_iter().map(TxEnvelope::try_from).collect::<Result<Vec<_>, _>>()?
+            }
+            // an empty transaction list deserializes as hashes
+            BlockTransactions::Hashes(hashes) if hashes.is_empty() => Vec::new(),
+            _ => return Err(ConversionError::MissingFullTransactions),
+        };
+
+        let body = BlockBody { transactions, ommers: Vec::new() };
+        Ok(body.into_block(block.header.try_into()?))
+    }
+}
+
 /// Block Transactions depending on the boolean attribute of `eth_getBlockBy*`,
 /// or if used by `eth_getUncle*`
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -578,6 +635,86 @@ mod tests {
         let _: Header = Header::arbitrary(&mut arbitrary::Unstructured::new(&bytes)).unwrap();
     }
 
+    #[test]
+    fn block_into_consensus() {
+        let header = Header {
+            hash: Some(B256::with_last_byte(1)),
+            parent_hash: B256::with_last_byte(2),
+            miner: IcanAddress::with_last_byte(3),
+            number: Some(4),
+            energy_limit: 5,
+            energy_used: 6,
+            timestamp: 7,
+            extra_data: Bytes::from_static(&[8]),
+            mix_hash: Some(B256::with_last_byte(9)),
+            nonce: Some(B64::with_last_byte(10)),
+            ..Default::default()
+        };
+        let block = Block { header: header.clone(), ..Default::default() };
+
+        let consensus = atoms_consensus::Block::try_from(block.clone()).unwrap();
+        assert!(consensus.body.transactions.is_empty());
+        assert_eq!(consensus.header.beneficiary, header.miner);
+        assert_eq!(consensus.header.number, 4);
+        assert_eq!(consensus.header.gas_limit, 5);
+        assert_eq!(consensus.header.gas_used, 6);
+        assert_eq!(consensus.header.nonce, B64::with_last_byte(10));
+
+        let hashes = Block { transactions: vec![B256::with_last_byte(11)].into(), ..block.clone() };
+        assert!(matches!(
+            atoms_consensus::Block::try_from(hashes),
+            Err(ConversionError::MissingFullTransactions)
+        ));
+
+        let uncles = Block { uncles: vec![B256::with_last_byte(12)], ..block.clone() };
+        assert!(matches!(
+            atoms_consensus::Block::try_from(uncles),
+            Err(ConversionError::MissingUncleHeaders)
+        ));
+
+        let pending = Block { header: Header { number: None, ..header }, ..block };
+        assert!(matches!(
+            atoms_consensus::Block::try_from(pending),
+            Err(ConversionError::MissingBlockNumber)
+        ));
+    }
+
+    #[test]
+    fn mixed_block_into_consensus() {
+        let legacy = Transaction {
+            nonce: 0,
+            to: Some(IcanAddress::with_last_byte(4)),
+            energy_price: Some(3),
+            energy: 21000,
+            signature: Some(format!("0x{}", "ab".repeat(171)).parse().unwrap()),
+            network_id: 1,
+            ..Default::default()
+        };
+        let eip2930 = Transaction {
+            nonce: 1,
+            energy: 30000,
+            access_list: Some(Default::default()),
+            transaction_type: Some(1),
+            ..legacy.clone()
+        };
+        let header = Header {
+            number: Some(4),
+            mix_hash: Some(B256::ZERO),
+            nonce: Some(B64::ZERO),
+            ..Default::default()
+        };
+        let block = Block {
+            header,
+            transactions: BlockTransactions::Full(vec![legacy, eip2930]),
+            ..Default::default()
+        };
+
+        let consensus = atoms_consensus::Block::try_from(block).unwrap();
+        let transactions = &consensus.body.transactions;
+        assert!(transactions[0].as_legacy().is_some());
+        assert_eq!(transactions[1].as_eip2930().unwrap().tx().energy_limit, 30000);
+    }
+
     #[test]
     fn test_full_conversion() {
         let full = true;
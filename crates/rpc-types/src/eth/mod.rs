@@ -10,6 +10,7 @@ mod filter;
 mod index;
 mod log;
 pub mod other;
+pub mod personal;
 pub mod pubsub;
 pub mod raw_log;
 pub mod state;
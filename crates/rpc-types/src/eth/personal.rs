@@ -0,0 +1,55 @@
+//! Types for the `personal` namespace of go-core.
+
+use base_primitives::IcanAddress;
+use serde::{Deserialize, Serialize};
+
+/// A wallet managed by the node, as returned by `personal_listWallets`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletInfo {
+    /// The URL of the wallet, e.g. `keystore:///path/to/keyfile`.
+    pub url: String,
+    /// A textual description of the status of the wallet, e.g. `Locked` or `Unlocked`.
+    pub status: String,
+    /// The error encountered by the wallet, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure: Option<String>,
+    /// The accounts of the wallet.
+    #[serde(default)]
+    pub accounts: Vec<WalletAccount>,
+}
+
+/// An account of a [`WalletInfo`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletAccount {
+    /// The address of the account.
+    pub address: IcanAddress,
+    /// The URL of the account within its wallet.
+    pub url: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_wallet_info() {
+        let response = r#"[{
+            "url":"keystore:///tmp/keystore/UTC--2024-01-01T00-00-00.000000000Z--cb82a5fd22b9bee8b8ab877c86e0a2c21765e1d5bfc5",
+            "status":"Locked",
+            "accounts":[{
+                "address":"0xcb82a5fd22b9bee8b8ab877c86e0a2c21765e1d5bfc5",
+                "url":"keystore:///tmp/keystore/UTC--2024-01-01T00-00-00.000000000Z--cb82a5fd22b9bee8b8ab877c86e0a2c21765e1d5bfc5"
+            }]
+        }]"#;
+        let wallets: Vec<WalletInfo> = serde_json::from_str(response).unwrap();
+
+        assert_eq!(wallets.len(), 1);
+        assert_eq!(wallets[0].status, "Locked");
+        assert_eq!(wallets[0].failure, None);
+        assert_eq!(
+            wallets[0].accounts[0].address,
+            "cb82a5fd22b9bee8b8ab877c86e0a2c21765e1d5bfc5".parse::<IcanAddress>().unwrap()
+        );
+        assert_eq!(wallets[0].accounts[0].url, wallets[0].url);
+    }
+}
@@ -24,6 +24,17 @@ pub enum ConversionError {
     /// Missing `chainId` field for EIP-1559 transaction.
     #[error("missing `chainId` field for EIP-155 transaction")]
     MissingChainId,
+    /// The `from` field does not match the signer recovered from the signature.
+    #[error("transaction `from` field {from} does not match the recovered signer {recovered}")]
+    SenderMismatch {
+        /// The sender reported in the `from` field.
+        from: base_primitives::IcanAddress,
+        /// The signer recovered from the signature.
+        recovered: base_primitives::IcanAddress,
+    },
+    /// The logs bloom does not match the logs of the receipt.
+    #[error("logs bloom does not match the receipt logs")]
+    InvalidLogsBloom,
     /// Missing `gasPrice` field for Legacy transaction.
     #[error("missing `gasPrice` field for Legacy transaction")]
     MissingGasPrice,
@@ -60,6 +71,15 @@ pub enum ConversionError {
     /// Missing block number
     #[error("missing block number")]
     MissingBlockNumber,
+    /// Missing mix hash
+    #[error("missing mix hash")]
+    MissingMixHash,
+    /// Missing block nonce
+    #[error("missing block nonce")]
+    MissingNonce,
+    /// Missing uncle headers, which are only referenced by hash in RPC blocks
+    #[error("missing uncle headers required for block decoding")]
+    MissingUncleHeaders,
     /// Blob gas used integer conversion error
     #[error("blob gas used integer conversion error: {0}")]
     BlobGasUsedConversion(TryFromIntError),
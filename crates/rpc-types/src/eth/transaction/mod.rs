@@ -3,7 +3,10 @@
 use std::str::FromStr;
 
 use crate::eth::other::OtherFields;
-use atoms_consensus::{SignableTransaction, Signed, TxLegacy};
+use atoms_consensus::{
+    Recovered, SignableTransaction, Signed, TxEip2930, TxEnvelope, TxLegacy, TxType,
+};
+use atoms_eips::eip2718::Eip2718Error;
 use base_primitives::{Bytes, IcanAddress, Signature, TxKind, B256, U256};
 
 use serde::{Deserialize, Serialize};
@@ -98,8 +101,8 @@ pub struct Transaction {
     /// EIP2930
     ///
     /// Pre-pay to warm storage access.
-    // #[serde(skip_serializing_if = "Option::is_none")]
-    // pub access_list: Option<AccessList>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_list: Option<AccessList>,
     /// EIP2718
     ///
     /// Transaction type,
@@ -152,7 +155,7 @@ impl Transaction {
             input: self.input.into(),
             nonce: Some(self.nonce),
             network_id: self.network_id,
-            // access_list: self.access_list,
+            access_list: self.access_list,
             transaction_type: self.transaction_type,
             max_fee_per_gas: self.max_fee_per_gas,
             max_priority_fee_per_gas: self.max_priority_fee_per_gas,
@@ -161,12 +164,31 @@ impl Transaction {
             // sidecar: None,
         }
     }
+
+    /// Converts the transaction into a [`TxEnvelope`] with its sender recovered from the
+    /// signature.
+    ///
+    /// Returns [`ConversionError::SenderMismatch`] if the recovered sender disagrees with the
+    /// `from` field reported by the node.
+    pub fn try_into_recovered(self) -> Result<Recovered<TxEnvelope>, ConversionError> {
+        let from = self.from;
+        let network_id = self.network_id;
+        let recovered = TxEnvelope::try_from(self)?.try_into_recovered(network_id)?;
+        if recovered.signer() != from {
+            return Err(ConversionError::SenderMismatch { from, recovered: recovered.signer() });
+        }
+        Ok(recovered)
+    }
 }
 
 impl TryFrom<Transaction> for Signed<TxLegacy> {
     type Error = ConversionError;
 
     fn try_from(tx: Transaction) -> Result<Self, Self::Error> {
+        let ty = tx.transaction_type.unwrap_or_default();
+        if ty != TxType::Legacy as u8 {
+            return Err(Eip2718Error::UnexpectedType(ty).into());
+        }
         let signature = tx.signature.ok_or(ConversionError::MissingSignature)?;
 
         let tx = TxLegacy {
@@ -182,6 +204,41 @@ impl TryFrom<Transaction> for Signed<TxLegacy> {
     }
 }
 
+impl TryFrom<Transaction> for Signed<TxEip2930> {
+    type Error = ConversionError;
+
+    fn try_from(tx: Transaction) -> Result<Self, Self::Error> {
+        let ty = tx.transaction_type.unwrap_or_default();
+        if ty != TxType::Eip2930 as u8 {
+            return Err(Eip2718Error::UnexpectedType(ty).into());
+        }
+        let signature = tx.signature.ok_or(ConversionError::MissingSignature)?;
+
+        let tx = TxEip2930 {
+            network_id: tx.network_id,
+            nonce: tx.nonce,
+            energy_price: tx.energy_price.ok_or(ConversionError::MissingGasPrice)?,
+            energy_limit: tx.energy,
+            to: tx.to.into(),
+            value: tx.value,
+            access_list: tx.access_list.ok_or(ConversionError::MissingAccessList)?,
+            input: tx.input,
+        };
+        Ok(tx.into_signed(signature))
+    }
+}
+
+impl TryFrom<Transaction> for TxEnvelope {
+    type Error = ConversionError;
+
+    fn try_from(tx: Transaction) -> Result<Self, Self::Error> {
+        match tx.transaction_type.unwrap_or_default().try_into()? {
+            TxType::Legacy => Ok(Self::Legacy(tx.try_into()?)),
+            TxType::Eip2930 => Ok(Self::Eip2930(tx.try_into()?)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -215,7 +272,7 @@ mod tests {
             signature: Some(Signature::from_str("0x000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000").unwrap()),
             network_id: 17,
             blob_versioned_hashes: None,
-            // access_list: None,
+            access_list: None,
             transaction_type: Some(20),
             max_fee_per_gas: Some(21),
             max_priority_fee_per_gas: Some(22),
@@ -251,6 +308,85 @@ mod tests {
         assert_eq!(transaction, deserialized);
     }
 
+    #[test]
+    fn recover_sender() {
+        use libgoldilocks::{PrehashSigner, SigningKey};
+
+        let key = SigningKey::from_str("7d6231471b5dbb6204fe5129617082792ae468d01a3f3623184c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318");
+        let from = IcanAddress::from_private_key(&key, 1);
+        let tx = TxLegacy {
+            network_id: 1,
+            nonce: 2,
+            energy_price: 3,
+            energy_limit: 21000,
+            to: TxKind::Call(IcanAddress::with_last_byte(4)),
+            value: U256::from(5),
+            input: Bytes::new(),
+        };
+        let sign = || key.sign_prehash(tx.signature_hash().as_ref()).unwrap();
+        let transaction = Transaction {
+            hash: *tx.clone().into_signed(sign()).hash(),
+            nonce: tx.nonce,
+            from,
+            to: Some(IcanAddress::with_last_byte(4)),
+            value: tx.value,
+            energy_price: Some(tx.energy_price),
+            energy: tx.energy_limit,
+            signature: Some(sign()),
+            network_id: tx.network_id,
+            ..Default::default()
+        };
+
+        let recovered = transaction.clone().try_into_recovered().unwrap();
+        assert_eq!(recovered.signer(), from);
+        assert_eq!(recovered.tx_hash(), &transaction.hash);
+
+        let spoofed = Transaction { from: IcanAddress::with_last_byte(6), ..transaction };
+        assert!(matches!(
+            spoofed.try_into_recovered(),
+            Err(ConversionError::SenderMismatch { recovered, .. }) if recovered == from
+        ));
+    }
+
+    #[test]
+    fn into_envelope_eip2930() {
+        let transaction = Transaction {
+            nonce: 2,
+            to: Some(IcanAddress::with_last_byte(4)),
+            value: U256::from(5),
+            energy_price: Some(3),
+            energy: 30000,
+            signature: Some(Signature::from_str(&format!("0x{}", "ab".repeat(171))).unwrap()),
+            network_id: 1,
+            access_list: Some(AccessList(vec![AccessListItem {
+                address: IcanAddress::with_last_byte(4),
+                storage_keys: vec![B256::ZERO],
+            }])),
+            transaction_type: Some(1),
+            ..Default::default()
+        };
+
+        let envelope = TxEnvelope::try_from(transaction.clone()).unwrap();
+        let tx = envelope.as_eip2930().unwrap().tx();
+        assert_eq!(Some(&tx.access_list), transaction.access_list.as_ref());
+        assert_eq!(tx.energy_limit, 30000);
+
+        let missing = Transaction { access_list: None, ..transaction.clone() };
+        assert!(matches!(TxEnvelope::try_from(missing), Err(ConversionError::MissingAccessList)));
+
+        assert!(matches!(
+            Signed::<TxLegacy>::try_from(transaction.clone()),
+            Err(ConversionError::Eip2718Error(Eip2718Error::UnexpectedType(1)))
+        ));
+
+        let legacy = Transaction { transaction_type: None, ..transaction };
+        assert!(matches!(
+            Signed::<TxEip2930>::try_from(legacy.clone()),
+            Err(ConversionError::Eip2718Error(Eip2718Error::UnexpectedType(0)))
+        ));
+        assert!(TxEnvelope::try_from(legacy).unwrap().as_legacy().is_some());
+    }
+
     #[test]
     fn into_request_legacy() {
         // cast rpc eth_getTransactionByHash
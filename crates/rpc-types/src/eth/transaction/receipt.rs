@@ -1,7 +1,7 @@
 #![allow(unknown_lints, non_local_definitions)]
 
-use crate::{Log, WithOtherFields};
-use atoms_consensus::{AnyReceiptEnvelope, ReceiptWithBloom};
+use crate::{ConversionError, Log, WithOtherFields};
+use atoms_consensus::{AnyReceiptEnvelope, Receipt, ReceiptWithBloom};
 use base_primitives::{Address, IcanAddress, B256};
 use serde::{Deserialize, Serialize};
 
@@ -85,6 +85,32 @@ impl TransactionReceipt {
     }
 }
 
+impl From<TransactionReceipt> for Receipt {
+    fn from(receipt: TransactionReceipt) -> Self {
+        let Receipt { status, cumulative_energy_used, logs } = receipt.inner.receipt;
+        Self {
+            status,
+            cumulative_energy_used,
+            logs: logs.into_iter().map(|log| log.inner).collect(),
+        }
+    }
+}
+
+impl TryFrom<TransactionReceipt> for ReceiptWithBloom {
+    type Error = ConversionError;
+
+    /// Converts the RPC receipt into its consensus form, checking that the bloom reported by the
+    /// node matches the logs of the receipt.
+    fn try_from(receipt: TransactionReceipt) -> Result<Self, Self::Error> {
+        let logs_bloom = receipt.inner.logs_bloom;
+        let receipt = Self::new(receipt.into(), logs_bloom);
+        if !receipt.is_bloom_valid() {
+            return Err(ConversionError::InvalidLogsBloom);
+        }
+        Ok(receipt)
+    }
+}
+
 /// Alias for a catch-all receipt type.
 pub type AnyTransactionReceipt = WithOtherFields<TransactionReceipt>;
 
@@ -143,4 +169,51 @@ mod test {
             serde_json::from_str::<serde_json::Value>(json_str).unwrap()
         );
     }
+
+    #[test]
+    fn into_consensus_receipt() {
+        let logs = vec![base_primitives::Log::new_unchecked(
+            cAddress!("0000dac17f958d2ee523a2206206994597c13d831ec7"),
+            vec![b256!("8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925")],
+            Default::default(),
+        )];
+        let consensus = Receipt { status: true, cumulative_energy_used: 21000, logs }.with_bloom();
+
+        let rpc_receipt = TransactionReceipt {
+            inner: ReceiptWithBloom::new(
+                Receipt {
+                    status: true,
+                    cumulative_energy_used: 21000,
+                    logs: consensus
+                        .receipt
+                        .logs
+                        .iter()
+                        .map(|log| Log { inner: log.clone(), ..Default::default() })
+                        .collect(),
+                },
+                consensus.logs_bloom,
+            ),
+            transaction_hash: B256::with_last_byte(1),
+            transaction_index: Some(0),
+            block_hash: Some(B256::with_last_byte(2)),
+            block_number: Some(3),
+            energy_used: 21000,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            from: IcanAddress::with_last_byte(4),
+            to: Some(IcanAddress::with_last_byte(5)),
+            contract_address: None,
+            state_root: None,
+        };
+
+        assert_eq!(Receipt::from(rpc_receipt.clone()), consensus.receipt);
+        assert_eq!(ReceiptWithBloom::try_from(rpc_receipt.clone()).unwrap(), consensus);
+
+        let mut forged = rpc_receipt;
+        forged.inner.logs_bloom = Bloom::ZERO;
+        assert!(matches!(
+            ReceiptWithBloom::try_from(forged),
+            Err(ConversionError::InvalidLogsBloom)
+        ));
+    }
 }
@@ -1,7 +1,8 @@
 //! Alloy basic Transaction Request type.
 
-use crate::Transaction;
-use atoms_consensus::{TxLegacy, TypedTransaction};
+use crate::{AccessList, Transaction};
+use atoms_consensus::{calculate_intrinsic_energy, TxEip2930, TxLegacy, TxType, TypedTransaction};
+use atoms_eips::eip2718::Eip2718Error;
 use base_primitives::{Address, Bytes, ChainId, IcanAddress, TxKind, B256, U256};
 use serde::{Deserialize, Serialize};
 use std::hash::Hash;
@@ -68,8 +69,8 @@ pub struct TransactionRequest {
     #[serde(default, with = "atoms_serde::num::u64_via_ruint")]
     pub network_id: ChainId,
     /// An EIP-2930 access list, which lowers cost for accessing accounts and storages in the list. See [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930) for more information.
-    // #[serde(default, skip_serializing_if = "Option::is_none")]
-    // pub access_list: Option<AccessList>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_list: Option<AccessList>,
     /// The EIP-2718 transaction type. See [EIP-2718](https://eips.ethereum.org/EIPS/eip-2718) for more information.
     #[serde(
         default,
@@ -137,11 +138,11 @@ impl TransactionRequest {
         self
     }
 
-    // /// Sets the access list for the transaction.
-    // pub fn access_list(mut self, access_list: AccessList) -> Self {
-    //     self.access_list = Some(access_list);
-    //     self
-    // }
+    /// Sets the access list for the transaction.
+    pub fn access_list(mut self, access_list: AccessList) -> Self {
+        self.access_list = Some(access_list);
+        self
+    }
 
     /// Sets the input data for the transaction.
     pub fn input(mut self, input: TransactionInput) -> Self {
@@ -157,6 +158,25 @@ impl TransactionRequest {
         self.energy_price.or(self.max_fee_per_gas)
     }
 
+    /// Returns the intrinsic energy of the transaction: the minimum energy limit it can be sent
+    /// with.
+    ///
+    /// See [`calculate_intrinsic_energy`].
+    pub fn intrinsic_energy(&self) -> u128 {
+        let is_create = self.to.is_some_and(|to| to.is_create());
+        calculate_intrinsic_energy(
+            self.input.input().map_or(&[][..], |input| &input[..]),
+            is_create,
+            self.access_list.as_deref().map_or(&[][..], |access_list| &access_list[..]),
+        )
+    }
+
+    /// Returns true if the energy limit is set and covers the intrinsic energy of the
+    /// transaction.
+    fn has_valid_energy_limit(&self) -> bool {
+        self.energy.is_some_and(|energy| energy >= self.intrinsic_energy())
+    }
+
     /// Gets invalid fields for all transaction types
     pub fn get_invalid_common_fields(&self) -> Vec<&'static str> {
         let mut errors = vec![];
@@ -165,7 +185,7 @@ impl TransactionRequest {
             errors.push("nonce");
         }
 
-        if self.energy.is_none() {
+        if !self.has_valid_energy_limit() {
             errors.push("energy_limit");
         }
 
@@ -207,12 +227,33 @@ impl TransactionRequest {
         }
     }
 
+    /// Build an EIP-2930 transaction.
+    ///
+    /// # Panics
+    ///
+    /// If required fields are missing. Use `complete_2930` to check if the
+    /// request can be built.
+    fn build_2930(self) -> TxEip2930 {
+        let checked_to = self.to.expect("checked in complete_2930.");
+
+        TxEip2930 {
+            network_id: self.network_id,
+            nonce: self.nonce.expect("checked in complete_2930"),
+            energy_price: self.energy_price.expect("checked in complete_2930"),
+            energy_limit: self.energy.expect("checked in complete_2930"),
+            to: checked_to,
+            value: self.value.unwrap_or_default(),
+            input: self.input.into_input().unwrap_or_default(),
+            access_list: self.access_list.unwrap_or_default(),
+        }
+    }
+
     fn check_reqd_fields(&self) -> Vec<&'static str> {
         let mut missing = Vec::with_capacity(12);
         if self.nonce.is_none() {
             missing.push("nonce");
         }
-        if self.energy.is_none() {
+        if !self.has_valid_energy_limit() {
             missing.push("energy_limit");
         }
         if self.to.is_none() {
@@ -239,10 +280,69 @@ impl TransactionRequest {
         }
     }
 
-    /// Build an [`TypedTransaction`]
+    /// Check if all necessary keys are present to build an EIP-2930 transaction,
+    /// returning a list of keys that are missing.
+    pub fn complete_2930(&self) -> Result<(), Vec<&'static str>> {
+        let mut missing = self.check_reqd_fields();
+
+        if self.access_list.is_none() {
+            missing.push("access_list");
+        }
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(missing)
+        }
+    }
+
+    /// Returns the transaction type this request is built into: the transaction type if it is
+    /// set, EIP-2930 if an access list is set, legacy otherwise.
+    ///
+    /// Returns an error if the transaction type is set to a type other than legacy (0) and
+    /// EIP-2930 (1).
+    pub const fn preferred_type(&self) -> Result<TxType, Eip2718Error> {
+        match self.transaction_type {
+            Some(0) => Ok(TxType::Legacy),
+            Some(1) => Ok(TxType::Eip2930),
+            Some(ty) => Err(Eip2718Error::UnexpectedType(ty)),
+            None if self.access_list.is_some() => Ok(TxType::Eip2930),
+            None => Ok(TxType::Legacy),
+        }
+    }
+
+    /// Check if all necessary keys are present to build the [`preferred_type`], returning a list
+    /// of keys that are missing.
+    ///
+    /// If the transaction type is not supported, `transaction_type` is returned as the only
+    /// invalid key.
+    ///
+    /// [`preferred_type`]: Self::preferred_type
+    pub fn complete_preferred(&self) -> Result<(), Vec<&'static str>> {
+        match self.preferred_type() {
+            Ok(TxType::Legacy) => self.complete_legacy(),
+            Ok(TxType::Eip2930) => self.complete_2930(),
+            Err(_) => Err(vec!["transaction_type"]),
+        }
+    }
+
+    /// Build an [`TypedTransaction`] of the [`preferred_type`].
+    ///
+    /// Returns the request back if its transaction type is not supported, or if it is missing
+    /// keys required to build the transaction, see [`complete_preferred`].
+    ///
+    /// [`preferred_type`]: Self::preferred_type
+    /// [`complete_preferred`]: Self::complete_preferred
     pub fn build_typed_tx(self) -> Result<TypedTransaction, Self> {
-        let tx = self.build_legacy();
-        Ok(TypedTransaction::Legacy(tx))
+        if self.complete_preferred().is_err() {
+            return Err(self);
+        }
+
+        match self.preferred_type() {
+            Ok(TxType::Legacy) => Ok(TypedTransaction::Legacy(self.build_legacy())),
+            Ok(TxType::Eip2930) => Ok(TypedTransaction::Eip2930(self.build_2930())),
+            Err(_) => Err(self),
+        }
     }
 }
 
@@ -354,10 +454,28 @@ impl From<TxLegacy> for TransactionRequest {
     }
 }
 
+impl From<TxEip2930> for TransactionRequest {
+    fn from(tx: TxEip2930) -> Self {
+        Self {
+            to: Some(tx.to),
+            energy_price: Some(tx.energy_price),
+            energy: Some(tx.energy_limit),
+            value: Some(tx.value),
+            input: tx.input.into(),
+            nonce: Some(tx.nonce),
+            network_id: tx.network_id,
+            access_list: Some(tx.access_list),
+            transaction_type: Some(1),
+            ..Default::default()
+        }
+    }
+}
+
 impl From<TypedTransaction> for TransactionRequest {
     fn from(tx: TypedTransaction) -> Self {
         match tx {
             TypedTransaction::Legacy(tx) => tx.into(),
+            TypedTransaction::Eip2930(tx) => tx.into(),
         }
     }
 }
@@ -436,4 +554,78 @@ mod tests {
         let serialized = serde_json::to_string(&tx).unwrap();
         assert_eq!(serialized, "{\"networkId\":\"0x0\"}");
     }
+
+    #[test]
+    fn energy_limit_below_intrinsic_energy() {
+        let req = TransactionRequest {
+            nonce: Some(0),
+            energy_price: Some(1),
+            network_id: 1,
+            to: Some(TxKind::Call(IcanAddress::ZERO)),
+            input: TransactionInput::new(Bytes::from_static(&[0, 1, 2])),
+            ..Default::default()
+        };
+        assert_eq!(req.intrinsic_energy(), 21000 + 4 + 2 * 16);
+
+        let req = req.energy_limit(21000);
+        assert_eq!(req.complete_legacy(), Err(vec!["energy_limit"]));
+        assert_eq!(req.get_invalid_common_fields(), vec!["energy_limit"]);
+
+        let req = req.energy_limit(21036);
+        assert_eq!(req.complete_legacy(), Ok(()));
+        assert!(req.get_invalid_common_fields().is_empty());
+    }
+
+    #[test]
+    fn build_eip2930() {
+        let access_list = AccessList(vec![crate::AccessListItem {
+            address: IcanAddress::ZERO,
+            storage_keys: vec![B256::ZERO],
+        }]);
+        let req = TransactionRequest {
+            nonce: Some(0),
+            energy_price: Some(1),
+            energy: Some(30_000),
+            network_id: 1,
+            to: Some(TxKind::Call(IcanAddress::ZERO)),
+            ..Default::default()
+        };
+        assert!(matches!(req.preferred_type(), Ok(TxType::Legacy)));
+        assert!(matches!(req.clone().build_typed_tx(), Ok(TypedTransaction::Legacy(_))));
+
+        let req = req.access_list(access_list.clone());
+        assert!(matches!(req.preferred_type(), Ok(TxType::Eip2930)));
+        assert_eq!(req.intrinsic_energy(), 21000 + 2400 + 1900);
+
+        let tx = req.clone().build_typed_tx().unwrap();
+        assert_eq!(tx.eip2930().unwrap().access_list, access_list);
+        assert_eq!(TransactionRequest::from(tx).access_list, Some(access_list));
+
+        let req = TransactionRequest { access_list: None, transaction_type: Some(1), ..req };
+        assert_eq!(req.complete_preferred(), Err(vec!["access_list"]));
+    }
+
+    #[test]
+    fn unsupported_transaction_type() {
+        let req = TransactionRequest {
+            nonce: Some(0),
+            energy_price: Some(1),
+            energy: Some(21_000),
+            network_id: 1,
+            to: Some(TxKind::Call(IcanAddress::ZERO)),
+            ..Default::default()
+        };
+        let legacy = req.clone().transaction_type(0);
+        assert!(matches!(legacy.preferred_type(), Ok(TxType::Legacy)));
+        assert!(matches!(legacy.build_typed_tx(), Ok(TypedTransaction::Legacy(_))));
+
+        for ty in [2, 7] {
+            let req = req.clone().transaction_type(ty);
+            assert!(
+                matches!(req.preferred_type(), Err(Eip2718Error::UnexpectedType(t)) if t == ty)
+            );
+            assert_eq!(req.complete_preferred(), Err(vec!["transaction_type"]));
+            assert_eq!(req.clone().build_typed_tx(), Err(req));
+        }
+    }
 }
@@ -5,7 +5,7 @@ use serde::{
 };
 use std::fmt;
 
-/// The result of an `eth_getWork` request
+/// The result of an `xcb_getWork` request
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct Work {
     /// The proof-of-work hash.
@@ -59,7 +59,13 @@ impl<'a> Deserialize<'a> for Work {
                 let target = seq
                     .next_element::<B256>()?
                     .ok_or_else(|| A::Error::custom("missing target"))?;
-                let number = seq.next_element::<u64>()?;
+                let number = seq
+                    .next_element::<U256>()?
+                    .map(|number| {
+                        u64::try_from(number)
+                            .map_err(|_| A::Error::custom("block number overflows u64"))
+                    })
+                    .transpose()?;
                 Ok(Work { pow_hash, seed_hash, target, number })
             }
         }
@@ -67,3 +73,25 @@ impl<'a> Deserialize<'a> for Work {
         deserializer.deserialize_any(WorkVisitor)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_work() {
+        let work = Work {
+            pow_hash: B256::with_last_byte(1),
+            seed_hash: B256::with_last_byte(2),
+            target: B256::with_last_byte(3),
+            number: Some(0x42),
+        };
+        let json = serde_json::to_string(&work).unwrap();
+        assert!(json.ends_with(r#","0x42"]"#));
+        assert_eq!(serde_json::from_str::<Work>(&json).unwrap(), work);
+
+        let work = Work { number: None, ..work };
+        let json = serde_json::to_string(&work).unwrap();
+        assert_eq!(serde_json::from_str::<Work>(&json).unwrap(), work);
+    }
+}
@@ -0,0 +1,138 @@
+//! USB HID transport.
+
+use crate::{ApduAnswer, ApduCommand, Exchange, LedgerError};
+use async_trait::async_trait;
+use hidapi::{HidApi, HidDevice};
+use std::{
+    fmt,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+/// The USB vendor ID of Ledger devices.
+const LEDGER_VENDOR_ID: u16 = 0x2c97;
+
+/// The HID usage page of the Ledger interface, reported on macOS and Windows.
+const LEDGER_USAGE_PAGE: u16 = 0xffa0;
+
+/// The channel of the HID framing.
+const LEDGER_CHANNEL: u16 = 0x0101;
+
+/// The tag of APDU packets.
+const LEDGER_PACKET_TAG: u8 = 0x05;
+
+/// The size of HID packets.
+const PACKET_SIZE: usize = 64;
+
+/// The size of the header of HID packets: the channel, the tag and the sequence index.
+const HEADER_SIZE: usize = 5;
+
+/// The timeout when reading from the device, in milliseconds.
+///
+/// This leaves time for the user to review the request on the device.
+const READ_TIMEOUT_MS: i32 = 5 * 60 * 1000;
+
+/// A transport for a Ledger device connected over USB.
+///
+/// Exchanges run on the blocking thread pool of the current [tokio] runtime, as they wait for the
+/// device to answer, which may take as long as the user needs to review the request.
+///
+/// [tokio]: https://docs.rs/tokio
+pub struct HidTransport {
+    device: Arc<Mutex<HidDevice>>,
+}
+
+impl fmt::Debug for HidTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HidTransport").finish_non_exhaustive()
+    }
+}
+
+impl HidTransport {
+    /// Opens the first Ledger device found.
+    pub fn new() -> Result<Self, LedgerError> {
+        let api = HidApi::new()?;
+        let info = api
+            .device_list()
+            .find(|info| {
+                info.vendor_id() == LEDGER_VENDOR_ID
+                    && (info.usage_page() == LEDGER_USAGE_PAGE || info.interface_number() == 0)
+            })
+            .ok_or(LedgerError::DeviceNotFound)?;
+        Ok(Self::from_device(info.open_device(&api)?))
+    }
+
+    /// Creates a transport for an already opened Ledger device.
+    pub fn from_device(device: HidDevice) -> Self {
+        Self { device: Arc::new(Mutex::new(device)) }
+    }
+
+    fn write(device: &HidDevice, apdu: &[u8]) -> Result<(), LedgerError> {
+        // the first packet starts with the length of the APDU
+        let mut payload = Vec::with_capacity(2 + apdu.len());
+        payload.extend_from_slice(&(apdu.len() as u16).to_be_bytes());
+        payload.extend_from_slice(apdu);
+
+        for (sequence, chunk) in payload.chunks(PACKET_SIZE - HEADER_SIZE).enumerate() {
+            // HID reports are prefixed by their ID, always 0
+            let mut report = [0u8; PACKET_SIZE + 1];
+            report[1..3].copy_from_slice(&LEDGER_CHANNEL.to_be_bytes());
+            report[3] = LEDGER_PACKET_TAG;
+            report[4..6].copy_from_slice(&(sequence as u16).to_be_bytes());
+            report[6..6 + chunk.len()].copy_from_slice(chunk);
+            device.write(&report)?;
+        }
+        Ok(())
+    }
+
+    fn read(device: &HidDevice) -> Result<Vec<u8>, LedgerError> {
+        let mut answer = Vec::new();
+        let mut len = None;
+        let mut sequence = 0u16;
+        loop {
+            let mut packet = [0u8; PACKET_SIZE];
+            let read = device.read_timeout(&mut packet, READ_TIMEOUT_MS)?;
+            if read < HEADER_SIZE {
+                return Err(LedgerError::UnexpectedResponse("truncated HID packet"));
+            }
+            if packet[..2] != LEDGER_CHANNEL.to_be_bytes()
+                || packet[2] != LEDGER_PACKET_TAG
+                || packet[3..5] != sequence.to_be_bytes()
+            {
+                return Err(LedgerError::UnexpectedResponse("invalid HID packet header"));
+            }
+
+            let mut chunk = &packet[HEADER_SIZE..read];
+            if sequence == 0 {
+                if chunk.len() < 2 {
+                    return Err(LedgerError::UnexpectedResponse("truncated HID packet"));
+                }
+                len = Some(u16::from_be_bytes([chunk[0], chunk[1]]) as usize);
+                chunk = &chunk[2..];
+            }
+            let len = len.unwrap_or_default();
+            let take = chunk.len().min(len - answer.len());
+            answer.extend_from_slice(&chunk[..take]);
+            if answer.len() == len {
+                return Ok(answer);
+            }
+            sequence = sequence.wrapping_add(1);
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Exchange for HidTransport {
+    async fn exchange(&self, command: &ApduCommand) -> Result<ApduAnswer, LedgerError> {
+        let device = self.device.clone();
+        let command = command.serialize();
+        let answer = tokio::task::spawn_blocking(move || {
+            let device = device.lock().unwrap_or_else(PoisonError::into_inner);
+            Self::write(&device, &command)?;
+            Self::read(&device)
+        })
+        .await
+        .map_err(LedgerError::transport)??;
+        ApduAnswer::deserialize(answer)
+    }
+}
@@ -0,0 +1,32 @@
+#![doc = include_str!("../README.md")]
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/base-rs/core/main/assets/alloy.jpg",
+    html_favicon_url = "https://raw.githubusercontent.com/base-rs/core/main/assets/favicon.ico"
+)]
+#![warn(
+    missing_copy_implementations,
+    missing_debug_implementations,
+    missing_docs,
+    unreachable_pub,
+    clippy::missing_const_for_fn,
+    rustdoc::all
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![deny(unused_must_use, rust_2018_idioms)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod signer;
+pub use signer::LedgerSigner;
+
+mod transport;
+pub use transport::{
+    ApduAnswer, ApduCommand, Exchange, RecordingTransport, ReplayError, ReplayTransport,
+};
+
+#[cfg(feature = "hid")]
+mod hid;
+#[cfg(feature = "hid")]
+pub use hid::HidTransport;
+
+mod types;
+pub use types::{DerivationType, LedgerError};
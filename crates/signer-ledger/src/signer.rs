@@ -0,0 +1,501 @@
+//! Ledger Core app signer.
+
+use crate::{
+    types::{DerivationType, CLA, INS, MAX_CHUNK_LEN, P1_GET_PUBLIC_KEY, P1_SIGN},
+    ApduCommand, Exchange, LedgerError,
+};
+use async_trait::async_trait;
+use atoms_consensus::{SignableTransaction, TxEnvelope, TxLegacy, TypedTransaction};
+use atoms_network::{Ethereum, NetworkSigner, TxSigner};
+use atoms_signer::{
+    sign_transaction_with_network_id, utils::raw_public_key_to_address, Result, Signer,
+    UnsupportedSignerOperation,
+};
+use base_primitives::{eip191_hash_message, ChainId, IcanAddress, Signature, B256};
+use std::fmt;
+
+#[cfg(feature = "hid")]
+use crate::HidTransport;
+
+/// The length of an Ed448 public key.
+const PUBLIC_KEY_LEN: usize = 57;
+
+/// A Ledger device running the Core app, signing with the key at a derivation path.
+///
+/// The signer talks to the device through an [`Exchange`] transport: a `HidTransport` for
+/// devices connected over USB, with the `hid` feature, or a [`ReplayTransport`](crate::ReplayTransport) to replay a
+/// recorded session in tests.
+///
+/// Raw hashes can't be signed, as the app only signs transactions and messages it can display.
+/// All signatures are checked against the address of the signer, which protects against a device
+/// signing with another key than the one the signer was created for.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[cfg(feature = "hid")]
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use atoms_signer::Signer;
+/// use atoms_signer_ledger::{DerivationType, HidTransport, LedgerSigner};
+///
+/// let transport = HidTransport::new()?;
+/// let signer = LedgerSigner::new(transport, DerivationType::LedgerLive(0), 1).await?;
+/// let signature = signer.sign_message(b"hello").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct LedgerSigner<T> {
+    /// The transport to the device.
+    transport: T,
+    /// The derivation path of the key.
+    derivation: DerivationType,
+    /// The encoded derivation path, sent with every command.
+    path: Vec<u8>,
+    /// The public key at the derivation path.
+    public_key: [u8; PUBLIC_KEY_LEN],
+    /// The signer's address.
+    address: IcanAddress,
+    /// The signer's network ID.
+    network_id: ChainId,
+}
+
+impl<T> fmt::Debug for LedgerSigner<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LedgerSigner")
+            .field("derivation", &self.derivation)
+            .field("address", &self.address)
+            .field("network_id", &self.network_id)
+            .finish()
+    }
+}
+
+#[cfg(feature = "hid")]
+impl LedgerSigner<HidTransport> {
+    /// Connects to the first Ledger device found over USB, and creates a signer for the key at
+    /// the given derivation path.
+    pub async fn connect(
+        derivation: DerivationType,
+        network_id: ChainId,
+    ) -> Result<Self, LedgerError> {
+        Self::new(HidTransport::new()?, derivation, network_id).await
+    }
+}
+
+impl<T: Exchange> LedgerSigner<T> {
+    /// Creates a signer for the key at the given derivation path, reading its public key from the
+    /// device.
+    pub async fn new(
+        transport: T,
+        derivation: DerivationType,
+        network_id: ChainId,
+    ) -> Result<Self, LedgerError> {
+        let path = derivation.encode()?;
+        let public_key = get_public_key(&transport, &path, P1_GET_PUBLIC_KEY::NON_CONFIRM).await?;
+        let address = raw_public_key_to_address(&public_key, network_id);
+        Ok(Self { transport, derivation, path, public_key, address, network_id })
+    }
+
+    /// Returns the address of the key at the given derivation path, for the signer's network.
+    pub async fn get_address_with_path(
+        &self,
+        derivation: &DerivationType,
+    ) -> Result<IcanAddress, LedgerError> {
+        let path = derivation.encode()?;
+        let public_key =
+            get_public_key(&self.transport, &path, P1_GET_PUBLIC_KEY::NON_CONFIRM).await?;
+        Ok(raw_public_key_to_address(&public_key, self.network_id))
+    }
+
+    /// Displays the address of the signer on the device, and waits for the user to confirm it.
+    pub async fn confirm_address(&self) -> Result<IcanAddress, LedgerError> {
+        let public_key =
+            get_public_key(&self.transport, &self.path, P1_GET_PUBLIC_KEY::CONFIRM).await?;
+        let address = raw_public_key_to_address(&public_key, self.network_id);
+        self.check_address(address)?;
+        Ok(address)
+    }
+
+    /// Signs a legacy transaction, after the user reviewed it on the device.
+    ///
+    /// The network ID of the transaction is not modified, use [`TxSigner::sign_transaction`] to
+    /// set it to the signer's one.
+    pub async fn sign_tx(&self, tx: &TxLegacy) -> Result<Signature, LedgerError> {
+        self.sign_payload(INS::SIGN, &tx.encoded_for_signing(), &tx.signature_hash()).await
+    }
+
+    /// Signs a message prefixed as specified in [EIP-191], after the user reviewed it on the
+    /// device.
+    ///
+    /// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+    pub async fn sign_personal_message(&self, message: &[u8]) -> Result<Signature, LedgerError> {
+        let mut payload = Vec::with_capacity(4 + message.len());
+        payload.extend_from_slice(&(message.len() as u32).to_be_bytes());
+        payload.extend_from_slice(message);
+        self.sign_payload(INS::SIGN_PERSONAL_MESSAGE, &payload, &eip191_hash_message(message)).await
+    }
+
+    /// Sends the derivation path followed by the payload to the device, in chunks, and checks the
+    /// returned signature of the hash.
+    async fn sign_payload(
+        &self,
+        ins: INS,
+        payload: &[u8],
+        hash: &B256,
+    ) -> Result<Signature, LedgerError> {
+        let mut data = self.path.clone();
+        data.extend_from_slice(payload);
+
+        let mut answer = Vec::new();
+        for (i, chunk) in data.chunks(MAX_CHUNK_LEN).enumerate() {
+            let p1 = if i == 0 { P1_SIGN::FIRST } else { P1_SIGN::MORE };
+            let command = command(ins, p1 as u8, chunk.to_vec());
+            answer = self.transport.exchange(&command).await?.into_result()?;
+        }
+
+        // the signature is followed by the public key of the signer
+        let signature = Signature::try_from(answer.as_slice())?;
+        self.check_address(signature.recover_address_from_prehash(hash, self.network_id)?)?;
+        Ok(signature)
+    }
+
+    fn check_address(&self, address: IcanAddress) -> Result<(), LedgerError> {
+        if address != self.address {
+            return Err(LedgerError::SignerMismatch { expected: self.address, actual: address });
+        }
+        Ok(())
+    }
+}
+
+impl<T> LedgerSigner<T> {
+    /// Returns the transport to the device.
+    pub const fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Returns the derivation path of the key.
+    pub const fn derivation(&self) -> &DerivationType {
+        &self.derivation
+    }
+
+    /// Returns the public key at the derivation path.
+    pub const fn public_key(&self) -> &[u8; PUBLIC_KEY_LEN] {
+        &self.public_key
+    }
+
+    /// Returns this signer's address.
+    #[inline]
+    pub const fn address(&self) -> IcanAddress {
+        self.address
+    }
+
+    /// Returns this signer's network ID.
+    #[inline]
+    pub const fn network_id(&self) -> ChainId {
+        self.network_id
+    }
+
+    /// Sets the network ID, updating the address accordingly.
+    #[inline]
+    pub fn set_network_id(&mut self, network_id: ChainId) {
+        self.network_id = network_id;
+        self.address = raw_public_key_to_address(&self.public_key, network_id);
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<T: Exchange> Signer for LedgerSigner<T> {
+    async fn sign_hash(&self, _hash: &B256) -> Result<Signature> {
+        Err(atoms_signer::Error::UnsupportedOperation(UnsupportedSignerOperation::SignHash))
+    }
+
+    #[inline]
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        self.sign_personal_message(message).await.map_err(Into::into)
+    }
+
+    #[inline]
+    fn address(&self) -> IcanAddress {
+        self.address
+    }
+
+    #[inline]
+    fn network_id(&self) -> ChainId {
+        self.network_id
+    }
+
+    #[inline]
+    fn set_network_id(&mut self, network_id: ChainId) {
+        Self::set_network_id(self, network_id)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<T: Exchange> TxSigner<Signature> for LedgerSigner<T> {
+    fn address(&self) -> IcanAddress {
+        self.address
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<Signature>,
+    ) -> Result<Signature> {
+        sign_transaction_with_network_id!(
+            self,
+            tx,
+            self.sign_payload(INS::SIGN, &tx.encoded_for_signing(), &tx.signature_hash()).await
+        )
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<T: Exchange> NetworkSigner<Ethereum> for LedgerSigner<T> {
+    fn default_signer_address(&self) -> IcanAddress {
+        self.address
+    }
+
+    fn has_signer_for(&self, address: &IcanAddress) -> bool {
+        *address == self.address
+    }
+
+    fn signer_addresses(&self) -> impl Iterator<Item = IcanAddress> {
+        std::iter::once(self.address)
+    }
+
+    async fn sign_transaction_from(
+        &self,
+        sender: IcanAddress,
+        tx: TypedTransaction,
+    ) -> Result<TxEnvelope> {
+        if sender != self.address {
+            return Err(atoms_signer::Error::other(format!(
+                "Missing signing credential for {}",
+                sender
+            )));
+        }
+
+        match tx {
+            TypedTransaction::Legacy(mut t) => {
+                let sig = TxSigner::sign_transaction(self, &mut t).await?;
+                Ok(t.into_signed(sig).into())
+            }
+            TypedTransaction::Eip2930(mut t) => {
+                let sig = TxSigner::sign_transaction(self, &mut t).await?;
+                Ok(t.into_signed(sig).into())
+            }
+        }
+    }
+}
+
+/// Creates a command of the Core app.
+const fn command(ins: INS, p1: u8, data: Vec<u8>) -> ApduCommand {
+    ApduCommand { cla: CLA, ins: ins as u8, p1, p2: 0, data }
+}
+
+/// Reads the public key at the encoded derivation path.
+///
+/// The answer holds the length of the public key, the public key, the length of the address and
+/// the address as displayed by the app. The address is derived from the public key instead, as it
+/// depends on the network.
+async fn get_public_key<T: Exchange + ?Sized>(
+    transport: &T,
+    path: &[u8],
+    p1: P1_GET_PUBLIC_KEY,
+) -> Result<[u8; PUBLIC_KEY_LEN], LedgerError> {
+    let command = command(INS::GET_PUBLIC_KEY, p1 as u8, path.to_vec());
+    let answer = transport.exchange(&command).await?.into_result()?;
+    match answer.split_first() {
+        Some((&len, rest)) if len as usize == PUBLIC_KEY_LEN && rest.len() >= PUBLIC_KEY_LEN => {
+            Ok(rest[..PUBLIC_KEY_LEN].try_into().unwrap())
+        }
+        _ => Err(LedgerError::UnexpectedResponse("invalid public key")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! The sessions replayed here are simulated with a software key: they check the chunking, the
+    //! status words and the signature checks, not the Core app protocol itself. `device_session`
+    //! checks the protocol against a device.
+
+    use super::*;
+    use crate::ReplayTransport;
+    use atoms_signer::Error;
+    use base_primitives::{cAddress, hex, TxKind, U256};
+    use libgoldilocks::{PrehashSigner, SigningKey};
+    use std::str::FromStr;
+
+    const KEY: &str = "7d6231471b5dbb6204fe5129617082792ae468d01a3f3623184c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    /// The `GET_PUBLIC_KEY` command for `m/44'/654'/0'/0'/0'`.
+    const GET_PUBLIC_KEY: &str = "e002000015058000002c8000028e800000008000000080000000";
+
+    fn sign(hash: &B256) -> Signature {
+        SigningKey::from_str(KEY).sign_prehash(hash.as_ref()).unwrap()
+    }
+
+    /// Simulates the `GET_PUBLIC_KEY` exchange of a device holding [`KEY`].
+    fn public_key_exchange() -> String {
+        // signatures are followed by the public key of the signer
+        let signature = sign(&B256::ZERO);
+        let public_key = signature.as_bytes()[114..].to_vec();
+        let address = IcanAddress::from_private_key(&SigningKey::from_str(KEY), 1);
+        let address = hex::encode(address);
+        format!(
+            "=> {GET_PUBLIC_KEY}\n<= 39{}2c{}9000\n",
+            hex::encode(public_key),
+            hex::encode(address)
+        )
+    }
+
+    /// Simulates the exchanges of a signing command, answered with the given hex data.
+    fn sign_exchanges(ins: u8, payload: &[u8], answer: &str) -> String {
+        let mut data = DerivationType::LedgerLive(0).encode().unwrap();
+        data.extend_from_slice(payload);
+
+        let chunks = data.chunks(255).collect::<Vec<_>>();
+        let mut recording = String::new();
+        for (i, chunk) in chunks.iter().enumerate() {
+            let p1 = if i == 0 { 0x00 } else { 0x80 };
+            let last = i + 1 == chunks.len();
+            recording +=
+                &format!("=> e0{ins:02x}{p1:02x}00{:02x}{}\n", chunk.len(), hex::encode(chunk));
+            recording += &format!("<= {}\n", if last { answer } else { "9000" });
+        }
+        recording
+    }
+
+    async fn signer(recording: &str) -> LedgerSigner<ReplayTransport> {
+        let transport = recording.parse().unwrap();
+        LedgerSigner::new(transport, DerivationType::LedgerLive(0), 1).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn get_address() {
+        let recording =
+            public_key_exchange() + &public_key_exchange().replace("e0020000", "e0020100");
+        let signer = signer(&recording).await;
+        let expected = IcanAddress::from_private_key(&SigningKey::from_str(KEY), 1);
+        assert_eq!(signer.address(), expected);
+
+        assert_eq!(signer.confirm_address().await.unwrap(), expected);
+        assert!(signer.transport().is_finished());
+
+        let mut signer = signer;
+        signer.set_network_id(3);
+        assert_eq!(signer.address(), IcanAddress::from_private_key(&SigningKey::from_str(KEY), 3));
+    }
+
+    #[tokio::test]
+    async fn sign_message() {
+        let short = b"hello".to_vec();
+        let long = vec![0x42; 300];
+
+        let mut recording = public_key_exchange();
+        for message in [&short, &long] {
+            let signature = sign(&eip191_hash_message(message));
+            let mut payload = (message.len() as u32).to_be_bytes().to_vec();
+            payload.extend_from_slice(message);
+            let answer = format!("{}9000", hex::encode(signature.as_bytes()));
+            recording += &sign_exchanges(0x08, &payload, &answer);
+        }
+        // the long message is sent in two chunks
+        assert_eq!(recording.matches("=> e00880").count(), 1);
+
+        let signer = signer(&recording).await;
+        for message in [&short, &long] {
+            let signature = signer.sign_message(message).await.unwrap();
+            assert_eq!(signature.recover_address_from_msg(message, 1).unwrap(), signer.address());
+        }
+        assert!(signer.transport().is_finished());
+
+        assert!(signer.sign_hash(&B256::ZERO).await.unwrap_err().is_unsupported());
+    }
+
+    #[tokio::test]
+    async fn sign_transaction() {
+        let tx = TxLegacy {
+            network_id: 1,
+            nonce: 2,
+            energy_price: 1_000_000_000,
+            energy_limit: 21000,
+            to: TxKind::Call(cAddress!("0000d8dA6BF26964aF9D7eEd9e03E53415D37aA96045")),
+            value: U256::from(100),
+            input: Default::default(),
+        };
+        let answer = format!("{}9000", hex::encode(sign(&tx.signature_hash()).as_bytes()));
+        let recording = public_key_exchange()
+            + &sign_exchanges(0x04, &tx.encoded_for_signing(), &answer)
+            + &sign_exchanges(0x04, &tx.encoded_for_signing(), &answer);
+
+        let signer = signer(&recording).await;
+        let signature = signer.sign_tx(&tx).await.unwrap();
+        assert_eq!(tx.clone().into_signed(signature).recover_signer(1).unwrap(), signer.address());
+
+        let envelope =
+            NetworkSigner::<Ethereum>::sign_transaction(&signer, tx.into()).await.unwrap();
+        assert_eq!(envelope.recover_signer(1).unwrap(), signer.address());
+        assert!(signer.transport().is_finished());
+    }
+
+    #[tokio::test]
+    async fn rejected() {
+        let message = b"hello";
+        let mut payload = (message.len() as u32).to_be_bytes().to_vec();
+        payload.extend_from_slice(message);
+
+        // a signature by another key
+        let other = SigningKey::from_str(&KEY.replace('7', "8"));
+        let other = other.sign_prehash(eip191_hash_message(message).as_ref()).unwrap();
+        let answer = format!("{}9000", hex::encode(other.as_bytes()));
+
+        let recording = public_key_exchange()
+            + &sign_exchanges(0x08, &payload, "6985")
+            + &sign_exchanges(0x08, &payload, &answer);
+        let signer = signer(&recording).await;
+
+        assert!(matches!(signer.sign_personal_message(message).await, Err(LedgerError::Rejected)));
+        assert!(matches!(
+            signer.sign_personal_message(message).await,
+            Err(LedgerError::SignerMismatch { .. })
+        ));
+
+        // transport errors are signer errors too, from which they can be recovered
+        let err = signer.sign_message(message).await.unwrap_err();
+        let Error::Other(err) = err else { panic!("unexpected error: {err:?}") };
+        assert!(matches!(err.downcast_ref::<LedgerError>(), Some(LedgerError::Transport(_))));
+    }
+
+    // Signs with a device running the Core app, and saves the session to
+    // `testdata/ledger_session.txt` so that it can be replayed without a device.
+    #[cfg(feature = "hid")]
+    #[tokio::test]
+    #[ignore = "requires a Ledger device running the Core app"]
+    async fn device_session() {
+        let transport = crate::RecordingTransport::new(HidTransport::new().unwrap());
+        let signer = LedgerSigner::new(transport, DerivationType::LedgerLive(0), 1).await.unwrap();
+
+        let message = b"hello core";
+        let signature = signer.sign_message(message).await.unwrap();
+        assert_eq!(signature.recover_address_from_msg(message, 1).unwrap(), signer.address());
+
+        let tx = TxLegacy {
+            network_id: 1,
+            nonce: 2,
+            energy_price: 1_000_000_000,
+            energy_limit: 21000,
+            to: TxKind::Call(cAddress!("0000d8dA6BF26964aF9D7eEd9e03E53415D37aA96045")),
+            value: U256::from(100),
+            input: Default::default(),
+        };
+        let signature = signer.sign_tx(&tx).await.unwrap();
+        assert_eq!(tx.into_signed(signature).recover_signer(1).unwrap(), signer.address());
+
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("ledger_session.txt"), signer.transport().recording()).unwrap();
+    }
+}
@@ -0,0 +1,332 @@
+//! APDU exchange with a Ledger device.
+
+use crate::LedgerError;
+use async_trait::async_trait;
+use base_primitives::hex;
+use std::{
+    collections::VecDeque,
+    fmt,
+    str::FromStr,
+    sync::{Mutex, PoisonError},
+};
+use thiserror::Error;
+
+/// A command sent to a Ledger device.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ApduCommand {
+    /// The instruction class.
+    pub cla: u8,
+    /// The instruction code.
+    pub ins: u8,
+    /// The first instruction parameter.
+    pub p1: u8,
+    /// The second instruction parameter.
+    pub p2: u8,
+    /// The command data, at most 255 bytes.
+    pub data: Vec<u8>,
+}
+
+impl ApduCommand {
+    /// Serializes the command: its header, followed by the length of the data and the data.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut serialized = Vec::with_capacity(5 + self.data.len());
+        serialized.extend_from_slice(&[self.cla, self.ins, self.p1, self.p2]);
+        serialized.push(self.data.len() as u8);
+        serialized.extend_from_slice(&self.data);
+        serialized
+    }
+}
+
+/// An answer received from a Ledger device.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ApduAnswer {
+    /// The answer data.
+    data: Vec<u8>,
+    /// The status word.
+    status: u16,
+}
+
+impl ApduAnswer {
+    /// Creates an answer with the given data and status word.
+    pub const fn new(data: Vec<u8>, status: u16) -> Self {
+        Self { data, status }
+    }
+
+    /// Deserializes an answer: its data, followed by the two bytes of the status word.
+    pub fn deserialize(mut answer: Vec<u8>) -> Result<Self, LedgerError> {
+        if answer.len() < 2 {
+            return Err(LedgerError::UnexpectedResponse("answer is missing the status word"));
+        }
+        let status = answer.split_off(answer.len() - 2);
+        Ok(Self { data: answer, status: u16::from_be_bytes([status[0], status[1]]) })
+    }
+
+    /// Serializes the answer: its data, followed by the two bytes of the status word.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut serialized = self.data.clone();
+        serialized.extend_from_slice(&self.status.to_be_bytes());
+        serialized
+    }
+
+    /// Returns the answer data.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the status word.
+    pub const fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Returns the answer data, or an error if the status word does not denote success.
+    pub fn into_result(self) -> Result<Vec<u8>, LedgerError> {
+        match LedgerError::from_status(self.status) {
+            None => Ok(self.data),
+            Some(err) => Err(err),
+        }
+    }
+}
+
+/// A transport exchanging APDUs with a Ledger device.
+///
+/// This is implemented by [`HidTransport`](crate::HidTransport) for devices connected over USB,
+/// and by [`ReplayTransport`] to replay recorded sessions in tests.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait Exchange: Send + Sync {
+    /// Sends a command to the device and returns its answer.
+    ///
+    /// The status word of the answer is not checked.
+    async fn exchange(&self, command: &ApduCommand) -> Result<ApduAnswer, LedgerError>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<T: Exchange + ?Sized> Exchange for Box<T> {
+    async fn exchange(&self, command: &ApduCommand) -> Result<ApduAnswer, LedgerError> {
+        (**self).exchange(command).await
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<T: Exchange + ?Sized> Exchange for std::sync::Arc<T> {
+    async fn exchange(&self, command: &ApduCommand) -> Result<ApduAnswer, LedgerError> {
+        (**self).exchange(command).await
+    }
+}
+
+/// Error thrown by [`ReplayTransport`].
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum ReplayError {
+    /// A line of the recording could not be parsed.
+    #[error("invalid recording line {line}: {reason}")]
+    InvalidRecording {
+        /// The line number, starting at 1.
+        line: usize,
+        /// The reason the line is invalid.
+        reason: &'static str,
+    },
+    /// The command sent is not the next one of the recording.
+    #[error("unexpected command {actual}, expected {expected}")]
+    UnexpectedCommand {
+        /// The hex encoded command of the recording.
+        expected: String,
+        /// The hex encoded command sent.
+        actual: String,
+    },
+    /// All the commands of the recording were already sent.
+    #[error("unexpected command {0}, the recording is over")]
+    RecordingOver(String),
+}
+
+/// A transport replaying a recorded session, without a device.
+///
+/// Recordings are made of `=>` lines, holding hex encoded commands, each followed by a `<=` line
+/// holding the hex encoded answer, including the status word. Empty lines and lines starting with
+/// `#` are ignored. This is the format of [`RecordingTransport::recording`]:
+///
+/// ```text
+/// # get the public key of m/44'/654'/0'/0'/0'
+/// => e002000015058000002c8000028e800000008000000080000000
+/// <= 39...9000
+/// ```
+///
+/// Each command sent must be the next one of the recording, or the exchange fails.
+#[derive(Debug)]
+pub struct ReplayTransport {
+    exchanges: Mutex<VecDeque<(Vec<u8>, Vec<u8>)>>,
+}
+
+impl FromStr for ReplayTransport {
+    type Err = ReplayError;
+
+    fn from_str(recording: &str) -> Result<Self, Self::Err> {
+        let mut exchanges = VecDeque::new();
+        let mut command = None;
+        for (i, line) in recording.lines().enumerate() {
+            let invalid = |reason| ReplayError::InvalidRecording { line: i + 1, reason };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(hex) = line.strip_prefix("=>") {
+                if command.is_some() {
+                    return Err(invalid("command without answer"));
+                }
+                command = Some(hex::decode(hex.trim()).map_err(|_| invalid("invalid hex"))?);
+            } else if let Some(hex) = line.strip_prefix("<=") {
+                let command = command.take().ok_or_else(|| invalid("answer without command"))?;
+                let answer = hex::decode(hex.trim()).map_err(|_| invalid("invalid hex"))?;
+                if answer.len() < 2 {
+                    return Err(invalid("answer without status word"));
+                }
+                exchanges.push_back((command, answer));
+            } else {
+                return Err(invalid("expected `=>` or `<=`"));
+            }
+        }
+        if command.is_some() {
+            return Err(ReplayError::InvalidRecording {
+                line: recording.lines().count(),
+                reason: "command without answer",
+            });
+        }
+        Ok(Self { exchanges: Mutex::new(exchanges) })
+    }
+}
+
+impl ReplayTransport {
+    /// Returns the number of exchanges of the recording which were not replayed yet.
+    pub fn remaining(&self) -> usize {
+        self.exchanges.lock().unwrap_or_else(PoisonError::into_inner).len()
+    }
+
+    /// Returns `true` if all the exchanges of the recording were replayed.
+    pub fn is_finished(&self) -> bool {
+        self.remaining() == 0
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Exchange for ReplayTransport {
+    async fn exchange(&self, command: &ApduCommand) -> Result<ApduAnswer, LedgerError> {
+        let actual = command.serialize();
+        let mut exchanges = self.exchanges.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some((expected, answer)) = exchanges.front() else {
+            return Err(LedgerError::transport(ReplayError::RecordingOver(hex::encode(actual))));
+        };
+        if *expected != actual {
+            return Err(LedgerError::transport(ReplayError::UnexpectedCommand {
+                expected: hex::encode(expected),
+                actual: hex::encode(actual),
+            }));
+        }
+        let answer = ApduAnswer::deserialize(answer.clone())?;
+        exchanges.pop_front();
+        Ok(answer)
+    }
+}
+
+/// A transport recording the exchanges of another one, to be replayed with a
+/// [`ReplayTransport`].
+pub struct RecordingTransport<T> {
+    inner: T,
+    recording: Mutex<String>,
+}
+
+impl<T> fmt::Debug for RecordingTransport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordingTransport").finish_non_exhaustive()
+    }
+}
+
+impl<T> RecordingTransport<T> {
+    /// Records the exchanges of the given transport.
+    pub const fn new(inner: T) -> Self {
+        Self { inner, recording: Mutex::new(String::new()) }
+    }
+
+    /// Returns the recording of the exchanges so far.
+    pub fn recording(&self) -> String {
+        self.recording.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Consumes the transport and returns the recorded one.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<T: Exchange> Exchange for RecordingTransport<T> {
+    async fn exchange(&self, command: &ApduCommand) -> Result<ApduAnswer, LedgerError> {
+        let answer = self.inner.exchange(command).await?;
+        let mut recording = self.recording.lock().unwrap_or_else(PoisonError::into_inner);
+        recording.push_str(&format!("=> {}\n", hex::encode(command.serialize())));
+        recording.push_str(&format!("<= {}\n", hex::encode(answer.serialize())));
+        Ok(answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(ins: u8, data: &[u8]) -> ApduCommand {
+        ApduCommand { cla: 0xe0, ins, p1: 0, p2: 0, data: data.to_vec() }
+    }
+
+    #[tokio::test]
+    async fn replay() {
+        let transport: ReplayTransport = "
+            # a comment
+            => e00200000201ff
+            <= abcd9000
+
+            => e004000000
+            <= 6985
+        "
+        .parse()
+        .unwrap();
+        assert_eq!(transport.remaining(), 2);
+
+        let answer = transport.exchange(&command(0x02, &[0x01, 0xff])).await.unwrap();
+        assert_eq!(answer, ApduAnswer::new(vec![0xab, 0xcd], 0x9000));
+        assert_eq!(answer.into_result().unwrap(), [0xab, 0xcd]);
+
+        // a wrong command doesn't consume the exchange
+        let err = transport.exchange(&command(0x08, &[])).await.unwrap_err();
+        assert!(err.to_string().contains("expected e004000000"), "{err}");
+
+        let answer = transport.exchange(&command(0x04, &[])).await.unwrap();
+        assert!(matches!(answer.into_result(), Err(LedgerError::Rejected)));
+        assert!(transport.is_finished());
+        assert!(transport.exchange(&command(0x04, &[])).await.is_err());
+    }
+
+    #[test]
+    fn invalid_recording() {
+        for (recording, line) in
+            [("=> e0\n=> e0", 2), ("<= 9000", 1), ("=> e0\n<= 90", 2), ("e0", 1), ("=> zz", 1)]
+        {
+            assert!(matches!(
+                recording.parse::<ReplayTransport>(),
+                Err(ReplayError::InvalidRecording { line: l, .. }) if l == line
+            ));
+        }
+        assert!("=> e0".parse::<ReplayTransport>().is_err());
+    }
+
+    #[tokio::test]
+    async fn record_and_replay() {
+        let recording = "=> e00200000201ff\n<= abcd9000\n";
+        let transport = RecordingTransport::new(recording.parse::<ReplayTransport>().unwrap());
+        transport.exchange(&command(0x02, &[0x01, 0xff])).await.unwrap();
+        assert_eq!(transport.recording(), recording);
+        assert!(transport.into_inner().is_finished());
+    }
+}
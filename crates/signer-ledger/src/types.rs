@@ -0,0 +1,204 @@
+//! Helpers for interacting with the Core Ledger App.
+//!
+//! The instruction codes and the layout of the answers follow the [Ethereum app]. Run the ignored
+//! `device_session` test with the `hid` feature to check them against a device.
+//!
+//! [Ethereum app]: https://github.com/LedgerHQ/app-ethereum/blob/master/doc/ethapp.adoc
+
+use base_primitives::IcanAddress;
+use std::fmt;
+use thiserror::Error;
+
+/// The class of the Core app's APDUs.
+pub(crate) const CLA: u8 = 0xe0;
+
+/// The maximum length of the data of an APDU.
+pub(crate) const MAX_CHUNK_LEN: usize = 255;
+
+/// The maximum number of components of a derivation path.
+pub(crate) const MAX_PATH_LEN: usize = 10;
+
+/// Offset for hardened derivation path components.
+const HARDENED: u32 = 0x8000_0000;
+
+/// Ledger wallet type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DerivationType {
+    /// Ledger Live-generated HD path: `m/44'/654'/{index}'/0'/0'`.
+    LedgerLive(usize),
+    /// Legacy generated HD Path: `m/44'/654'/0'/{index}'`.
+    Legacy(usize),
+    /// Any other path, whose components must all be hardened.
+    Other(String),
+}
+
+impl fmt::Display for DerivationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LedgerLive(index) => write!(f, "m/44'/654'/{index}'/0'/0'"),
+            Self::Legacy(index) => write!(f, "m/44'/654'/0'/{index}'"),
+            Self::Other(inner) => f.write_str(inner),
+        }
+    }
+}
+
+impl DerivationType {
+    /// Encodes the derivation path as expected by the Core app: the number of components,
+    /// followed by each component as a big-endian `u32`.
+    ///
+    /// As with the mnemonic wallets, ed448 has no non-hardened derivation, so every component
+    /// must be hardened.
+    pub(crate) fn encode(&self) -> Result<Vec<u8>, LedgerError> {
+        let path = self.to_string();
+        let invalid = || LedgerError::InvalidDerivationPath(path.clone());
+
+        let components = path
+            .strip_prefix("m/")
+            .ok_or_else(invalid)?
+            .split('/')
+            .map(|component| match component.strip_suffix('\'').map(str::parse::<u32>) {
+                Some(Ok(index)) if index < HARDENED => Ok(index | HARDENED),
+                _ => Err(invalid()),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if components.len() > MAX_PATH_LEN {
+            return Err(invalid());
+        }
+
+        let mut encoded = Vec::with_capacity(1 + components.len() * 4);
+        encoded.push(components.len() as u8);
+        for component in components {
+            encoded.extend_from_slice(&component.to_be_bytes());
+        }
+        Ok(encoded)
+    }
+}
+
+/// Error when using the Ledger transport.
+#[derive(Debug, Error)]
+pub enum LedgerError {
+    /// The user rejected the request on the device.
+    #[error("request rejected on the device")]
+    Rejected,
+    /// The device is locked.
+    #[error("the device is locked")]
+    Locked,
+    /// The Core app is not open on the device.
+    #[error("the Core app is not open on the device")]
+    AppNotOpen,
+    /// The device answered with an unexpected status word.
+    #[error("unexpected status word {0:#06x}")]
+    Status(u16),
+    /// The device answered with malformed data.
+    #[error("unexpected response from the device: {0}")]
+    UnexpectedResponse(&'static str),
+    /// The device signed with another key than the signer's one.
+    #[error("signed by {actual} instead of {expected}")]
+    SignerMismatch {
+        /// The address of the signer.
+        expected: IcanAddress,
+        /// The address of the key used by the device.
+        actual: IcanAddress,
+    },
+    /// The derivation path is invalid.
+    #[error("invalid derivation path: {0}")]
+    InvalidDerivationPath(String),
+    /// No Ledger device is connected.
+    #[error("no Ledger device found")]
+    DeviceNotFound,
+    /// The transport failed to exchange an APDU.
+    #[error(transparent)]
+    Transport(Box<dyn std::error::Error + Send + Sync + 'static>),
+    /// [`hidapi`] error.
+    #[cfg(feature = "hid")]
+    #[error(transparent)]
+    HidError(#[from] hidapi::HidError),
+    /// [`base_primitives`] signature error.
+    #[error(transparent)]
+    SignatureError(#[from] base_primitives::SignatureError),
+}
+
+impl LedgerError {
+    /// Creates a transport error.
+    pub fn transport(err: impl std::error::Error + Send + Sync + 'static) -> Self {
+        Self::Transport(Box::new(err))
+    }
+
+    /// Maps a status word to an error, or returns `None` if it denotes success.
+    pub(crate) fn from_status(status: u16) -> Option<Self> {
+        match status {
+            0x9000 => None,
+            0x6985 => Some(Self::Rejected),
+            0x5515 | 0x6b0c => Some(Self::Locked),
+            0x6d00 | 0x6e00 | 0x6e01 | 0x6511 => Some(Self::AppNotOpen),
+            status => Some(Self::Status(status)),
+        }
+    }
+}
+
+impl From<LedgerError> for atoms_signer::Error {
+    fn from(error: LedgerError) -> Self {
+        Self::other(error)
+    }
+}
+
+/// Core app instructions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+#[allow(non_camel_case_types)]
+pub(crate) enum INS {
+    GET_PUBLIC_KEY = 0x02,
+    SIGN = 0x04,
+    SIGN_PERSONAL_MESSAGE = 0x08,
+}
+
+/// First parameter of the `GET_PUBLIC_KEY` instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+#[allow(non_camel_case_types)]
+pub(crate) enum P1_GET_PUBLIC_KEY {
+    /// Return the public key without confirmation.
+    NON_CONFIRM = 0x00,
+    /// Display the address and ask for confirmation.
+    CONFIRM = 0x01,
+}
+
+/// First parameter of the signing instructions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+#[allow(non_camel_case_types)]
+pub(crate) enum P1_SIGN {
+    /// First chunk of the payload.
+    FIRST = 0x00,
+    /// Subsequent chunk of the payload.
+    MORE = 0x80,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_derivation_path() {
+        assert_eq!(
+            DerivationType::LedgerLive(1).encode().unwrap(),
+            [5, 0x80, 0, 0, 0x2c, 0x80, 0, 0x02, 0x8e, 0x80, 0, 0, 1, 0x80, 0, 0, 0, 0x80, 0, 0, 0]
+        );
+        assert_eq!(
+            DerivationType::Legacy(2).encode().unwrap(),
+            [4, 0x80, 0, 0, 0x2c, 0x80, 0, 0x02, 0x8e, 0x80, 0, 0, 0, 0x80, 0, 0, 2]
+        );
+        assert_eq!(DerivationType::Other("m/0'".into()).encode().unwrap(), [1, 0x80, 0, 0, 0]);
+
+        // ed448 has no non-hardened derivation
+        let too_long = "m/0'/0'/0'/0'/0'/0'/0'/0'/0'/0'/0'";
+        for path in
+            ["44'/654'", "m/a'", "m/2147483648'", too_long, "m/", "m/0", "m/44'/654'/0'/0/0"]
+        {
+            assert!(matches!(
+                DerivationType::Other(path.into()).encode(),
+                Err(LedgerError::InvalidDerivationPath(_))
+            ));
+        }
+    }
+}
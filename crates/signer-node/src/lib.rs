@@ -0,0 +1,413 @@
+#![doc = include_str!("../README.md")]
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/base-rs/core/main/assets/alloy.jpg",
+    html_favicon_url = "https://raw.githubusercontent.com/base-rs/core/main/assets/favicon.ico"
+)]
+#![warn(
+    missing_copy_implementations,
+    missing_debug_implementations,
+    missing_docs,
+    unreachable_pub,
+    clippy::missing_const_for_fn,
+    rustdoc::all
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![deny(unused_must_use, rust_2018_idioms)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use alloy_rlp::Decodable;
+use async_trait::async_trait;
+use atoms_consensus::{
+    SignableTransaction, Transaction, TxEip2930, TxEnvelope, TxType, TypedTransaction,
+};
+use atoms_eips::eip2718::Decodable2718;
+use atoms_network::{Ethereum, NetworkSigner, TxSigner};
+use atoms_rpc_client::RpcClient;
+use atoms_rpc_types::TransactionRequest;
+use atoms_signer::{Error, Result, Signer, UnsupportedSignerOperation};
+use atoms_transport::Transport;
+use base_primitives::{Bytes, ChainId, IcanAddress, Signature, B256};
+use serde::Deserialize;
+use std::fmt;
+use zeroize::Zeroizing;
+
+/// The result of `xcb_signTransaction`.
+#[derive(Deserialize)]
+struct SignTransactionResult {
+    /// The EIP-2718 encoded signed transaction.
+    raw: Bytes,
+}
+
+/// A signer that delegates signing to the account management RPCs of a go-core node.
+///
+/// The account must be unlocked on the node. If a password is set with
+/// [`with_password`](Self::with_password), messages are signed with `personal_sign`, which
+/// unlocks the account for the duration of the call, instead of `xcb_sign`.
+///
+/// Raw hashes can't be signed, as the node only signs messages prefixed as specified in
+/// [EIP-191]. Transactions are signed with `xcb_signTransaction`, and the transaction returned by
+/// the node is checked against the requested one. The signer of every signature returned by the
+/// node is recovered and checked against the signer's address.
+///
+/// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+#[derive(Clone)]
+pub struct NodeSigner<T> {
+    /// The client of the node holding the account.
+    client: RpcClient<T>,
+    /// The address of the account.
+    address: IcanAddress,
+    /// The network ID of the signer.
+    network_id: ChainId,
+    /// The password of the account, used with `personal_sign`, zeroized when dropped.
+    password: Option<Zeroizing<String>>,
+}
+
+impl<T> fmt::Debug for NodeSigner<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeSigner")
+            .field("address", &self.address)
+            .field("network_id", &self.network_id)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+impl<T> NodeSigner<T> {
+    /// Creates a new signer for the account with the given address, held by the node behind the
+    /// client.
+    pub const fn new(client: RpcClient<T>, address: IcanAddress, network_id: ChainId) -> Self {
+        Self { client, address, network_id, password: None }
+    }
+
+    /// Sets the password used to sign messages with `personal_sign`.
+    pub fn with_password(mut self, password: impl Into<String>) -> Self {
+        self.password = Some(Zeroizing::new(password.into()));
+        self
+    }
+
+    /// Returns the client of the node holding the account.
+    pub const fn client(&self) -> &RpcClient<T> {
+        &self.client
+    }
+}
+
+impl<T: Transport + Clone> NodeSigner<T> {
+    /// Signs the transaction request with `xcb_signTransaction`, using the signer's address if
+    /// the `from` field is not set.
+    ///
+    /// The node fills the missing fields of the request, so the returned transaction should be
+    /// checked by the caller if the request is incomplete.
+    pub async fn sign_transaction_request(
+        &self,
+        mut request: TransactionRequest,
+    ) -> Result<TxEnvelope> {
+        request.from.get_or_insert(self.address);
+        let result: SignTransactionResult =
+            self.client.request("xcb_signTransaction", (request,)).await.map_err(Error::other)?;
+        TxEnvelope::decode_2718(&mut result.raw.as_ref()).map_err(Error::other)
+    }
+
+    fn check_address(&self, recovered: IcanAddress) -> Result<()> {
+        if recovered != self.address {
+            return Err(Error::SignerMismatch { expected: self.address, recovered });
+        }
+        Ok(())
+    }
+
+    /// Checks that the transaction signed by the node was signed by the signer's account.
+    fn check_envelope(&self, envelope: &TxEnvelope) -> Result<()> {
+        self.check_address(envelope.recover_signer(self.network_id)?)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<T: Transport + Clone> Signer for NodeSigner<T> {
+    async fn sign_hash(&self, _hash: &B256) -> Result<Signature> {
+        Err(Error::UnsupportedOperation(UnsupportedSignerOperation::SignHash))
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        let bytes = Bytes::copy_from_slice(message);
+        let signature: Bytes = match &self.password {
+            Some(password) => {
+                self.client.request("personal_sign", (bytes, self.address, password.as_str())).await
+            }
+            None => self.client.request("xcb_sign", (self.address, bytes)).await,
+        }
+        .map_err(Error::other)?;
+        let signature = Signature::try_from(signature.as_ref())?;
+        self.check_address(signature.recover_address_from_msg(message, self.network_id)?)?;
+        Ok(signature)
+    }
+
+    #[inline]
+    fn address(&self) -> IcanAddress {
+        self.address
+    }
+
+    #[inline]
+    fn network_id(&self) -> ChainId {
+        self.network_id
+    }
+
+    #[inline]
+    fn set_network_id(&mut self, network_id: ChainId) {
+        self.network_id = network_id;
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<T: Transport + Clone> TxSigner<Signature> for NodeSigner<T> {
+    fn address(&self) -> IcanAddress {
+        self.address
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<Signature>,
+    ) -> Result<Signature> {
+        if !tx.set_chain_id_checked(self.network_id) {
+            return Err(Error::TransactionNetworkIdMismatch {
+                signer: self.network_id,
+                tx: tx.chain_id(),
+            });
+        }
+
+        let envelope = self.sign_transaction_request(request_for(tx)?).await?;
+        if envelope.signature_hash() != tx.signature_hash() {
+            return Err(Error::other("node signed a different transaction than requested"));
+        }
+        self.check_envelope(&envelope)?;
+        Ok(signature(envelope))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<T: Transport + Clone> NetworkSigner<Ethereum> for NodeSigner<T> {
+    fn default_signer_address(&self) -> IcanAddress {
+        self.address
+    }
+
+    fn has_signer_for(&self, address: &IcanAddress) -> bool {
+        *address == self.address
+    }
+
+    fn signer_addresses(&self) -> impl Iterator<Item = IcanAddress> {
+        std::iter::once(self.address)
+    }
+
+    async fn sign_transaction_from(
+        &self,
+        sender: IcanAddress,
+        tx: TypedTransaction,
+    ) -> Result<TxEnvelope> {
+        if sender != self.address {
+            return Err(Error::other(format!("Missing signing credential for {}", sender)));
+        }
+
+        let envelope = self.sign_transaction_request(tx.clone().into()).await?;
+        if TypedTransaction::from(envelope.clone()) != tx {
+            return Err(Error::other("node signed a different transaction than requested"));
+        }
+        self.check_envelope(&envelope)?;
+        Ok(envelope)
+    }
+}
+
+/// Converts a transaction to sign into a request for `xcb_signTransaction`.
+fn request_for(tx: &dyn SignableTransaction<Signature>) -> Result<TransactionRequest> {
+    // the signing payload of typed transactions is prefixed by their type, unlike the RLP list of
+    // legacy transactions
+    let encoded = tx.encoded_for_signing();
+    if encoded.first() == Some(&u8::from(TxType::Eip2930)) {
+        let tx = TxEip2930::decode(&mut &encoded[1..]).map_err(Error::other)?;
+        return Ok(tx.into());
+    }
+
+    Ok(TransactionRequest {
+        to: Some(tx.to()),
+        value: Some(tx.value()),
+        input: Bytes::copy_from_slice(tx.input()).into(),
+        nonce: Some(tx.nonce()),
+        energy_price: tx.gas_price(),
+        energy: Some(tx.gas_limit()),
+        network_id: tx.chain_id(),
+        ..Default::default()
+    })
+}
+
+/// Returns the signature of a signed transaction.
+fn signature(envelope: TxEnvelope) -> Signature {
+    match envelope {
+        TxEnvelope::Legacy(tx) => tx.into_parts().1,
+        TxEnvelope::Eip2930(tx) => tx.into_parts().1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atoms_consensus::TxLegacy;
+    use atoms_eips::{
+        eip2718::Encodable2718,
+        eip2930::{AccessList, AccessListItem},
+    };
+    use atoms_json_rpc::{Id, Response, ResponsePayload};
+    use atoms_pubsub::PubSubFrontend;
+    use atoms_rpc_client::ClientBuilder;
+    use atoms_transport_ipc::{IpcConnect, MockIpcServer};
+    use base_primitives::{cAddress, utils::eip191_hash_message, TxKind, U256};
+    use libgoldilocks::{PrehashSigner, SigningKey};
+    use std::str::FromStr;
+
+    const KEY: &str = "7d6231471b5dbb6204fe5129617082792ae468d01a3f3623184c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+    /// The key of another account unlocked on the node.
+    const OTHER_KEY: &str = "6f142508b4eea641e33cb2a0161221105086a84584c74245ca463a49effea30b6f142508b4eea641e33cb2a0161221105086a84584c74245ca";
+
+    /// Spawns a mock node answering the requests with the given results, in order, and returns a
+    /// signer for the account of [`KEY`] connected to it.
+    async fn signer<R: serde::Serialize>(results: Vec<R>) -> NodeSigner<PubSubFrontend> {
+        let mut server = MockIpcServer::new();
+        for (id, result) in results.into_iter().enumerate() {
+            server.add_response(Response {
+                id: Id::Number(id as u64),
+                payload: ResponsePayload::Success(result),
+            });
+        }
+        let path = server.path();
+        server.spawn().await;
+
+        let client = ClientBuilder::default().ipc(IpcConnect::new(path)).await.unwrap();
+        let address = IcanAddress::from_private_key(&SigningKey::from_str(KEY), 1);
+        NodeSigner::new(client, address, 1)
+    }
+
+    fn raw_result(envelope: &TxEnvelope) -> serde_json::Value {
+        serde_json::json!({ "raw": Bytes::from(envelope.encoded_2718()) })
+    }
+
+    fn legacy_tx() -> TxLegacy {
+        TxLegacy {
+            network_id: 1,
+            nonce: 2,
+            energy_price: 1_000_000_000,
+            energy_limit: 21000,
+            to: TxKind::Call(cAddress!("0000d8dA6BF26964aF9D7eEd9e03E53415D37aA96045")),
+            value: U256::from(100),
+            input: Default::default(),
+        }
+    }
+
+    fn sign<S: SignableTransaction<Signature>>(tx: S) -> TxEnvelope
+    where
+        atoms_consensus::Signed<S>: Into<TxEnvelope>,
+    {
+        sign_with(KEY, tx)
+    }
+
+    fn sign_with<S: SignableTransaction<Signature>>(key: &str, tx: S) -> TxEnvelope
+    where
+        atoms_consensus::Signed<S>: Into<TxEnvelope>,
+    {
+        let key = SigningKey::from_str(key);
+        let signature = key.sign_prehash(tx.signature_hash().as_ref()).unwrap();
+        tx.into_signed(signature).into()
+    }
+
+    #[tokio::test]
+    async fn sign_message() {
+        let message = b"hello";
+        let key = SigningKey::from_str(KEY);
+        let expected = key.sign_prehash(eip191_hash_message(message).as_ref()).unwrap();
+
+        let signer = signer(vec![Bytes::copy_from_slice(&expected.as_bytes())]).await;
+        let signature = signer.sign_message(message).await.unwrap();
+        assert_eq!(signature, expected);
+        assert_eq!(signature.recover_address_from_msg(message, 1).unwrap(), signer.address);
+
+        assert!(signer.sign_hash(&B256::ZERO).await.unwrap_err().is_unsupported());
+    }
+
+    #[tokio::test]
+    async fn sign_transaction() {
+        let tx = legacy_tx();
+        let expected = sign(tx.clone());
+
+        let signer = signer(vec![raw_result(&expected)]).await;
+        let envelope =
+            NetworkSigner::<Ethereum>::sign_transaction(&signer, tx.into()).await.unwrap();
+        assert_eq!(envelope, expected);
+        assert_eq!(envelope.recover_signer(1).unwrap(), signer.address);
+    }
+
+    #[tokio::test]
+    async fn sign_access_list_transaction() {
+        let mut tx = TxEip2930 {
+            network_id: 1,
+            nonce: 2,
+            energy_price: 1_000_000_000,
+            energy_limit: 30000,
+            to: TxKind::Call(cAddress!("0000d8dA6BF26964aF9D7eEd9e03E53415D37aA96045")),
+            value: U256::from(100),
+            access_list: AccessList(vec![AccessListItem {
+                address: cAddress!("0000d8dA6BF26964aF9D7eEd9e03E53415D37aA96045"),
+                storage_keys: vec![B256::ZERO],
+            }]),
+            input: Default::default(),
+        };
+        let expected = sign(tx.clone());
+
+        let signer = signer(vec![raw_result(&expected)]).await;
+        let signature = TxSigner::sign_transaction(&signer, &mut tx).await.unwrap();
+        assert_eq!(tx.into_signed(signature), *expected.as_eip2930().unwrap());
+    }
+
+    #[tokio::test]
+    async fn reject_different_transaction() {
+        let tx = legacy_tx();
+        let other = sign(TxLegacy { value: U256::from(1_000_000), ..tx.clone() });
+
+        let signer = signer(vec![raw_result(&other), raw_result(&other)]).await;
+        assert!(NetworkSigner::<Ethereum>::sign_transaction(&signer, tx.clone().into())
+            .await
+            .is_err());
+        assert!(TxSigner::sign_transaction(&signer, &mut tx.clone()).await.is_err());
+
+        let tx = TxLegacy { network_id: 3, ..tx };
+        assert!(matches!(
+            TxSigner::sign_transaction(&signer, &mut tx.clone()).await,
+            Err(Error::TransactionNetworkIdMismatch { signer: 1, tx: 3 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn reject_other_account() {
+        let message = b"hello";
+        let other = SigningKey::from_str(OTHER_KEY);
+        let other_address = IcanAddress::from_private_key(&other, 1);
+        let message_signature = other.sign_prehash(eip191_hash_message(message).as_ref()).unwrap();
+
+        let tx = legacy_tx();
+        let envelope = sign_with(OTHER_KEY, tx.clone());
+
+        let signer = signer(vec![
+            serde_json::json!(Bytes::copy_from_slice(&message_signature.as_bytes())),
+            raw_result(&envelope),
+            raw_result(&envelope),
+        ])
+        .await;
+        let mismatch = |result: Result<_>| {
+            assert!(matches!(
+                result,
+                Err(Error::SignerMismatch { expected, recovered })
+                    if expected == signer.address && recovered == other_address
+            ));
+        };
+        mismatch(signer.sign_message(message).await.map(drop));
+        mismatch(TxSigner::sign_transaction(&signer, &mut tx.clone()).await.map(drop));
+        mismatch(NetworkSigner::<Ethereum>::sign_transaction(&signer, tx.into()).await.map(drop));
+    }
+}
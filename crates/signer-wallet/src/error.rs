@@ -1,4 +1,4 @@
-use base_primitives::hex;
+use base_primitives::{hex, IcanAddress};
 use thiserror::Error;
 
 /// Error thrown by [`Wallet`](crate::Wallet).
@@ -24,12 +24,37 @@ pub enum WalletError {
     #[cfg(feature = "mnemonic")]
     Bip39Error(#[from] coins_bip39::MnemonicError),
     /// [`MnemonicBuilder`](super::mnemonic::MnemonicBuilder) error.
-    // #[error(transparent)]
-    // #[cfg(feature = "mnemonic")]
-    // MnemonicBuilderError(#[from] super::mnemonic::MnemonicBuilderError),
+    #[error(transparent)]
+    #[cfg(feature = "mnemonic")]
+    MnemonicBuilderError(#[from] super::mnemonic::MnemonicBuilderError),
+
+    /// [`cryptoki`] error.
+    #[cfg(feature = "pkcs11")]
+    #[error(transparent)]
+    Pkcs11Error(#[from] cryptoki::error::Error),
+    /// No Ed448 key with the given label was found on the PKCS#11 token.
+    #[cfg(feature = "pkcs11")]
+    #[error("no Ed448 key labeled {0:?} found on the token")]
+    Pkcs11KeyNotFound(String),
+    /// Several Ed448 keys with the given label were found on the PKCS#11 token.
+    #[cfg(feature = "pkcs11")]
+    #[error("several Ed448 keys labeled {0:?} found on the token")]
+    Pkcs11AmbiguousKey(String),
+    /// The public key read from the PKCS#11 token is not a valid Ed448 public key.
+    #[cfg(feature = "pkcs11")]
+    #[error("invalid Ed448 public key")]
+    Pkcs11InvalidPublicKey,
 
     /// [`xcb_keystore`] error.
     #[cfg(feature = "keystore")]
     #[error(transparent)]
     EthKeystoreError(#[from] xcb_keystore::KeystoreError),
+    /// No keystore was found for the account in the keystore directory.
+    #[cfg(feature = "keystore")]
+    #[error("no keystore found for account {0}")]
+    KeystoreAccountNotFound(IcanAddress),
+    /// A keystore already exists for the account in the keystore directory.
+    #[cfg(feature = "keystore")]
+    #[error("a keystore already exists for account {0}")]
+    KeystoreAccountExists(IcanAddress),
 }
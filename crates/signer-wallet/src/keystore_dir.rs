@@ -0,0 +1,352 @@
+//! go-core style keystore directory.
+
+use crate::{LocalWallet, WalletError};
+use base_primitives::{hex, ChainId, IcanAddress};
+use rand::{CryptoRng, Rng};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// An account stored in a [`KeystoreDir`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeystoreAccount {
+    /// The address of the account.
+    pub address: IcanAddress,
+    /// The path to the keystore file of the account.
+    pub path: PathBuf,
+}
+
+/// A directory of encrypted JSON keystores, laid out like go-core's `keystore/` directory.
+///
+/// Accounts are identified by the `address` field of their keystore, or the address at the end of
+/// their file name if the field is missing. New keystores are named like go-core's:
+/// `UTC--<creation time>--<address>`.
+///
+/// Keystores are only decrypted to unlock, export or re-encrypt an account, so listing and
+/// looking up accounts is cheap.
+#[derive(Clone, Debug)]
+pub struct KeystoreDir {
+    /// The path to the directory.
+    path: PathBuf,
+    /// The network ID of the accounts.
+    network_id: ChainId,
+}
+
+impl KeystoreDir {
+    /// Opens the keystore directory at the given path, for accounts of the given network.
+    ///
+    /// The directory is created when the first account is stored in it.
+    pub fn new(path: impl Into<PathBuf>, network_id: ChainId) -> Self {
+        Self { path: path.into(), network_id }
+    }
+
+    /// Returns the path to the directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the network ID of the accounts.
+    pub const fn network_id(&self) -> ChainId {
+        self.network_id
+    }
+
+    /// Lists the accounts of the directory, sorted by file name.
+    ///
+    /// Hidden files, directories and files which are not keystores are skipped. A missing directory
+    /// has no accounts.
+    pub fn accounts(&self) -> Result<Vec<KeystoreAccount>, WalletError> {
+        let entries = match fs::read_dir(&self.path) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut accounts = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() || entry.file_name().to_string_lossy().starts_with('.')
+            {
+                continue;
+            }
+            let path = entry.path();
+            if let Some(address) = read_address(&path) {
+                accounts.push(KeystoreAccount { address, path });
+            }
+        }
+        accounts.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(accounts)
+    }
+
+    /// Looks up the account with the given address.
+    pub fn find(&self, address: &IcanAddress) -> Result<Option<KeystoreAccount>, WalletError> {
+        Ok(self.accounts()?.into_iter().find(|account| account.address == *address))
+    }
+
+    /// Returns `true` if the directory holds an account with the given address.
+    pub fn contains(&self, address: &IcanAddress) -> Result<bool, WalletError> {
+        self.find(address).map(|account| account.is_some())
+    }
+
+    /// Decrypts the account with the given address.
+    pub fn unlock(
+        &self,
+        address: &IcanAddress,
+        password: impl AsRef<[u8]>,
+    ) -> Result<LocalWallet, WalletError> {
+        let account = self.get(address)?;
+        LocalWallet::decrypt_keystore(account.path, password, self.network_id)
+    }
+
+    /// Creates a new random account, encrypted with the given password.
+    pub fn new_account<R: Rng + CryptoRng>(
+        &self,
+        rng: &mut R,
+        password: impl AsRef<[u8]>,
+    ) -> Result<(LocalWallet, KeystoreAccount), WalletError> {
+        let wallet = LocalWallet::random_with(rng, self.network_id);
+        let account = self.store(rng, &wallet.private_key_bytes(), password)?;
+        Ok((wallet, account))
+    }
+
+    /// Imports a raw private key as a new account, encrypted with the given password.
+    ///
+    /// Fails if the directory already holds an account for the key.
+    pub fn import<R: Rng + CryptoRng>(
+        &self,
+        rng: &mut R,
+        private_key: impl AsRef<[u8]>,
+        password: impl AsRef<[u8]>,
+    ) -> Result<KeystoreAccount, WalletError> {
+        self.store(rng, private_key.as_ref(), password)
+    }
+
+    /// Exports the account with the given address to the `dest` directory, re-encrypted with
+    /// `new_password`. Returns the path to the exported keystore.
+    pub fn export<R: Rng + CryptoRng>(
+        &self,
+        rng: &mut R,
+        address: &IcanAddress,
+        password: impl AsRef<[u8]>,
+        dest: impl AsRef<Path>,
+        new_password: impl AsRef<[u8]>,
+    ) -> Result<PathBuf, WalletError> {
+        let wallet = self.unlock(address, password)?;
+        let dest = Self::new(dest.as_ref(), self.network_id);
+        Ok(dest.store(rng, &wallet.private_key_bytes(), new_password)?.path)
+    }
+
+    /// Re-encrypts the account with the given address with `new_password`.
+    ///
+    /// The keystore is replaced atomically, keeping its file name.
+    pub fn change_password<R: Rng + CryptoRng>(
+        &self,
+        rng: &mut R,
+        address: &IcanAddress,
+        password: impl AsRef<[u8]>,
+        new_password: impl AsRef<[u8]>,
+    ) -> Result<(), WalletError> {
+        let account = self.get(address)?;
+        let wallet = LocalWallet::decrypt_keystore(&account.path, password, self.network_id)?;
+
+        let file_name = account.path.file_name().unwrap_or_default().to_string_lossy();
+        let tmp_name = format!(".{file_name}.tmp");
+        LocalWallet::encrypt_keystore(
+            &self.path,
+            rng,
+            &wallet.private_key_bytes(),
+            new_password,
+            Some(&tmp_name),
+            self.network_id,
+        )?;
+        fs::rename(self.path.join(tmp_name), &account.path)?;
+        Ok(())
+    }
+
+    /// Deletes the account with the given address, after checking the password.
+    pub fn delete(
+        &self,
+        address: &IcanAddress,
+        password: impl AsRef<[u8]>,
+    ) -> Result<(), WalletError> {
+        let account = self.get(address)?;
+        LocalWallet::decrypt_keystore(&account.path, password, self.network_id)?;
+        fs::remove_file(account.path)?;
+        Ok(())
+    }
+
+    fn get(&self, address: &IcanAddress) -> Result<KeystoreAccount, WalletError> {
+        self.find(address)?.ok_or(WalletError::KeystoreAccountNotFound(*address))
+    }
+
+    /// Encrypts the private key in a new keystore, named after the current time.
+    fn store<R: Rng + CryptoRng>(
+        &self,
+        rng: &mut R,
+        private_key: &[u8],
+        password: impl AsRef<[u8]>,
+    ) -> Result<KeystoreAccount, WalletError> {
+        let wallet = LocalWallet::from_slice(private_key, self.network_id)?;
+        let address = wallet.address();
+        if self.contains(&address)? {
+            return Err(WalletError::KeystoreAccountExists(address));
+        }
+
+        fs::create_dir_all(&self.path)?;
+        let name = keystore_file_name(&address, SystemTime::now());
+        LocalWallet::encrypt_keystore(
+            &self.path,
+            rng,
+            private_key,
+            password,
+            Some(&name),
+            self.network_id,
+        )?;
+        Ok(KeystoreAccount { address, path: self.path.join(name) })
+    }
+}
+
+/// Returns the go-core keystore file name of the account created at the given time:
+/// `UTC--<ISO 8601 time>--<address>`, e.g. `UTC--2023-11-14T22-13-20.000000000Z--cb82...`.
+pub fn keystore_file_name(address: &IcanAddress, time: SystemTime) -> String {
+    let time = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = time.as_secs();
+    let (year, month, day) = civil_from_days((secs / 86400) as i64);
+    let (hour, min, sec) = (secs % 86400 / 3600, secs % 3600 / 60, secs % 60);
+    format!(
+        "UTC--{year:04}-{month:02}-{day:02}T{hour:02}-{min:02}-{sec:02}.{:09}Z--{}",
+        time.subsec_nanos(),
+        hex::encode(address),
+    )
+}
+
+/// Converts days since the Unix epoch to a (year, month, day) date in the proleptic Gregorian
+/// calendar.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+const fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+/// Reads the address of a keystore, from its `address` field or its go-core file name.
+fn read_address(path: &Path) -> Option<IcanAddress> {
+    let contents = fs::read(path).ok()?;
+    let json: serde_json::Value = serde_json::from_slice(&contents).ok()?;
+    // skip files which are not keystores
+    json.get("crypto").or_else(|| json.get("Crypto"))?;
+
+    if let Some(address) = json.get("address").and_then(|address| address.as_str()) {
+        return address.trim_start_matches("0x").parse().ok();
+    }
+    let file_name = path.file_name()?.to_str()?;
+    file_name.rsplit_once("--")?.1.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atoms_signer::SignerSync;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    #[test]
+    fn file_name() {
+        let address = "cb82a5fd22b9bee8b8ab877c86e0a2c21765e1d5bfc5".parse().unwrap();
+        let time = UNIX_EPOCH + Duration::new(1_700_000_000, 5);
+        assert_eq!(
+            keystore_file_name(&address, time),
+            "UTC--2023-11-14T22-13-20.000000005Z--cb82a5fd22b9bee8b8ab877c86e0a2c21765e1d5bfc5"
+        );
+        // as go-core's `toISO8601`, the fraction of a second is never trimmed
+        assert_eq!(
+            keystore_file_name(&address, UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+            "UTC--2023-11-14T22-13-20.000000000Z--cb82a5fd22b9bee8b8ab877c86e0a2c21765e1d5bfc5"
+        );
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(11016), (2000, 2, 29));
+    }
+
+    #[test]
+    fn manage_accounts() {
+        let dir = tempdir().unwrap();
+        let keystore = KeystoreDir::new(dir.path().join("keystore"), 1);
+        let mut rng = rand::thread_rng();
+        assert!(keystore.accounts().unwrap().is_empty());
+
+        let (wallet, account) = keystore.new_account(&mut rng, "first").unwrap();
+        assert_eq!(account.address, wallet.address());
+        assert!(account.path.file_name().unwrap().to_str().unwrap().starts_with("UTC--"));
+
+        let private_key =
+            hex::decode("6f142508b4eea641e33cb2a0161221105086a84584c74245ca463a49effea30b6f142508b4eea641e33cb2a0161221105086a84584c74245ca")
+                .unwrap();
+        let imported = keystore.import(&mut rng, &private_key, "second").unwrap();
+        assert!(matches!(
+            keystore.import(&mut rng, &private_key, "second"),
+            Err(WalletError::KeystoreAccountExists(address)) if address == imported.address
+        ));
+
+        // files which are not keystores are skipped
+        fs::write(keystore.path().join("README"), "not a keystore").unwrap();
+        let accounts = keystore.accounts().unwrap();
+        assert_eq!(accounts.len(), 2);
+        assert!(accounts.contains(&account) && accounts.contains(&imported));
+        assert_eq!(keystore.find(&imported.address).unwrap(), Some(imported.clone()));
+
+        let unlocked = keystore.unlock(&wallet.address(), "first").unwrap();
+        assert_eq!(unlocked, wallet);
+        assert!(keystore.unlock(&wallet.address(), "wrong").is_err());
+
+        keystore.change_password(&mut rng, &wallet.address(), "first", "changed").unwrap();
+        assert!(keystore.unlock(&wallet.address(), "first").is_err());
+        assert_eq!(keystore.unlock(&wallet.address(), "changed").unwrap(), wallet);
+        assert_eq!(keystore.find(&wallet.address()).unwrap(), Some(account));
+
+        let exported = dir.path().join("exported");
+        let path =
+            keystore.export(&mut rng, &imported.address, "second", &exported, "third").unwrap();
+        let wallet = LocalWallet::decrypt_keystore(path, "third", 1).unwrap();
+        assert_eq!(wallet.address(), imported.address);
+        let message = b"hello";
+        assert_eq!(
+            wallet.sign_message_sync(message).unwrap(),
+            keystore
+                .unlock(&imported.address, "second")
+                .unwrap()
+                .sign_message_sync(message)
+                .unwrap()
+        );
+
+        assert!(keystore.delete(&imported.address, "wrong").is_err());
+        keystore.delete(&imported.address, "second").unwrap();
+        assert!(!keystore.contains(&imported.address).unwrap());
+        assert!(matches!(
+            keystore.unlock(&imported.address, "second"),
+            Err(WalletError::KeystoreAccountNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn go_core_file_name() {
+        let dir = tempdir().unwrap();
+        let keystore = KeystoreDir::new(dir.path(), 1);
+        let account = keystore.import(&mut rand::thread_rng(), [1u8; 57], "password").unwrap();
+
+        // keystores without an address field are identified by their file name
+        let mut json: serde_json::Value =
+            serde_json::from_slice(&fs::read(&account.path).unwrap()).unwrap();
+        json.as_object_mut().unwrap().remove("address");
+        fs::write(&account.path, serde_json::to_vec(&json).unwrap()).unwrap();
+        assert_eq!(keystore.accounts().unwrap(), vec![account]);
+    }
+}
@@ -14,36 +14,83 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![deny(unused_must_use, rust_2018_idioms)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
 
-use async_trait::async_trait;
 use atoms_consensus::SignableTransaction;
-use atoms_network::{TxSigner, TxSignerSync};
-use atoms_signer::{sign_transaction_with_network_id, Error, Result, Signer, SignerSync};
+use atoms_signer::{sign_transaction_with_network_id, Error, Result, SignerSync, TxSignerSync};
 use base_primitives::{ChainId, IcanAddress, Signature, B256};
-use libgoldilocks::{PrehashSigner, SigningKey};
-use std::fmt;
+use core::fmt;
+use libgoldilocks::PrehashSigner;
+
+#[cfg(feature = "std")]
+use {async_trait::async_trait, atoms_network::TxSigner, atoms_signer::Signer};
 
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+#[cfg(feature = "std")]
 mod error;
+#[cfg(feature = "std")]
 pub use error::WalletError;
 
-// #[cfg(feature = "mnemonic")]
-// mod mnemonic;
-// #[cfg(feature = "mnemonic")]
-// pub use mnemonic::MnemonicBuilder;
+#[cfg(feature = "mnemonic")]
+mod mnemonic;
+#[cfg(feature = "mnemonic")]
+pub use mnemonic::{MnemonicBuilder, MnemonicBuilderError};
 
 mod private_key;
+pub use private_key::PrivateKey;
+
+#[cfg(feature = "std")]
+mod vanity;
+#[cfg(feature = "std")]
+pub use vanity::{VanityError, VanityHandle, VanityProgress, VanitySearch};
+
+#[cfg(feature = "keystore")]
+mod keystore_dir;
+#[cfg(feature = "keystore")]
+pub use keystore_dir::{keystore_file_name, KeystoreAccount, KeystoreDir};
 
-#[cfg(feature = "yubihsm")]
-mod yubi;
+#[cfg(feature = "keystore-watcher")]
+mod watcher;
+#[cfg(feature = "keystore-watcher")]
+pub use watcher::{KeystoreChanges, KeystoreWatcher, DEFAULT_WATCH_INTERVAL};
 
-#[cfg(feature = "yubihsm")]
-pub use yubihsm;
+#[cfg(feature = "pkcs11")]
+mod pkcs11;
+#[cfg(feature = "pkcs11")]
+pub use pkcs11::Pkcs11Signer;
+
+#[cfg(feature = "pkcs11")]
+pub use cryptoki;
 
 #[cfg(feature = "mnemonic")]
 pub use coins_bip39;
 
-/// A wallet instantiated with a locally stored private key
-pub type LocalWallet = Wallet<SigningKey>;
+pub use zeroize;
+
+/// A wallet instantiated with a locally stored private key.
+///
+/// The key is held in a [`PrivateKey`], which is zeroized when dropped.
+pub type LocalWallet = Wallet<PrivateKey>;
+
+/// A signer of prehashed messages, used by a [`Wallet`].
+///
+/// Implemented for every [`PrehashSigner`], and for the [`PrivateKey`] of [`LocalWallet`]s.
+pub trait HashSigner {
+    /// Signs the given hash.
+    fn sign_hash(&self, hash: &B256) -> Result<Signature>;
+}
+
+impl<D: PrehashSigner<Signature>> HashSigner for D {
+    #[inline]
+    fn sign_hash(&self, hash: &B256) -> Result<Signature> {
+        self.sign_prehash(hash.as_ref()).map_err(|e| Error::Other(Box::new(e)))
+    }
+}
 
 /// An Core private-public key pair which can be used for signing messages.
 ///
@@ -85,9 +132,10 @@ pub struct Wallet<D> {
     pub(crate) network_id: ChainId,
 }
 
+#[cfg(feature = "std")]
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
-impl<D: PrehashSigner<Signature> + Send + Sync> Signer for Wallet<D> {
+impl<D: HashSigner + Send + Sync> Signer for Wallet<D> {
     #[inline]
     async fn sign_hash(&self, hash: &B256) -> Result<Signature> {
         self.sign_hash_sync(hash)
@@ -109,11 +157,10 @@ impl<D: PrehashSigner<Signature> + Send + Sync> Signer for Wallet<D> {
     }
 }
 
-impl<D: PrehashSigner<Signature>> SignerSync for Wallet<D> {
+impl<D: HashSigner> SignerSync for Wallet<D> {
     #[inline]
     fn sign_hash_sync(&self, hash: &B256) -> Result<Signature> {
-        let sig = self.signer.sign_prehash(hash.as_ref()).map_err(|e| Error::Other(Box::new(e)))?;
-        Ok(sig)
+        self.signer.sign_hash(hash)
     }
 
     #[inline]
@@ -122,14 +169,20 @@ impl<D: PrehashSigner<Signature>> SignerSync for Wallet<D> {
     }
 }
 
-impl<D: PrehashSigner<Signature>> Wallet<D> {
-    /// Construct a new wallet with an external [`PrehashSigner`].
+impl<D: HashSigner> Wallet<D> {
+    /// Construct a new wallet with an external [`HashSigner`], such as a [`PrehashSigner`].
+    ///
+    /// The signer is dropped as is with the wallet: it is responsible for clearing its own key
+    /// material, if any.
     #[inline]
     pub const fn new_with_signer(signer: D, address: IcanAddress, network_id: ChainId) -> Self {
         Wallet { signer, address, network_id }
     }
 
     /// Returns this wallet's signer.
+    ///
+    /// The [`PrivateKey`] of a [`LocalWallet`] doesn't expose the key bytes, which can only be
+    /// exported with the `key-export` feature.
     #[inline]
     pub const fn signer(&self) -> &D {
         &self.signer
@@ -155,7 +208,7 @@ impl<D: PrehashSigner<Signature>> Wallet<D> {
 }
 
 // do not log the signer
-impl<D: PrehashSigner<Signature>> fmt::Debug for Wallet<D> {
+impl<D: HashSigner> fmt::Debug for Wallet<D> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Wallet")
             .field("address", &self.address)
@@ -164,11 +217,12 @@ impl<D: PrehashSigner<Signature>> fmt::Debug for Wallet<D> {
     }
 }
 
+#[cfg(feature = "std")]
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl<D> TxSigner<Signature> for Wallet<D>
 where
-    D: PrehashSigner<Signature> + Send + Sync,
+    D: HashSigner + Send + Sync,
 {
     fn address(&self) -> IcanAddress {
         self.address
@@ -184,7 +238,7 @@ where
 
 impl<D> TxSignerSync<Signature> for Wallet<D>
 where
-    D: PrehashSigner<Signature>,
+    D: HashSigner,
 {
     fn address(&self) -> IcanAddress {
         self.address
@@ -203,6 +257,7 @@ mod test {
     use super::*;
     use atoms_consensus::TxLegacy;
     use base_primitives::{cAddress, U256};
+    use libgoldilocks::SigningKey;
 
     #[tokio::test]
     async fn signs_tx() {
@@ -264,4 +319,31 @@ mod test {
         let expected_error = atoms_signer::Error::TransactionNetworkIdMismatch { signer: 1, tx: 2 };
         assert_eq!(error.to_string(), expected_error.to_string());
     }
+
+    #[test]
+    fn signs_tx_sync_to_bytes() {
+        let wallet = LocalWallet::from_signing_key(
+            SigningKey::from_str(
+                "7d6231471b5dbb6204fe5129617082792ae468d01a3f3623184c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+            ),
+            1,
+        );
+        let mut tx = TxLegacy {
+            to: cAddress!("0000F0109fC8DF283027b6285cc889F5aA624EaC1F55").into(),
+            value: U256::from(1_000_000_000),
+            energy_limit: 2_000_000,
+            nonce: 0,
+            energy_price: 21_000_000_000,
+            input: Default::default(),
+            network_id: 1,
+        };
+        let sig = wallet.sign_transaction_sync(&mut tx).unwrap();
+        let signed = tx.into_signed(sig);
+
+        let mut encoded = Vec::new();
+        signed.tx().encode_with_signature_fields(signed.signature(), &mut encoded);
+        let decoded = TxLegacy::decode_signed_fields(&mut encoded.as_slice()).unwrap();
+        assert_eq!(decoded, signed);
+        assert_eq!(decoded.recover_signer(1).unwrap(), wallet.address());
+    }
 }
@@ -1,254 +1,418 @@
-// todo:error2215 uncomment when mnemonic will be working
-
-// //! Specific helper functions for creating/loading a mnemonic private key following [BIP-39]
-// //! specifications.
-// //!
-// //! [BIP-39]: https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
-
-// use crate::{LocalWallet, Wallet, WalletError};
-// use atoms_signer::utils::secret_key_to_address;
-// use coins_bip32::path::DerivationPath;
-// use coins_bip39::{Mnemonic, Wordlist};
-// use k256::ecdsa::SigningKey;
-// use rand::Rng;
-// use std::{marker::PhantomData, path::PathBuf};
-// use thiserror::Error;
-
-// const DEFAULT_DERIVATION_PATH_PREFIX: &str = "m/44'/60'/0'/0/";
-// const DEFAULT_DERIVATION_PATH: &str = "m/44'/60'/0'/0/0";
-
-// /// Represents a structure that can resolve into a `LocalWallet`.
-// #[derive(Clone, Debug, PartialEq, Eq)]
-// #[must_use = "builders do nothing unless `build` is called"]
-// pub struct MnemonicBuilder<W: Wordlist> {
-//     /// The mnemonic phrase can be supplied to the builder as a string. A builder that has a valid
-//     /// phrase should `build` the wallet.
-//     phrase: Option<String>,
-//     /// The mnemonic builder can also be asked to generate a new random wallet by providing the
-//     /// number of words in the phrase. By default this is set to 12.
-//     word_count: usize,
-//     /// The derivation path at which the extended private key child will be derived at. By default
-//     /// the mnemonic builder uses the path: "m/44'/60'/0'/0/0".
-//     derivation_path: DerivationPath,
-//     /// Optional password for the mnemonic phrase.
-//     password: Option<String>,
-//     /// Optional field that if enabled, writes the mnemonic phrase to disk storage at the provided
-//     /// path.
-//     write_to: Option<PathBuf>,
-//     /// PhantomData
-//     _wordlist: PhantomData<W>,
-// }
-
-// /// Error produced by the mnemonic wallet module
-// #[derive(Debug, Error)]
-// #[allow(missing_copy_implementations)]
-// pub enum MnemonicBuilderError {
-//     /// Error suggests that a phrase (path or words) was expected but not found.
-//     #[error("expected phrase not found")]
-//     ExpectedPhraseNotFound,
-//     /// Error suggests that a phrase (path or words) was not expected but found.
-//     #[error("unexpected phrase found")]
-//     UnexpectedPhraseFound,
-// }
-
-// impl<W: Wordlist> Default for MnemonicBuilder<W> {
-//     fn default() -> Self {
-//         Self {
-//             phrase: None,
-//             word_count: 12usize,
-//             derivation_path: DEFAULT_DERIVATION_PATH.parse().unwrap(),
-//             password: None,
-//             write_to: None,
-//             _wordlist: PhantomData,
-//         }
-//     }
-// }
-
-// impl<W: Wordlist> MnemonicBuilder<W> {
-//     /// Sets the phrase in the mnemonic builder. The phrase can either be a string or a path to
-//     /// the file that contains the phrase. Once a phrase is provided, the key will be generated
-//     /// deterministically by calling the `build` method.
-//     ///
-//     /// # Examples
-//     ///
-//     /// ```
-//     /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
-//     /// use atoms_signer_wallet::{MnemonicBuilder, coins_bip39::English};
-//     ///
-//     /// let wallet = MnemonicBuilder::<English>::default()
-//     ///     .phrase("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about")
-//     ///     .build()?;
-//     /// # Ok(())
-//     /// # }
-//     /// ```
-//     pub fn phrase<P: Into<String>>(mut self, phrase: P) -> Self {
-//         self.phrase = Some(phrase.into());
-//         self
-//     }
-
-//     /// Sets the word count of a mnemonic phrase to be generated at random. If the `phrase` field
-//     /// is set, then `word_count` will be ignored.
-//     ///
-//     /// # Examples
-//     ///
-//     /// ```no_run
-//     /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
-//     /// use atoms_signer_wallet::{coins_bip39::English, MnemonicBuilder};
-//     ///
-//     /// let wallet = MnemonicBuilder::<English>::default().word_count(24).build()?;
-//     /// # Ok(())
-//     /// # }
-//     /// ```
-//     pub const fn word_count(mut self, count: usize) -> Self {
-//         self.word_count = count;
-//         self
-//     }
-
-//     /// Sets the derivation path of the child key to be derived. The derivation path is calculated
-//     /// using the default derivation path prefix used in Core, i.e. "m/44'/60'/0'/0/{index}".
-//     pub fn index(self, index: u32) -> Result<Self, WalletError> {
-//         self.derivation_path(format!("{DEFAULT_DERIVATION_PATH_PREFIX}{index}"))
-//     }
-
-//     /// Sets the derivation path of the child key to be derived.
-//     pub fn derivation_path<T: AsRef<str>>(mut self, path: T) -> Result<Self, WalletError> {
-//         self.derivation_path = path.as_ref().parse()?;
-//         Ok(self)
-//     }
-
-//     /// Sets the password used to construct the seed from the mnemonic phrase.
-//     pub fn password<T: Into<String>>(mut self, password: T) -> Self {
-//         self.password = Some(password.into());
-//         self
-//     }
-
-//     /// Sets the path to which the randomly generated phrase will be written to. This field is
-//     /// ignored when building a wallet from the provided mnemonic phrase.
-//     pub fn write_to<P: Into<PathBuf>>(mut self, path: P) -> Self {
-//         self.write_to = Some(path.into());
-//         self
-//     }
-
-//     /// Builds a `LocalWallet` using the parameters set in mnemonic builder. This method expects
-//     /// the phrase field to be set.
-//     pub fn build(&self) -> Result<LocalWallet, WalletError> {
-//         let mnemonic = match &self.phrase {
-//             Some(phrase) => Mnemonic::<W>::new_from_phrase(phrase)?,
-//             None => return Err(MnemonicBuilderError::ExpectedPhraseNotFound.into()),
-//         };
-//         self.mnemonic_to_wallet(&mnemonic)
-//     }
-
-//     /// Builds a `LocalWallet` using the parameters set in the mnemonic builder and constructing
-//     /// the phrase using the thread RNG.
-//     pub fn build_random(&self) -> Result<LocalWallet, WalletError> {
-//         self.build_random_with(&mut rand::thread_rng())
-//     }
-
-//     /// Builds a `LocalWallet` using the parameters set in the mnemonic builder and constructing
-//     /// the phrase using the provided random number generator.
-//     pub fn build_random_with<R: Rng>(&self, rng: &mut R) -> Result<LocalWallet, WalletError> {
-//         let mnemonic = match &self.phrase {
-//             None => Mnemonic::<W>::new_with_count(rng, self.word_count)?,
-//             _ => return Err(MnemonicBuilderError::UnexpectedPhraseFound.into()),
-//         };
-//         let wallet = self.mnemonic_to_wallet(&mnemonic)?;
-
-//         // Write the mnemonic phrase to storage if a directory has been provided.
-//         if let Some(dir) = &self.write_to {
-//             std::fs::write(dir.join(wallet.address.to_string()), mnemonic.to_phrase().as_bytes())?;
-//         }
-
-//         Ok(wallet)
-//     }
-
-//     fn mnemonic_to_wallet(&self, mnemonic: &Mnemonic<W>) -> Result<LocalWallet, WalletError> {
-//         let derived_priv_key =
-//             mnemonic.derive_key(&self.derivation_path, self.password.as_deref())?;
-//         let key: &coins_bip32::prelude::SigningKey = derived_priv_key.as_ref();
-//         let signer = SigningKey::from_bytes(&key.to_bytes())?;
-//         let address = secret_key_to_address(&signer);
-//         Ok(Wallet::<SigningKey> { signer, address, chain_id: None })
-//     }
-// }
-
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-//     use coins_bip39::English;
-//     use tempfile::tempdir;
-
-//     const TEST_DERIVATION_PATH: &str = "m/44'/60'/0'/2/1";
-
-//     #[test]
-//     fn mnemonic_deterministic() {
-//         // Testcases have been taken from MyCryptoWallet
-//         let tests = [
-//             (
-//                 "work man father plunge mystery proud hollow address reunion sauce theory bonus",
-//                 0u32,
-//                 Some("TREZOR123"),
-//                 "0x431a00DA1D54c281AeF638A73121B3D153e0b0F6",
-//             ),
-//             (
-//                 "inject danger program federal spice bitter term garbage coyote breeze thought funny",
-//                 1u32,
-//                 Some("LEDGER321"),
-//                 "0x231a3D0a05d13FAf93078C779FeeD3752ea1350C",
-//             ),
-//             (
-//                 "fire evolve buddy tenant talent favorite ankle stem regret myth dream fresh",
-//                 2u32,
-//                 None,
-//                 "0x1D86AD5eBb2380dAdEAF52f61f4F428C485460E9",
-//             ),
-//             (
-//                 "thumb soda tape crunch maple fresh imitate cancel order blind denial giraffe",
-//                 3u32,
-//                 None,
-//                 "0xFB78b25f69A8e941036fEE2A5EeAf349D81D4ccc",
-//             ),
-//         ];
-//         for (phrase, index, password, expected_addr) in tests {
-//             let mut builder =
-//                 MnemonicBuilder::<English>::default().phrase(phrase).index(index).unwrap();
-//             if let Some(psswd) = password {
-//                 builder = builder.password(psswd);
-//             }
-//             let wallet = builder.build().unwrap();
-//             assert_eq!(&wallet.address.to_string(), expected_addr);
-//         }
-//     }
-
-//     #[test]
-//     fn mnemonic_write_read() {
-//         let dir = tempdir().unwrap();
-
-//         let wallet1 = MnemonicBuilder::<English>::default()
-//             .word_count(24)
-//             .derivation_path(TEST_DERIVATION_PATH)
-//             .unwrap()
-//             .write_to(dir.as_ref())
-//             .build_random()
-//             .unwrap();
-
-//         // Ensure that only one file has been created.
-//         let paths = std::fs::read_dir(dir.as_ref()).unwrap();
-//         assert_eq!(paths.count(), 1);
-
-//         // Use the newly created mnemonic to instantiate wallet.
-//         let phrase_path = dir.as_ref().join(wallet1.address.to_string());
-//         let phrase = std::fs::read_to_string(phrase_path).unwrap();
-//         let wallet2 = MnemonicBuilder::<English>::default()
-//             .phrase(phrase)
-//             .derivation_path(TEST_DERIVATION_PATH)
-//             .unwrap()
-//             .build()
-//             .unwrap();
-
-//         // Ensure that both wallets belong to the same address.
-//         assert_eq!(wallet1.address, wallet2.address);
-
-//         dir.close().unwrap();
-//     }
-// }
+//! Specific helper functions for creating/loading a mnemonic private key following [BIP-39]
+//! specifications.
+//!
+//! Ed448 keys are derived from the BIP-39 seed with a [SLIP-10]-like scheme, where HMAC-SHA512 is
+//! run twice per step to produce the 57-byte private key and the 57-byte chain code. Ed448 has no
+//! public child derivation, so every index of the derivation path must be hardened.
+//!
+//! [BIP-39]: https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+//! [SLIP-10]: https://github.com/satoshilabs/slips/blob/master/slip-0010.md
+
+use crate::{LocalWallet, Wallet, WalletError};
+use coins_bip32::{path::DerivationPath, BIP32_HARDEN};
+use coins_bip39::{Mnemonic, Wordlist};
+use hmac::{Hmac, Mac};
+use libgoldilocks::SigningKey;
+use rand::{CryptoRng, Rng};
+use sha2::Sha512;
+use std::{fmt, marker::PhantomData};
+use thiserror::Error;
+use zeroize::{Zeroize, Zeroizing};
+#[cfg(feature = "keystore")]
+use {crate::KeystoreDir, std::path::PathBuf};
+
+const DEFAULT_DERIVATION_PATH_PREFIX: &str = "m/44'/654'/0'/0'/";
+const DEFAULT_DERIVATION_PATH: &str = "m/44'/654'/0'/0'/0'";
+
+/// The HMAC key used to derive the master key from the seed.
+// TODO: check the derivation against the vectors of the Corepass wallet
+const MASTER_KEY_SEED: &[u8] = b"ed448 seed";
+
+/// The length of an ed448 private key and of a chain code.
+const KEY_LEN: usize = 57;
+
+/// Represents a structure that can resolve into a `LocalWallet`.
+///
+/// The phrase and the password are zeroized when the builder is dropped.
+#[derive(Clone, PartialEq, Eq)]
+#[must_use = "builders do nothing unless `build` is called"]
+pub struct MnemonicBuilder<W: Wordlist> {
+    /// The mnemonic phrase can be supplied to the builder as a string. A builder that has a valid
+    /// phrase should `build` the wallet.
+    phrase: Option<Zeroizing<String>>,
+    /// The mnemonic builder can also be asked to generate a new random wallet by providing the
+    /// number of words in the phrase. By default this is set to 12.
+    word_count: usize,
+    /// The derivation path at which the extended private key child will be derived at. By default
+    /// the mnemonic builder uses the path: "m/44'/654'/0'/0'/0'".
+    derivation_path: DerivationPath,
+    /// Optional password for the mnemonic phrase.
+    password: Option<Zeroizing<String>>,
+    /// Optional field that if enabled, stores the key of the randomly generated wallet in the
+    /// keystore directory at the provided path, encrypted with the provided keystore password.
+    #[cfg(feature = "keystore")]
+    write_to: Option<(PathBuf, Zeroizing<String>)>,
+    /// The network ID of the built wallet. By default this is set to 1.
+    network_id: u64,
+    /// PhantomData
+    _wordlist: PhantomData<W>,
+}
+
+/// Error produced by the mnemonic wallet module
+#[derive(Debug, Error)]
+#[allow(missing_copy_implementations)]
+pub enum MnemonicBuilderError {
+    /// Error suggests that a phrase (path or words) was expected but not found.
+    #[error("expected phrase not found")]
+    ExpectedPhraseNotFound,
+    /// Error suggests that a phrase (path or words) was not expected but found.
+    #[error("unexpected phrase found")]
+    UnexpectedPhraseFound,
+    /// Error suggests that an index of the derivation path is not hardened, which ed448 does not
+    /// support.
+    #[error("derivation path index {0} is not hardened")]
+    NonHardenedIndex(u32),
+}
+
+// do not log the phrase and the password
+impl<W: Wordlist> fmt::Debug for MnemonicBuilder<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("MnemonicBuilder");
+        debug
+            .field("phrase", &self.phrase.as_ref().map(|_| "<redacted>"))
+            .field("word_count", &self.word_count)
+            .field("derivation_path", &self.derivation_path)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"));
+        #[cfg(feature = "keystore")]
+        debug.field("write_to", &self.write_to.as_ref().map(|(path, _)| path));
+        debug.field("network_id", &self.network_id).finish()
+    }
+}
+
+impl<W: Wordlist> Default for MnemonicBuilder<W> {
+    fn default() -> Self {
+        Self {
+            phrase: None,
+            word_count: 12usize,
+            derivation_path: DEFAULT_DERIVATION_PATH.parse().unwrap(),
+            password: None,
+            #[cfg(feature = "keystore")]
+            write_to: None,
+            network_id: 1,
+            _wordlist: PhantomData,
+        }
+    }
+}
+
+impl<W: Wordlist> MnemonicBuilder<W> {
+    /// Sets the phrase in the mnemonic builder. The phrase can either be a string or a path to
+    /// the file that contains the phrase. Once a phrase is provided, the key will be generated
+    /// deterministically by calling the `build` method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// use atoms_signer_wallet::{MnemonicBuilder, coins_bip39::English};
+    ///
+    /// let wallet = MnemonicBuilder::<English>::default()
+    ///     .phrase("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about")
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn phrase<P: Into<String>>(mut self, phrase: P) -> Self {
+        self.phrase = Some(Zeroizing::new(phrase.into()));
+        self
+    }
+
+    /// Sets the word count of a mnemonic phrase to be generated at random. If the `phrase` field
+    /// is set, then `word_count` will be ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// use atoms_signer_wallet::{coins_bip39::English, MnemonicBuilder};
+    ///
+    /// let (wallet, phrase) =
+    ///     MnemonicBuilder::<English>::default().word_count(24).build_random()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub const fn word_count(mut self, count: usize) -> Self {
+        self.word_count = count;
+        self
+    }
+
+    /// Sets the derivation path of the child key to be derived. The derivation path is calculated
+    /// using the default derivation path prefix used in Core, i.e. "m/44'/654'/0'/0'/{index}'".
+    pub fn index(self, index: u32) -> Result<Self, WalletError> {
+        self.derivation_path(format!("{DEFAULT_DERIVATION_PATH_PREFIX}{index}'"))
+    }
+
+    /// Sets the derivation path of the child key to be derived.
+    ///
+    /// Every index of the path must be hardened, e.g. "m/44'/654'/0'/0'/0'".
+    pub fn derivation_path<T: AsRef<str>>(mut self, path: T) -> Result<Self, WalletError> {
+        let path: DerivationPath = path.as_ref().parse()?;
+        if let Some(index) = path.iter().find(|index| **index & BIP32_HARDEN == 0) {
+            return Err(MnemonicBuilderError::NonHardenedIndex(*index).into());
+        }
+        self.derivation_path = path;
+        Ok(self)
+    }
+
+    /// Sets the password used to construct the seed from the mnemonic phrase.
+    pub fn password<T: Into<String>>(mut self, password: T) -> Self {
+        self.password = Some(Zeroizing::new(password.into()));
+        self
+    }
+
+    /// Sets the keystore directory in which the key of the randomly generated wallet will be
+    /// stored, encrypted with `keystore_password`. This field is ignored when building a wallet
+    /// from the provided mnemonic phrase.
+    ///
+    /// The keystore password is unrelated to the [password](Self::password) of the phrase, and the
+    /// phrase itself is never written to disk.
+    #[cfg(feature = "keystore")]
+    pub fn write_to<P: Into<PathBuf>, S: Into<String>>(
+        mut self,
+        path: P,
+        keystore_password: S,
+    ) -> Self {
+        self.write_to = Some((path.into(), Zeroizing::new(keystore_password.into())));
+        self
+    }
+
+    /// Sets the network ID of the built wallet.
+    pub const fn network_id(mut self, network_id: u64) -> Self {
+        self.network_id = network_id;
+        self
+    }
+
+    /// Builds a `LocalWallet` using the parameters set in mnemonic builder. This method expects
+    /// the phrase field to be set.
+    pub fn build(&self) -> Result<LocalWallet, WalletError> {
+        let mnemonic = match &self.phrase {
+            Some(phrase) => Mnemonic::<W>::new_from_phrase(phrase)?,
+            None => return Err(MnemonicBuilderError::ExpectedPhraseNotFound.into()),
+        };
+        self.mnemonic_to_wallet(&mnemonic)
+    }
+
+    /// Builds a `LocalWallet` using the parameters set in the mnemonic builder and constructing
+    /// the phrase using the thread RNG.
+    ///
+    /// Returns the wallet along with the generated phrase, which is needed to restore it.
+    pub fn build_random(&self) -> Result<(LocalWallet, Zeroizing<String>), WalletError> {
+        self.build_random_with(&mut rand::thread_rng())
+    }
+
+    /// Builds a `LocalWallet` using the parameters set in the mnemonic builder and constructing
+    /// the phrase using the provided random number generator.
+    ///
+    /// Returns the wallet along with the generated phrase, which is needed to restore it.
+    pub fn build_random_with<R: Rng + CryptoRng>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(LocalWallet, Zeroizing<String>), WalletError> {
+        let mnemonic = match &self.phrase {
+            None => Mnemonic::<W>::new_with_count(rng, self.word_count)?,
+            _ => return Err(MnemonicBuilderError::UnexpectedPhraseFound.into()),
+        };
+        let wallet = self.mnemonic_to_wallet(&mnemonic)?;
+
+        // Store the encrypted key if a keystore directory has been provided.
+        #[cfg(feature = "keystore")]
+        if let Some((dir, password)) = &self.write_to {
+            KeystoreDir::new(dir, self.network_id).import(
+                rng,
+                wallet.private_key_bytes(),
+                password.as_bytes(),
+            )?;
+        }
+
+        Ok((wallet, Zeroizing::new(mnemonic.to_phrase())))
+    }
+
+    fn mnemonic_to_wallet(&self, mnemonic: &Mnemonic<W>) -> Result<LocalWallet, WalletError> {
+        let seed = Zeroizing::new(mnemonic.to_seed(self.password.as_ref().map(|p| p.as_str()))?);
+        let key = self
+            .derivation_path
+            .iter()
+            .fold(ExtendedKey::master(&seed[..]), |parent, index| parent.child(*index));
+        let signer = SigningKey::from_bytes(&key.key)?;
+        Ok(Wallet::from_signing_key(signer, self.network_id))
+    }
+}
+
+/// An ed448 extended private key: the private key and the chain code used to derive its children.
+///
+/// Both are zeroized when dropped.
+struct ExtendedKey {
+    key: [u8; KEY_LEN],
+    chain_code: [u8; KEY_LEN],
+}
+
+impl Drop for ExtendedKey {
+    fn drop(&mut self) {
+        self.key.zeroize();
+        self.chain_code.zeroize();
+    }
+}
+
+impl ExtendedKey {
+    /// Derives the master key from a BIP-39 seed.
+    fn master(seed: &[u8]) -> Self {
+        Self::derive(MASTER_KEY_SEED, &[seed])
+    }
+
+    /// Derives the child key at the given hardened index.
+    fn child(&self, index: u32) -> Self {
+        debug_assert_ne!(index & BIP32_HARDEN, 0, "ed448 only supports hardened derivation");
+        Self::derive(&self.chain_code, &[&[0], &self.key, &index.to_be_bytes()])
+    }
+
+    /// Splits `HMAC-SHA512(key, 0x00 || data) || HMAC-SHA512(key, 0x01 || data)` into the private
+    /// key and the chain code.
+    fn derive(key: &[u8], data: &[&[u8]]) -> Self {
+        let mut out = [0u8; 128];
+        for (counter, chunk) in out.chunks_exact_mut(64).enumerate() {
+            let mut mac =
+                Hmac::<Sha512>::new_from_slice(key).expect("HMAC can take key of any size");
+            mac.update(&[counter as u8]);
+            data.iter().for_each(|data| mac.update(data));
+            chunk.copy_from_slice(&mac.finalize().into_bytes());
+        }
+
+        let mut this = Self { key: [0; KEY_LEN], chain_code: [0; KEY_LEN] };
+        this.key.copy_from_slice(&out[..KEY_LEN]);
+        this.chain_code.copy_from_slice(&out[KEY_LEN..2 * KEY_LEN]);
+        out.zeroize();
+        this
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base_primitives::hex;
+    use coins_bip39::English;
+    #[cfg(feature = "keystore")]
+    use tempfile::tempdir;
+
+    const TEST_PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    const TEST_DERIVATION_PATH: &str = "m/44'/654'/0'/2'/1'";
+
+    #[test]
+    fn mnemonic_deterministic() {
+        let tests = [
+            (
+                0u32,
+                None,
+                hex!("612141fd917bcb9da8976a2d9ddb828c50e634716cc82775cd8af219af5a4f4221997298c98ee6c6285538403fd5b25d659ef2c0de274da974"),
+            ),
+            (
+                1u32,
+                None,
+                hex!("8a5e6ef7a3368e13dad45baaefaadc0c758a329525a5d0ed88225ed6e0de111fe0a0050db1f435213e51966106514df05c8ca9a114c8df54c2"),
+            ),
+            (
+                0u32,
+                Some("TREZOR"),
+                hex!("7f15f214d7190050409b2134855c7a3c65d4b727febd004f90ad1e6adb3e3f551860b10c3b417857e86564a5e5a4d1e1f5f4978d98c333e6ee"),
+            ),
+        ];
+        for (index, password, expected_key) in tests {
+            let mut builder =
+                MnemonicBuilder::<English>::default().phrase(TEST_PHRASE).index(index).unwrap();
+            if let Some(psswd) = password {
+                builder = builder.password(psswd);
+            }
+            let wallet = builder.build().unwrap();
+            let expected = Wallet::from_slice(&expected_key, 1).unwrap();
+            assert_eq!(wallet.signer(), expected.signer());
+            assert_eq!(wallet.address(), expected.address());
+        }
+    }
+
+    #[test]
+    fn mnemonic_debug_redacted() {
+        let builder = MnemonicBuilder::<English>::default().phrase(TEST_PHRASE).password("TREZOR");
+        let debug = format!("{builder:?}");
+        assert!(!debug.contains("abandon"), "{debug}");
+        assert!(!debug.contains("TREZOR"), "{debug}");
+    }
+
+    #[test]
+    fn mnemonic_index_and_path() {
+        let by_index =
+            MnemonicBuilder::<English>::default().phrase(TEST_PHRASE).index(3).unwrap().build();
+        let by_path = MnemonicBuilder::<English>::default()
+            .phrase(TEST_PHRASE)
+            .derivation_path("m/44'/654'/0'/0'/3'")
+            .unwrap()
+            .build();
+        assert_eq!(by_index.unwrap().address(), by_path.unwrap().address());
+
+        // ed448 has no non-hardened derivation
+        for path in ["m/44'/654'/0'/0/3", "m/44/654'/0'/0'/3'"] {
+            assert!(matches!(
+                MnemonicBuilder::<English>::default().derivation_path(path),
+                Err(WalletError::MnemonicBuilderError(MnemonicBuilderError::NonHardenedIndex(_)))
+            ));
+        }
+        assert!(MnemonicBuilder::<English>::default().index(BIP32_HARDEN).is_err());
+
+        let wallet = MnemonicBuilder::<English>::default()
+            .phrase(TEST_PHRASE)
+            .network_id(3)
+            .build()
+            .unwrap();
+        assert_eq!(wallet.network_id(), 3);
+
+        assert!(MnemonicBuilder::<English>::default().build().is_err());
+        assert!(MnemonicBuilder::<English>::default().phrase(TEST_PHRASE).build_random().is_err());
+    }
+
+    #[test]
+    fn mnemonic_random_restore() {
+        let builder = MnemonicBuilder::<English>::default().word_count(24).password("TREZOR");
+        let (wallet, phrase) = builder.build_random().unwrap();
+        assert_eq!(phrase.split_whitespace().count(), 24);
+
+        // The returned phrase restores the wallet.
+        let restored = builder.phrase(phrase.as_str()).build().unwrap();
+        assert_eq!(wallet, restored);
+    }
+
+    #[test]
+    #[cfg(feature = "keystore")]
+    fn mnemonic_write_read() {
+        let dir = tempdir().unwrap();
+
+        let builder = MnemonicBuilder::<English>::default()
+            .word_count(24)
+            .derivation_path(TEST_DERIVATION_PATH)
+            .unwrap()
+            .password("phrase password");
+        let (wallet1, phrase) =
+            builder.clone().write_to(dir.as_ref(), "keystore password").build_random().unwrap();
+
+        // Ensure that only one file has been created.
+        let paths = std::fs::read_dir(dir.as_ref()).unwrap();
+        assert_eq!(paths.count(), 1);
+
+        // Decrypt the stored key with the keystore password.
+        let keystore = KeystoreDir::new(dir.as_ref(), 1);
+        assert!(keystore.unlock(&wallet1.address, "phrase password").is_err());
+        let wallet2 = keystore.unlock(&wallet1.address, "keystore password").unwrap();
+
+        // Ensure that both wallets hold the same key, which the phrase restores.
+        assert_eq!(wallet1, wallet2);
+        assert_eq!(builder.phrase(phrase.as_str()).build().unwrap(), wallet2);
+
+        dir.close().unwrap();
+    }
+}
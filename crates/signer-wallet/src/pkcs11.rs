@@ -0,0 +1,365 @@
+//! [PKCS#11](cryptoki) signer implementation.
+
+use crate::WalletError;
+use async_trait::async_trait;
+use atoms_consensus::SignableTransaction;
+use atoms_network::{TxSigner, TxSignerSync};
+use atoms_signer::{
+    sign_transaction_with_network_id, utils::raw_public_key_to_address, Result, Signer, SignerSync,
+};
+use base_primitives::{ChainId, IcanAddress, Signature, B256};
+use cryptoki::{
+    mechanism::{
+        eddsa::{EddsaParams, EddsaSignatureScheme},
+        Mechanism,
+    },
+    object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle},
+    session::Session,
+};
+use std::{
+    fmt,
+    sync::{Mutex, PoisonError},
+};
+
+/// The length of an Ed448 public key.
+const PUBLIC_KEY_LEN: usize = 57;
+
+/// The DER encoded `id-Ed448` OID (1.3.101.113), the `CKA_EC_PARAMS` of Ed448 keys.
+const ED448_PARAMS: [u8; 5] = [0x06, 0x03, 0x2b, 0x65, 0x71];
+
+/// A signer for an Ed448 key stored on a [PKCS#11](cryptoki) token, such as an HSM.
+///
+/// The private key never leaves the token: it is referenced by its handle in a logged in session,
+/// and all signatures are produced by the token with the `CKM_EDDSA` mechanism. Keys generated
+/// with [`generate`](Self::generate) are sensitive and non-extractable.
+///
+/// # Examples
+///
+/// ```no_run
+/// use atoms_signer::SignerSync;
+/// use atoms_signer_wallet::{
+///     cryptoki::{
+///         context::{CInitializeArgs, Pkcs11},
+///         session::UserType,
+///         types::AuthPin,
+///     },
+///     Pkcs11Signer,
+/// };
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let pkcs11 = Pkcs11::new("/usr/lib/softhsm/libsofthsm2.so")?;
+/// pkcs11.initialize(CInitializeArgs::OsThreads)?;
+/// let slot = pkcs11.get_slots_with_token()?[0];
+/// let session = pkcs11.open_ro_session(slot)?;
+/// session.login(UserType::User, Some(&AuthPin::new("1234".into())))?;
+///
+/// let signer = Pkcs11Signer::new(session, "hot-wallet", 1)?;
+/// let signature = signer.sign_message_sync(b"hello")?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Pkcs11Signer {
+    /// The logged in session. Sessions can't be used concurrently.
+    session: Mutex<Session>,
+    /// The handle of the private key.
+    key: ObjectHandle,
+    /// The public key.
+    public_key: [u8; PUBLIC_KEY_LEN],
+    /// The signer's address.
+    address: IcanAddress,
+    /// The signer's network ID.
+    network_id: ChainId,
+}
+
+impl fmt::Debug for Pkcs11Signer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pkcs11Signer")
+            .field("key", &self.key)
+            .field("address", &self.address)
+            .field("network_id", &self.network_id)
+            .finish()
+    }
+}
+
+impl Pkcs11Signer {
+    /// Creates a signer for the Ed448 key pair with the given label on the session's token.
+    ///
+    /// The session must be logged in as a user allowed to use the private key.
+    pub fn new(session: Session, label: &str, network_id: ChainId) -> Result<Self, WalletError> {
+        let key = find_key(&session, ObjectClass::PRIVATE_KEY, label)?;
+        let public_key = find_key(&session, ObjectClass::PUBLIC_KEY, label)?;
+        let public_key = read_public_key(&session, public_key)?;
+        Ok(Self::from_parts(session, key, public_key, network_id))
+    }
+
+    /// Generates a new Ed448 key pair with the given label on the session's token, and creates a
+    /// signer for it.
+    ///
+    /// The key pair is stored on the token, and the private key can't be extracted from it.
+    pub fn generate(
+        session: Session,
+        label: &str,
+        network_id: ChainId,
+    ) -> Result<Self, WalletError> {
+        let public_template = [
+            Attribute::Token(true),
+            Attribute::Label(label.into()),
+            Attribute::EcParams(ED448_PARAMS.to_vec()),
+            Attribute::Verify(true),
+        ];
+        let private_template = [
+            Attribute::Token(true),
+            Attribute::Label(label.into()),
+            Attribute::Private(true),
+            Attribute::Sensitive(true),
+            Attribute::Extractable(false),
+            Attribute::Sign(true),
+        ];
+        let (public_key, key) = session.generate_key_pair(
+            &Mechanism::EccEdwardsKeyPairGen,
+            &public_template,
+            &private_template,
+        )?;
+        let public_key = read_public_key(&session, public_key)?;
+        Ok(Self::from_parts(session, key, public_key, network_id))
+    }
+
+    fn from_parts(
+        session: Session,
+        key: ObjectHandle,
+        public_key: [u8; PUBLIC_KEY_LEN],
+        network_id: ChainId,
+    ) -> Self {
+        let address = raw_public_key_to_address(&public_key, network_id);
+        Self { session: Mutex::new(session), key, public_key, address, network_id }
+    }
+
+    /// Returns this signer's public key.
+    #[inline]
+    pub const fn public_key(&self) -> &[u8; PUBLIC_KEY_LEN] {
+        &self.public_key
+    }
+
+    /// Returns this signer's address.
+    #[inline]
+    pub const fn address(&self) -> IcanAddress {
+        self.address
+    }
+
+    /// Returns this signer's network ID.
+    #[inline]
+    pub const fn network_id(&self) -> ChainId {
+        self.network_id
+    }
+
+    /// Sets the network ID, updating the address accordingly.
+    #[inline]
+    pub fn set_network_id(&mut self, network_id: ChainId) {
+        self.network_id = network_id;
+        self.address = raw_public_key_to_address(&self.public_key, network_id);
+    }
+
+    /// Consumes this signer and returns its session.
+    pub fn into_session(self) -> Session {
+        self.session.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl SignerSync for Pkcs11Signer {
+    fn sign_hash_sync(&self, hash: &B256) -> Result<Signature> {
+        // Core signs hashes with pure Ed448 and an empty context
+        let mechanism = Mechanism::Eddsa(EddsaParams::new(EddsaSignatureScheme::Ed448(&[])));
+        let session = self.session.lock().unwrap_or_else(PoisonError::into_inner);
+        let sig = session
+            .sign(&mechanism, self.key, hash.as_slice())
+            .map_err(atoms_signer::Error::other)?;
+        drop(session);
+
+        // the signature is followed by the public key of the signer
+        let mut bytes = sig;
+        bytes.extend_from_slice(&self.public_key);
+        Ok(Signature::try_from(bytes.as_slice())?)
+    }
+
+    #[inline]
+    fn network_id_sync(&self) -> ChainId {
+        self.network_id
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Signer for Pkcs11Signer {
+    #[inline]
+    async fn sign_hash(&self, hash: &B256) -> Result<Signature> {
+        self.sign_hash_sync(hash)
+    }
+
+    #[inline]
+    fn address(&self) -> IcanAddress {
+        self.address
+    }
+
+    #[inline]
+    fn network_id(&self) -> ChainId {
+        self.network_id
+    }
+
+    #[inline]
+    fn set_network_id(&mut self, network_id: ChainId) {
+        Self::set_network_id(self, network_id)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl TxSigner<Signature> for Pkcs11Signer {
+    fn address(&self) -> IcanAddress {
+        self.address
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<Signature>,
+    ) -> Result<Signature> {
+        sign_transaction_with_network_id!(self, tx, self.sign_hash_sync(&tx.signature_hash()))
+    }
+}
+
+impl TxSignerSync<Signature> for Pkcs11Signer {
+    fn address(&self) -> IcanAddress {
+        self.address
+    }
+
+    fn sign_transaction_sync(
+        &self,
+        tx: &mut dyn SignableTransaction<Signature>,
+    ) -> Result<Signature> {
+        sign_transaction_with_network_id!(self, tx, self.sign_hash_sync(&tx.signature_hash()))
+    }
+}
+
+/// Finds the Ed448 key of the given class with the given label.
+fn find_key(
+    session: &Session,
+    class: ObjectClass,
+    label: &str,
+) -> Result<ObjectHandle, WalletError> {
+    let template = [
+        Attribute::Class(class),
+        Attribute::KeyType(KeyType::EC_EDWARDS),
+        Attribute::Label(label.into()),
+    ];
+    match session.find_objects(&template)?.as_slice() {
+        [key] => Ok(*key),
+        [] => Err(WalletError::Pkcs11KeyNotFound(label.to_string())),
+        _ => Err(WalletError::Pkcs11AmbiguousKey(label.to_string())),
+    }
+}
+
+/// Reads the raw Ed448 public key from the `CKA_EC_POINT` of a public key.
+fn read_public_key(
+    session: &Session,
+    key: ObjectHandle,
+) -> Result<[u8; PUBLIC_KEY_LEN], WalletError> {
+    let point = session
+        .get_attributes(key, &[AttributeType::EcPoint])?
+        .into_iter()
+        .find_map(|attribute| match attribute {
+            Attribute::EcPoint(point) => Some(point),
+            _ => None,
+        })
+        .ok_or(WalletError::Pkcs11InvalidPublicKey)?;
+
+    // the point should be a DER encoded octet string, but some tokens return it raw
+    let raw = match point.as_slice() {
+        [0x04, len, raw @ ..] if *len as usize == PUBLIC_KEY_LEN => raw,
+        raw => raw,
+    };
+    raw.try_into().map_err(|_| WalletError::Pkcs11InvalidPublicKey)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atoms_consensus::TxLegacy;
+    use atoms_signer::verify_message;
+    use base_primitives::U256;
+    use cryptoki::{
+        context::{CInitializeArgs, Pkcs11},
+        session::UserType,
+        types::AuthPin,
+    };
+
+    /// Initializes a token with the PKCS#11 module at `$PKCS11_MODULE`, e.g. SoftHSM's
+    /// `libsofthsm2.so`, and returns a user session on it.
+    fn session() -> (Pkcs11, Session) {
+        let module = std::env::var("PKCS11_MODULE").expect("PKCS11_MODULE is not set");
+        let pkcs11 = Pkcs11::new(module).unwrap();
+        pkcs11.initialize(CInitializeArgs::OsThreads).unwrap();
+
+        let slot = pkcs11.get_slots_with_token().unwrap()[0];
+        let so_pin = AuthPin::new("abcdef".into());
+        let user_pin = AuthPin::new("fedcba".into());
+        pkcs11.init_token(slot, &so_pin, "atoms-test").unwrap();
+
+        let session = pkcs11.open_rw_session(slot).unwrap();
+        session.login(UserType::So, Some(&so_pin)).unwrap();
+        session.init_pin(&user_pin).unwrap();
+        session.logout().unwrap();
+        session.login(UserType::User, Some(&user_pin)).unwrap();
+        (pkcs11, session)
+    }
+
+    // A single test, as the module can only be initialized once per process.
+    //
+    // Requires SoftHSM 2.6 or later, with a token directory it can write to:
+    //
+    // ```sh
+    // mkdir -p /tmp/softhsm
+    // echo "directories.tokendir = /tmp/softhsm" > /tmp/softhsm2.conf
+    // SOFTHSM2_CONF=/tmp/softhsm2.conf PKCS11_MODULE=/usr/lib/softhsm/libsofthsm2.so \
+    //     cargo test -p atoms-signer-wallet --features pkcs11 -- --ignored softhsm
+    // ```
+    #[tokio::test]
+    #[ignore = "requires SoftHSM, see the comment above"]
+    async fn softhsm() {
+        let (pkcs11, session) = session();
+
+        let signer = Pkcs11Signer::generate(session, "atoms", 1).unwrap();
+        let address = signer.address();
+
+        // the private key can't be read from the token
+        let session = signer.into_session();
+        let key = find_key(&session, ObjectClass::PRIVATE_KEY, "atoms").unwrap();
+        let attributes = session.get_attributes(key, &[AttributeType::Value]).unwrap();
+        assert!(!attributes.iter().any(|attribute| matches!(attribute, Attribute::Value(_))));
+
+        let signer = Pkcs11Signer::new(session, "atoms", 1).unwrap();
+        assert_eq!(signer.address(), address);
+        assert!(matches!(
+            Pkcs11Signer::new(
+                pkcs11.open_ro_session(pkcs11.get_slots_with_token().unwrap()[0]).unwrap(),
+                "missing",
+                1
+            ),
+            Err(WalletError::Pkcs11KeyNotFound(_))
+        ));
+
+        let message = b"hello";
+        let signature = signer.sign_message(message).await.unwrap();
+        assert_eq!(verify_message(&signature, message, 1).unwrap(), address);
+
+        let mut tx = TxLegacy {
+            network_id: 1,
+            nonce: 0,
+            energy_price: 1,
+            energy_limit: 21_000,
+            to: IcanAddress::ZERO.into(),
+            value: U256::from(1),
+            input: Default::default(),
+        };
+        let signature = signer.sign_transaction_sync(&mut tx).unwrap();
+        assert_eq!(tx.into_signed(signature).recover_signer(1).unwrap(), address);
+    }
+}
@@ -1,14 +1,72 @@
 //! [`k256`] wallet implementation.
 
-use super::{Wallet, WalletError};
-use atoms_signer::utils::secret_key_to_address;
+use super::{HashSigner, Wallet};
+use atoms_signer::{utils::secret_key_to_address, Result};
+use base_primitives::{Signature, B256};
+use core::fmt;
 use libgoldilocks::{errors::LibgoldilockErrors, SigningKey};
 use rand::{CryptoRng, Rng};
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 
 #[cfg(feature = "keystore")]
-use {elliptic_curve::rand_core, std::path::Path};
+use {super::WalletError, elliptic_curve::rand_core, std::path::Path};
+
+/// The length of an ed448 private key.
+const KEY_LEN: usize = 57;
+
+/// An ed448 private key, held in a buffer which is zeroized when dropped.
+///
+/// A [`SigningKey`] is only built from it for the duration of each signature, and the key bytes
+/// can't be read back: they can only be exported with the `key-export` feature.
+#[derive(Clone)]
+pub struct PrivateKey(Zeroizing<[u8; KEY_LEN]>);
+
+impl PrivateKey {
+    /// Copies the key of the signing key.
+    fn from_signing_key(signer: &SigningKey) -> Self {
+        let mut bytes = signer.to_bytes();
+        let mut key = Zeroizing::new([0; KEY_LEN]);
+        key.copy_from_slice(&bytes[..]);
+        bytes.zeroize();
+        Self(key)
+    }
+
+    /// Builds the signing key, which must not outlive the signature it is built for.
+    fn signing_key(&self) -> SigningKey {
+        SigningKey::from_bytes(&self.0[..]).expect("copied from a valid signing key")
+    }
+}
+
+impl HashSigner for PrivateKey {
+    #[inline]
+    fn sign_hash(&self, hash: &B256) -> Result<Signature> {
+        HashSigner::sign_hash(&self.signing_key(), hash)
+    }
+}
 
-impl Wallet<SigningKey> {
+impl ZeroizeOnDrop for PrivateKey {}
+
+/// Compares the keys in constant time.
+impl PartialEq for PrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0[..].ct_eq(&other.0[..]).into()
+    }
+}
+
+impl Eq for PrivateKey {}
+
+// do not log the key
+impl fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PrivateKey(<redacted>)")
+    }
+}
+
+impl Wallet<PrivateKey> {
     /// Creates a new Wallet instance from a [`SigningKey`].
     ///
     /// This can also be used to create a Wallet from a [`SecretKey`](SecretKey).
@@ -19,7 +77,7 @@ impl Wallet<SigningKey> {
     #[inline]
     pub fn from_signing_key(signer: SigningKey, network_id: u64) -> Self {
         let address = secret_key_to_address(&signer, network_id);
-        Self::new_with_signer(signer, address, network_id)
+        Self::new_with_signer(PrivateKey::from_signing_key(&signer), address, network_id)
     }
 
     // /// Creates a new Wallet instance from a raw scalar serialized as a [`B256`] byte array.
@@ -45,44 +103,43 @@ impl Wallet<SigningKey> {
     }
 
     /// Creates a new random keypair seeded with [`rand::thread_rng()`].
+    #[cfg(feature = "std")]
     #[inline]
     pub fn random(network_id: u64) -> Self {
         Self::random_with(&mut rand::thread_rng(), network_id)
     }
 
     /// Creates a new random keypair seeded with the provided RNG.
+    ///
+    /// Without the `std` feature, this is the only way to generate a keypair: the RNG is usually
+    /// backed by the hardware RNG of the device.
     #[inline]
     pub fn random_with<R: Rng + CryptoRng>(rng: &mut R, network_id: u64) -> Self {
         Self::from_signing_key(SigningKey::random(rng), network_id)
     }
 
-    // /// Borrow the secret [`NonZeroScalar`] value for this key.
-    // ///
-    // /// # ⚠️ Warning
-    // ///
-    // /// This value is key material.
-    // ///
-    // /// Please treat it with the care it deserves!
-    // #[inline]
-    // pub fn as_nonzero_scalar(&self) -> &NonZeroScalar {
-    //     self.signer.as_nonzero_scalar()
-    // }
-
-    // /// Serialize this [`Wallet`]'s [`SigningKey`] as a [`B256`] byte array.
-    // #[inline]
-    // pub fn to_bytes(&self) -> B256 {
-    //     B256::new(<[u8; 32]>::from(self.to_field_bytes()))
-    // }
+    /// Exports the raw private key of this wallet.
+    ///
+    /// # ⚠️ Warning
+    ///
+    /// This value is key material. The returned bytes are zeroized when dropped, but not the
+    /// copies made from them.
+    ///
+    /// Please treat it with the care it deserves!
+    #[cfg(feature = "key-export")]
+    #[inline]
+    pub fn export_private_key(&self) -> Zeroizing<Vec<u8>> {
+        self.private_key_bytes()
+    }
 
-    // /// Serialize this [`Wallet`]'s [`SigningKey`] as a [`FieldBytes`] byte array.
-    // #[inline]
-    // pub fn to_field_bytes(&self) -> FieldBytes {
-    //     self.signer.to_bytes()
-    // }
+    /// Returns the raw private key of this wallet, zeroized when dropped.
+    pub(crate) fn private_key_bytes(&self) -> Zeroizing<Vec<u8>> {
+        Zeroizing::new(self.signer.0.to_vec())
+    }
 }
 
 #[cfg(feature = "keystore")]
-impl Wallet<SigningKey> {
+impl Wallet<PrivateKey> {
     /// Creates a new random encrypted JSON with the provided password and stores it in the
     /// provided directory. Returns a tuple (Wallet, String) of the wallet instance for the
     /// keystore with its random UUID. Accepts an optional name for the keystore file. If `None`,
@@ -101,6 +158,7 @@ impl Wallet<SigningKey> {
         S: AsRef<[u8]>,
     {
         let (secret, uuid) = xcb_keystore::new(dir, rng, password, name, network_id)?;
+        let secret = Zeroizing::new(secret);
         Ok((
             Self::from_slice(&secret, network_id).map_err(|e| WalletError::LibgoldilockError(e))?,
             uuid,
@@ -118,7 +176,7 @@ impl Wallet<SigningKey> {
         P: AsRef<Path>,
         S: AsRef<[u8]>,
     {
-        let secret = xcb_keystore::decrypt_key(keypath, password)?;
+        let secret = Zeroizing::new(xcb_keystore::decrypt_key(keypath, password)?);
         Ok(Self::from_slice(&secret, network_id).map_err(|e| WalletError::LibgoldilockError(e))?)
     }
 
@@ -147,14 +205,17 @@ impl Wallet<SigningKey> {
     }
 }
 
-impl PartialEq for Wallet<SigningKey> {
+/// Compares the private keys in constant time.
+impl PartialEq for Wallet<PrivateKey> {
     fn eq(&self, other: &Self) -> bool {
-        self.signer.to_bytes().eq(&other.signer.to_bytes())
+        self.signer == other.signer
             && self.address == other.address
             && self.network_id == other.network_id
     }
 }
 
+impl Eq for Wallet<PrivateKey> {}
+
 // impl From<SigningKey> for Wallet<SigningKey> {
 //     fn from(value: SigningKey) -> Self {
 //         Self::from_signing_key(value)
@@ -179,8 +240,9 @@ impl PartialEq for Wallet<SigningKey> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::SignerSync;
+    use crate::{Signer, SignerSync};
     use base_primitives::cAddress;
+    use core::mem::{self, MaybeUninit};
 
     #[cfg(feature = "keystore")]
     use tempfile::tempdir;
@@ -188,15 +250,15 @@ mod tests {
     #[test]
     fn from_signing_key() {
         let s = "010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101";
-        let _pk: Wallet<SigningKey> =
-            Wallet::<SigningKey>::from_signing_key(SigningKey::from_str(s), 1);
+        let _pk: Wallet<PrivateKey> =
+            Wallet::<PrivateKey>::from_signing_key(SigningKey::from_str(s), 1);
     }
 
     #[test]
     fn from_bytes() {
         let s: &[u8; 3] = &[0, 0, 0];
         assert!(s.len() < 64);
-        let pk = Wallet::<SigningKey>::from_slice(s, 1);
+        let pk = Wallet::<PrivateKey>::from_slice(s, 1);
         match pk {
             Err(LibgoldilockErrors::InvalidPrivKeyLengthErrro(3)) => {}
             _ => panic!("Unexpected error"),
@@ -204,7 +266,7 @@ mod tests {
     }
 
     #[cfg(feature = "keystore")]
-    fn test_encrypted_json_keystore(key: Wallet<SigningKey>, uuid: &str, dir: &Path) {
+    fn test_encrypted_json_keystore(key: Wallet<PrivateKey>, uuid: &str, dir: &Path) {
         // sign a message using the given key
         let message = "Some data";
         let signature = key.sign_message_sync(message.as_bytes()).unwrap();
@@ -212,7 +274,7 @@ mod tests {
         // read from the encrypted JSON keystore and decrypt it, while validating that the
         // signatures produced by both the keys should match
         let path = Path::new(dir).join(uuid);
-        let key2 = Wallet::<SigningKey>::decrypt_keystore(path.clone(), "randpsswd", 1).unwrap();
+        let key2 = Wallet::<PrivateKey>::decrypt_keystore(path.clone(), "randpsswd", 1).unwrap();
 
         let signature2 = key2.sign_message_sync(message.as_bytes()).unwrap();
         assert_eq!(signature, signature2);
@@ -227,7 +289,7 @@ mod tests {
         let dir = tempdir().unwrap();
         let mut rng = rand::thread_rng();
         let (key, uuid) =
-            Wallet::<SigningKey>::new_keystore(&dir, &mut rng, "randpsswd", None, 1).unwrap();
+            Wallet::<PrivateKey>::new_keystore(&dir, &mut rng, "randpsswd", None, 1).unwrap();
 
         test_encrypted_json_keystore(key, &uuid, dir.path());
     }
@@ -245,7 +307,7 @@ mod tests {
             hex::decode("6f142508b4eea641e33cb2a0161221105086a84584c74245ca463a49effea30b6f142508b4eea641e33cb2a0161221105086a84584c74245ca")
                 .unwrap();
 
-        let (key, uuid) = Wallet::<SigningKey>::encrypt_keystore(
+        let (key, uuid) = Wallet::<PrivateKey>::encrypt_keystore(
             &dir,
             &mut rng,
             private_key,
@@ -262,7 +324,7 @@ mod tests {
     fn signs_msg() {
         let message = "Some data";
         let hash = base_primitives::utils::eip191_hash_message(message);
-        let key = Wallet::<SigningKey>::random_with(&mut rand::thread_rng(), 1);
+        let key = Wallet::<PrivateKey>::random_with(&mut rand::thread_rng(), 1);
         let address = key.address;
 
         // sign a message
@@ -327,15 +389,47 @@ mod tests {
         assert_eq!(wallet.sign_hash_sync(&dynamic_hash).unwrap(), sig_dynamic);
     }
 
+    #[test]
+    fn equality_and_into_signer() {
+        let wallet = Wallet::<PrivateKey>::random(1);
+        assert_eq!(wallet, wallet.clone());
+        assert_ne!(wallet, Wallet::<PrivateKey>::random(1));
+        assert_ne!(wallet, wallet.clone().with_network_id(3));
+
+        let signer = wallet.clone().into_signer();
+        assert_eq!(&signer, wallet.signer());
+        assert_eq!(format!("{signer:?}"), "PrivateKey(<redacted>)");
+    }
+
+    #[test]
+    fn private_key_zeroized_on_drop() {
+        let wallet = Wallet::<PrivateKey>::random(1);
+        let mut slot = MaybeUninit::new(wallet.into_signer());
+        // SAFETY: the key is initialized, and is not used after being dropped.
+        unsafe { slot.as_mut_ptr().drop_in_place() };
+        const SIZE: usize = mem::size_of::<PrivateKey>();
+        // SAFETY: the bytes of the key were initialized, and dropping it overwrote them in place.
+        let bytes = unsafe { &*slot.as_ptr().cast::<[u8; SIZE]>() };
+        assert_eq!(*bytes, [0; SIZE]);
+    }
+
+    #[test]
+    #[cfg(feature = "key-export")]
+    fn export_private_key() {
+        let wallet = Wallet::<PrivateKey>::random(1);
+        let exported = wallet.export_private_key();
+        assert_eq!(Wallet::from_slice(&exported, 1).unwrap(), wallet);
+    }
+
     #[test]
     fn key_to_address() {
-        let wallet: Wallet<SigningKey> = Wallet::<SigningKey>::from_signing_key(SigningKey::from_str("000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000001"), 1);
+        let wallet: Wallet<PrivateKey> = Wallet::<PrivateKey>::from_signing_key(SigningKey::from_str("000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000001"), 1);
         assert_eq!(wallet.address, cAddress!("cb25df8f3635e284878cdec0ec0de74a90ae619d3efd"));
 
-        let wallet: Wallet<SigningKey> = Wallet::<SigningKey>::from_signing_key(SigningKey::from_str("000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000003"), 3);
+        let wallet: Wallet<PrivateKey> = Wallet::<PrivateKey>::from_signing_key(SigningKey::from_str("000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000003"), 3);
         assert_eq!(wallet.address, cAddress!("ab30be4d7b842cda33cd1f58ebd10790fbcbee2ee808"));
 
-        let wallet: Wallet<SigningKey> = Wallet::<SigningKey>::from_signing_key(SigningKey::from_str("000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000005"), 5);
+        let wallet: Wallet<PrivateKey> = Wallet::<PrivateKey>::from_signing_key(SigningKey::from_str("000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000005"), 5);
         assert_eq!(wallet.address, cAddress!("ce85b3ccb2368c2d4b7a5e1c9c773d42eda1721683b3"));
     }
 
@@ -347,10 +441,10 @@ mod tests {
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 5,
         ];
 
-        let wallet_sk: Wallet<SigningKey> = Wallet::<SigningKey>::from_signing_key(sk, 1);
+        let wallet_sk: Wallet<PrivateKey> = Wallet::<PrivateKey>::from_signing_key(sk, 1);
         assert_eq!(wallet_sk.address, cAddress!("cb94b3ccb2368c2d4b7a5e1c9c773d42eda1721683b3"));
         assert_eq!(wallet_sk.network_id, 1);
-        assert_eq!(wallet_sk.signer, SigningKey::from_bytes(key_slice).unwrap());
+        assert_eq!(*wallet_sk.signer.0, *key_slice);
 
         let wallet_slice = Wallet::from_slice(&key_slice[..], 1).unwrap();
         assert_eq!(wallet_slice.address, wallet_sk.address);
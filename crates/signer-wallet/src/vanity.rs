@@ -0,0 +1,398 @@
+//! Vanity ICAN address generator.
+
+use crate::LocalWallet;
+use atoms_signer::utils::raw_public_key_to_address;
+use base_primitives::{hex, ChainId, IcanAddress};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        mpsc, Arc, Mutex, PoisonError,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+/// The length of a hex encoded ICAN address.
+const ADDRESS_LEN: usize = 44;
+
+/// The offset of the address body in a hex encoded ICAN address, after the network prefix and the
+/// checksum.
+const BODY_OFFSET: usize = 4;
+
+/// Error thrown by [`VanitySearch`].
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum VanityError {
+    /// The pattern contains a character which can't appear at its position.
+    #[error("invalid character {character:?} at position {position} of the address")]
+    InvalidCharacter {
+        /// The invalid character.
+        character: char,
+        /// The position of the character in the hex encoded address.
+        position: usize,
+    },
+    /// The pattern is longer than the part of the address it applies to.
+    #[error("pattern {0:?} is too long")]
+    TooLong(String),
+    /// The pattern conflicts with another one.
+    #[error("pattern {0:?} conflicts with another pattern")]
+    Conflict(String),
+    /// The pattern does not start with the network prefix of the addresses.
+    #[error("addresses of network {network_id} start with {prefix:?}")]
+    NetworkPrefixMismatch {
+        /// The network ID of the search.
+        network_id: ChainId,
+        /// The network prefix of the addresses.
+        prefix: String,
+    },
+}
+
+/// A multi-threaded search for a wallet whose ICAN address matches some patterns.
+///
+/// An ICAN address is made of a network prefix, two checksum digits and a 40 hex characters body,
+/// e.g. `cb` `82` `a5fd22b9bee8b8ab877c86e0a2c21765e1d5bfc5`. Patterns are matched against the
+/// hex encoded address, ignoring case:
+/// - [`body_prefix`](Self::body_prefix) and [`body_suffix`](Self::body_suffix) match the body.
+/// - [`address_prefix`](Self::address_prefix) matches the whole address, including the checksum,
+///   e.g. `cb00` for addresses with a `00` checksum.
+///
+/// Each additional hex character in a pattern makes the search 16 times longer, and each checksum
+/// digit about 10 times longer.
+///
+/// # Examples
+///
+/// ```no_run
+/// use atoms_signer_wallet::VanitySearch;
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let handle = VanitySearch::new(1).body_prefix("c0ffee")?.spawn();
+/// while !handle.is_finished() {
+///     let progress = handle.progress();
+///     println!("{} attempts, {:.0}% likely", progress.attempts, progress.probability() * 100.0);
+///     std::thread::sleep(Duration::from_secs(1));
+/// }
+/// let wallet = handle.wait().unwrap();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct VanitySearch {
+    /// The network ID of the wallets.
+    network_id: ChainId,
+    /// The expected lowercase hex character at each position of the address, if any.
+    pattern: [Option<u8>; ADDRESS_LEN],
+    /// The number of threads.
+    threads: usize,
+}
+
+impl VanitySearch {
+    /// Creates a new search for wallets of the given network, matching any address.
+    ///
+    /// Uses as many threads as the available parallelism.
+    pub fn new(network_id: ChainId) -> Self {
+        let threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+        Self { network_id, pattern: [None; ADDRESS_LEN], threads }
+    }
+
+    /// Sets the number of threads to search with.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Matches addresses whose body starts with the given hex characters.
+    pub fn body_prefix(self, prefix: &str) -> Result<Self, VanityError> {
+        if prefix.len() > ADDRESS_LEN - BODY_OFFSET {
+            return Err(VanityError::TooLong(prefix.to_string()));
+        }
+        self.with_pattern(prefix, BODY_OFFSET)
+    }
+
+    /// Matches addresses whose body ends with the given hex characters.
+    pub fn body_suffix(self, suffix: &str) -> Result<Self, VanityError> {
+        if suffix.len() > ADDRESS_LEN - BODY_OFFSET {
+            return Err(VanityError::TooLong(suffix.to_string()));
+        }
+        self.with_pattern(suffix, ADDRESS_LEN - suffix.len())
+    }
+
+    /// Matches addresses starting with the given characters, including the network prefix and
+    /// the checksum digits.
+    pub fn address_prefix(self, prefix: &str) -> Result<Self, VanityError> {
+        if prefix.len() > ADDRESS_LEN {
+            return Err(VanityError::TooLong(prefix.to_string()));
+        }
+        let network_prefix = network_prefix(self.network_id);
+        let len = prefix.len().min(BODY_OFFSET / 2);
+        if !prefix.is_char_boundary(len)
+            || !prefix[..len].eq_ignore_ascii_case(&network_prefix[..len])
+        {
+            return Err(VanityError::NetworkPrefixMismatch {
+                network_id: self.network_id,
+                prefix: network_prefix,
+            });
+        }
+        self.with_pattern(prefix, 0)
+    }
+
+    fn with_pattern(mut self, pattern: &str, offset: usize) -> Result<Self, VanityError> {
+        for (i, character) in pattern.chars().enumerate() {
+            let position = offset + i;
+            let valid = if (2..BODY_OFFSET).contains(&position) {
+                character.is_ascii_digit()
+            } else {
+                character.is_ascii_hexdigit()
+            };
+            if !valid {
+                return Err(VanityError::InvalidCharacter { character, position });
+            }
+
+            let expected = character.to_ascii_lowercase() as u8;
+            match self.pattern[position] {
+                Some(previous) if previous != expected => {
+                    return Err(VanityError::Conflict(pattern.to_string()));
+                }
+                _ => self.pattern[position] = Some(expected),
+            }
+        }
+        Ok(self)
+    }
+
+    /// Returns `true` if the address matches the patterns.
+    pub fn matches(&self, address: &IcanAddress) -> bool {
+        let encoded = hex::encode(address);
+        self.pattern
+            .iter()
+            .zip(encoded.bytes())
+            .all(|(expected, actual)| expected.map_or(true, |expected| expected == actual))
+    }
+
+    /// Returns the expected number of attempts to find a matching address.
+    ///
+    /// Checksum digits are assumed to be uniformly distributed, so this is an approximation if
+    /// they are constrained.
+    pub fn expected_attempts(&self) -> f64 {
+        self.pattern
+            .iter()
+            .enumerate()
+            .filter(|(_, expected)| expected.is_some())
+            .map(|(position, _)| match position {
+                0 | 1 => 1.0,
+                2 | 3 => 10.0,
+                _ => 16.0,
+            })
+            .product()
+    }
+
+    /// Starts the search on background threads.
+    pub fn spawn(self) -> VanityHandle {
+        let state = Arc::new(SearchState {
+            stop: AtomicBool::new(false),
+            attempts: AtomicU64::new(0),
+            running: AtomicUsize::new(self.threads),
+        });
+        let (sender, receiver) = mpsc::channel();
+
+        for _ in 0..self.threads {
+            let search = self.clone();
+            let state = state.clone();
+            let sender = sender.clone();
+            thread::spawn(move || {
+                let mut rng = rand::thread_rng();
+                while !state.stop.load(Ordering::Relaxed) {
+                    let wallet = LocalWallet::random_with(&mut rng, search.network_id);
+                    state.attempts.fetch_add(1, Ordering::Relaxed);
+                    if search.matches(&wallet.address())
+                        && !state.stop.swap(true, Ordering::Relaxed)
+                    {
+                        let _ = sender.send(wallet);
+                    }
+                }
+                state.running.fetch_sub(1, Ordering::Release);
+            });
+        }
+
+        VanityHandle {
+            state,
+            result: Mutex::new(receiver),
+            started: Instant::now(),
+            expected_attempts: self.expected_attempts(),
+        }
+    }
+
+    /// Runs the search, blocking until a matching wallet is found.
+    pub fn run(self) -> LocalWallet {
+        self.spawn().wait().expect("search was not cancelled")
+    }
+}
+
+/// The state shared by the threads of a search.
+#[derive(Debug)]
+struct SearchState {
+    /// Whether the search is over.
+    stop: AtomicBool,
+    /// The number of generated wallets.
+    attempts: AtomicU64,
+    /// The number of running threads.
+    running: AtomicUsize,
+}
+
+/// A handle to a running [`VanitySearch`].
+///
+/// Dropping the handle cancels the search.
+#[derive(Debug)]
+pub struct VanityHandle {
+    state: Arc<SearchState>,
+    result: Mutex<mpsc::Receiver<LocalWallet>>,
+    started: Instant,
+    expected_attempts: f64,
+}
+
+impl VanityHandle {
+    /// Returns the progress of the search.
+    pub fn progress(&self) -> VanityProgress {
+        VanityProgress {
+            attempts: self.state.attempts.load(Ordering::Relaxed),
+            expected_attempts: self.expected_attempts,
+            elapsed: self.started.elapsed(),
+        }
+    }
+
+    /// Cancels the search.
+    pub fn cancel(&self) {
+        self.state.stop.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if a wallet was found, or the search was cancelled and all threads exited.
+    pub fn is_finished(&self) -> bool {
+        self.state.running.load(Ordering::Acquire) == 0
+    }
+
+    /// Waits for the search to end, returning the matching wallet, or `None` if the search was
+    /// cancelled.
+    pub fn wait(&self) -> Option<LocalWallet> {
+        self.result.lock().unwrap_or_else(PoisonError::into_inner).recv().ok()
+    }
+}
+
+impl Drop for VanityHandle {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// The progress of a [`VanitySearch`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VanityProgress {
+    /// The number of generated wallets.
+    pub attempts: u64,
+    /// The expected number of attempts to find a matching wallet.
+    pub expected_attempts: f64,
+    /// The time elapsed since the search started.
+    pub elapsed: Duration,
+}
+
+impl VanityProgress {
+    /// Returns the number of wallets generated per second.
+    pub fn attempts_per_second(&self) -> f64 {
+        self.attempts as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Returns the probability to have found a matching wallet after this many attempts.
+    pub fn probability(&self) -> f64 {
+        1.0 - (1.0 - 1.0 / self.expected_attempts).powf(self.attempts as f64)
+    }
+
+    /// Returns the estimated time until the expected number of attempts is reached, if any wallet
+    /// was generated yet.
+    pub fn estimated_remaining(&self) -> Option<Duration> {
+        let rate = self.attempts_per_second();
+        if self.attempts == 0 || rate <= 0.0 {
+            return None;
+        }
+        let remaining = (self.expected_attempts - self.attempts as f64).max(0.0);
+        Some(Duration::from_secs_f64(remaining / rate))
+    }
+}
+
+/// Returns the network prefix of the hex encoded addresses of the network.
+fn network_prefix(network_id: ChainId) -> String {
+    // the prefix doesn't depend on the key, so any public key does
+    let address = raw_public_key_to_address(&[0; 57], network_id);
+    hex::encode(address)[..BODY_OFFSET / 2].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn network_prefixes() {
+        assert_eq!(network_prefix(1), "cb");
+        assert_eq!(network_prefix(3), "ab");
+    }
+
+    #[test]
+    fn patterns() {
+        let address: IcanAddress = "cb82a5fd22b9bee8b8ab877c86e0a2c21765e1d5bfc5".parse().unwrap();
+
+        let search = VanitySearch::new(1).body_prefix("A5fd").unwrap().body_suffix("bfc5").unwrap();
+        assert!(search.matches(&address));
+        assert_eq!(search.expected_attempts(), 16f64.powi(8));
+
+        let search = VanitySearch::new(1).address_prefix("cb82a5").unwrap();
+        assert!(search.matches(&address));
+        assert_eq!(search.expected_attempts(), 100.0 * 256.0);
+        assert!(!VanitySearch::new(1).address_prefix("cb83").unwrap().matches(&address));
+
+        assert_eq!(
+            VanitySearch::new(1).body_prefix("xy").unwrap_err(),
+            VanityError::InvalidCharacter { character: 'x', position: 4 }
+        );
+        assert_eq!(
+            VanitySearch::new(1).address_prefix("cbab").unwrap_err(),
+            VanityError::InvalidCharacter { character: 'a', position: 2 }
+        );
+        assert!(matches!(
+            VanitySearch::new(1).address_prefix("ab").unwrap_err(),
+            VanityError::NetworkPrefixMismatch { network_id: 1, .. }
+        ));
+        assert_eq!(
+            VanitySearch::new(1).address_prefix("cb82a5").unwrap().body_prefix("b").unwrap_err(),
+            VanityError::Conflict("b".to_string())
+        );
+        assert!(VanitySearch::new(1).body_suffix(&"0".repeat(41)).is_err());
+    }
+
+    #[test]
+    fn search() {
+        let search = VanitySearch::new(1).threads(2).body_prefix("a").unwrap().body_suffix("b");
+        let search = search.unwrap();
+        let handle = search.clone().spawn();
+        let wallet = handle.wait().unwrap();
+        assert!(search.matches(&wallet.address()));
+        assert!(handle.progress().attempts >= 1);
+
+        // checksum aware patterns
+        let search = VanitySearch::new(1).threads(2).address_prefix("cb1").unwrap();
+        let wallet = search.run();
+        let encoded = hex::encode(wallet.address());
+        assert!(encoded.starts_with("cb1"));
+        assert_eq!(encoded.parse::<IcanAddress>().unwrap(), wallet.address());
+    }
+
+    #[test]
+    fn cancel() {
+        let handle = VanitySearch::new(1).threads(2).body_prefix(&"0".repeat(40)).unwrap().spawn();
+        thread::sleep(Duration::from_millis(50));
+        handle.cancel();
+        assert!(handle.wait().is_none());
+        assert!(handle.is_finished());
+
+        let progress = handle.progress();
+        assert!(progress.attempts > 0);
+        assert!(progress.probability() < 1e-9);
+        assert!(progress.estimated_remaining().is_some());
+    }
+}
@@ -0,0 +1,303 @@
+//! Keystore directory watcher.
+
+use crate::{LocalWallet, WalletError};
+use atoms_network::CoreSigner;
+use base_primitives::{ChainId, IcanAddress};
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+use tokio::task::JoinHandle;
+use zeroize::Zeroizing;
+
+/// The default interval between two scans of the keystore directory.
+pub const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Keeps the signers of a [`CoreSigner`] in sync with the keystores of a directory.
+///
+/// Every scan registers the signers of the keystores added to the directory, and removes the
+/// signers of the keystores removed from it. All keystores are decrypted with the same password.
+/// Keystores that can't be decrypted are skipped until they are modified.
+///
+/// The watcher only removes the signers it registered, once no keystore of their account is left
+/// in the directory. Signers registered by other means are never replaced nor removed.
+///
+/// As the signers of a [`CoreSigner`] are shared between its clones, the watcher can be given a
+/// clone of the signer of a provider to rotate its accounts while it is in use.
+///
+/// ```no_run
+/// use atoms_network::CoreSigner;
+/// use atoms_signer_wallet::KeystoreWatcher;
+///
+/// # async fn f() {
+/// let signer = CoreSigner::default();
+/// let watcher = KeystoreWatcher::new("./keystore", "password", 1, signer.clone());
+/// let handle = watcher.spawn();
+/// # }
+/// ```
+pub struct KeystoreWatcher {
+    /// The watched directory.
+    dir: PathBuf,
+    /// The password of the keystores.
+    password: Zeroizing<String>,
+    /// The network ID of the wallets.
+    network_id: ChainId,
+    /// The synced signer.
+    signer: CoreSigner,
+    /// The interval between two scans.
+    interval: Duration,
+    /// The keystores seen in the directory, with their modification time and the address of their
+    /// wallet, if they could be decrypted and their signer is owned by the watcher.
+    keystores: HashMap<PathBuf, (SystemTime, Option<IcanAddress>)>,
+    /// The addresses of the signers registered by the watcher, with the number of keystores of
+    /// each of them in the directory.
+    owned: HashMap<IcanAddress, usize>,
+}
+
+// do not log the password
+impl fmt::Debug for KeystoreWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeystoreWatcher")
+            .field("dir", &self.dir)
+            .field("network_id", &self.network_id)
+            .field("signer", &self.signer)
+            .field("interval", &self.interval)
+            .field("keystores", &self.keystores.len())
+            .field("owned", &self.owned.len())
+            .finish()
+    }
+}
+
+/// The changes made to the signer by a [`KeystoreWatcher`] scan.
+#[derive(Debug, Default)]
+pub struct KeystoreChanges {
+    /// The addresses of the registered signers.
+    pub added: Vec<IcanAddress>,
+    /// The addresses of the removed signers.
+    pub removed: Vec<IcanAddress>,
+    /// The keystores that could not be decrypted.
+    pub failed: Vec<(PathBuf, WalletError)>,
+}
+
+impl KeystoreChanges {
+    /// Returns `true` if the scan did not change the signer.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl KeystoreWatcher {
+    /// Creates a new watcher of the keystores in `dir`, registering their wallets in `signer`.
+    pub fn new(
+        dir: impl Into<PathBuf>,
+        password: impl Into<String>,
+        network_id: ChainId,
+        signer: CoreSigner,
+    ) -> Self {
+        Self {
+            dir: dir.into(),
+            password: Zeroizing::new(password.into()),
+            network_id,
+            signer,
+            interval: DEFAULT_WATCH_INTERVAL,
+            keystores: HashMap::new(),
+            owned: HashMap::new(),
+        }
+    }
+
+    /// Sets the interval between two scans of the directory. Defaults to
+    /// [`DEFAULT_WATCH_INTERVAL`].
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Returns the watched directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the synced signer.
+    pub const fn signer(&self) -> &CoreSigner {
+        &self.signer
+    }
+
+    /// Scans the directory once, updating the signer.
+    ///
+    /// Decrypting keystores is CPU intensive, so this should not be called from an async context.
+    pub fn sync(&mut self) -> Result<KeystoreChanges, WalletError> {
+        let mut changes = KeystoreChanges::default();
+
+        let mut seen = HashMap::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            // skip hidden files, such as the temporary files written by `KeystoreDir`
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let metadata = entry.metadata()?;
+            if metadata.is_file() {
+                seen.insert(entry.path(), metadata.modified()?);
+            }
+        }
+
+        // keystores removed or modified since the last scan
+        let stale = self
+            .keystores
+            .iter()
+            .filter(|(path, (modified, _))| seen.get(*path) != Some(modified))
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+        for path in stale {
+            if let Some((_, Some(address))) = self.keystores.remove(&path) {
+                self.release(address, &mut changes);
+            }
+        }
+
+        for (path, modified) in seen {
+            if self.keystores.contains_key(&path) {
+                continue;
+            }
+            let address = match LocalWallet::decrypt_keystore(
+                &path,
+                self.password.as_bytes(),
+                self.network_id,
+            ) {
+                Ok(wallet) => {
+                    let address = wallet.address();
+                    if let Some(count) = self.owned.get_mut(&address) {
+                        *count += 1;
+                        Some(address)
+                    } else if self.signer.signer_by_address(address).is_some() {
+                        // registered by someone else, leave it alone
+                        None
+                    } else {
+                        self.signer.register_signer(wallet);
+                        self.owned.insert(address, 1);
+                        changes.added.push(address);
+                        Some(address)
+                    }
+                }
+                Err(err) => {
+                    changes.failed.push((path.clone(), err));
+                    None
+                }
+            };
+            self.keystores.insert(path, (modified, address));
+        }
+
+        Ok(changes)
+    }
+
+    /// Releases a keystore of the given owned address, removing its signer if it was the last one.
+    fn release(&mut self, address: IcanAddress, changes: &mut KeystoreChanges) {
+        let Some(count) = self.owned.get_mut(&address) else { return };
+        *count -= 1;
+        if *count == 0 {
+            self.owned.remove(&address);
+            if self.signer.remove_signer(&address).is_some() {
+                changes.removed.push(address);
+            }
+        }
+    }
+
+    /// Spawns a task scanning the directory at every interval until it is aborted.
+    ///
+    /// Scans are run on the blocking thread pool. Errors reading the directory are ignored, and
+    /// the directory is scanned again at the next interval.
+    pub fn spawn(mut self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            loop {
+                interval.tick().await;
+                self = match tokio::task::spawn_blocking(move || {
+                    let _ = self.sync();
+                    self
+                })
+                .await
+                {
+                    Ok(this) => this,
+                    Err(_) => return,
+                };
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atoms_network::{Ethereum, NetworkSigner};
+    use tempfile::tempdir;
+
+    #[test]
+    fn sync_keystores() {
+        let dir = tempdir().unwrap();
+        let mut rng = rand::thread_rng();
+        let signer = CoreSigner::default();
+        let mut watcher = KeystoreWatcher::new(dir.path(), "randpsswd", 1, signer.clone());
+        assert!(watcher.sync().unwrap().is_empty());
+
+        let (first, first_uuid) =
+            LocalWallet::new_keystore(&dir, &mut rng, "randpsswd", None, 1).unwrap();
+        let (second, _) = LocalWallet::new_keystore(&dir, &mut rng, "randpsswd", None, 1).unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not a keystore").unwrap();
+        // partially written keystores are ignored
+        std::fs::write(dir.path().join(".partial.tmp"), "{").unwrap();
+
+        let changes = watcher.sync().unwrap();
+        assert_eq!(changes.added.len(), 2);
+        assert_eq!(changes.failed.len(), 1);
+        assert_eq!(changes.failed[0].0, dir.path().join("notes.txt"));
+        assert!(NetworkSigner::<Ethereum>::has_signer_for(&signer, &first.address()));
+        assert!(NetworkSigner::<Ethereum>::has_signer_for(&signer, &second.address()));
+
+        // unchanged keystores are not decrypted again
+        let changes = watcher.sync().unwrap();
+        assert!(changes.is_empty());
+        assert!(changes.failed.is_empty());
+
+        std::fs::remove_file(dir.path().join(first_uuid)).unwrap();
+        let changes = watcher.sync().unwrap();
+        assert_eq!(changes.removed, vec![first.address()]);
+        assert!(!NetworkSigner::<Ethereum>::has_signer_for(&signer, &first.address()));
+        assert!(NetworkSigner::<Ethereum>::has_signer_for(&signer, &second.address()));
+    }
+
+    #[test]
+    fn sync_only_removes_owned_signers() {
+        let dir = tempdir().unwrap();
+        let mut rng = rand::thread_rng();
+        let signer = CoreSigner::default();
+        let mut watcher = KeystoreWatcher::new(dir.path(), "randpsswd", 1, signer.clone());
+
+        // a signer registered before the watcher found its keystore
+        let (external, external_uuid) =
+            LocalWallet::new_keystore(&dir, &mut rng, "randpsswd", None, 1).unwrap();
+        let external_address = external.address();
+        signer.register_signer(external);
+
+        // two keystores of the same account
+        let (owned, owned_uuid) =
+            LocalWallet::new_keystore(&dir, &mut rng, "randpsswd", None, 1).unwrap();
+        let copy = dir.path().join("copy");
+        std::fs::copy(dir.path().join(&owned_uuid), &copy).unwrap();
+
+        let changes = watcher.sync().unwrap();
+        assert_eq!(changes.added, vec![owned.address()]);
+
+        std::fs::remove_file(dir.path().join(external_uuid)).unwrap();
+        assert!(watcher.sync().unwrap().is_empty());
+        assert!(NetworkSigner::<Ethereum>::has_signer_for(&signer, &external_address));
+
+        std::fs::remove_file(dir.path().join(owned_uuid)).unwrap();
+        assert!(watcher.sync().unwrap().is_empty());
+        assert!(NetworkSigner::<Ethereum>::has_signer_for(&signer, &owned.address()));
+
+        std::fs::remove_file(copy).unwrap();
+        assert_eq!(watcher.sync().unwrap().removed, vec![owned.address()]);
+        assert!(!NetworkSigner::<Ethereum>::has_signer_for(&signer, &owned.address()));
+    }
+}
@@ -1,52 +1,162 @@
-use base_primitives::hex;
-use std::fmt;
-use thiserror::Error;
+use base_primitives::{hex, ChainId, IcanAddress, Selector, U256};
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
 
 /// Result type alias for [`Error`](enum@Error).
-pub type Result<T, E = Error> = std::result::Result<T, E>;
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+/// The error type held by [`Error::Other`].
+#[cfg(feature = "std")]
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// The error type held by [`Error::Other`].
+///
+/// Without the `std` feature, this is any debuggable and displayable type, as
+/// `std::error::Error` is not available.
+#[cfg(not(feature = "std"))]
+pub type BoxError = Box<dyn OtherError + Send + Sync + 'static>;
+
+/// An error which can be held by [`Error::Other`] without the `std` feature.
+#[cfg(not(feature = "std"))]
+pub trait OtherError: fmt::Debug + fmt::Display {}
+
+#[cfg(not(feature = "std"))]
+impl<T: fmt::Debug + fmt::Display + ?Sized> OtherError for T {}
 
 /// Generic error type for [`Signer`](crate::Signer) implementations.
-#[derive(Debug, Error)]
+#[derive(Debug)]
 pub enum Error {
     /// This operation is not supported by the signer.
-    #[error("operation `{0}` is not supported by the signer")]
     UnsupportedOperation(UnsupportedSignerOperation),
     /// Mismatch between provided transaction chain ID and signer network ID.
-    #[error("transaction-provided network ID ({tx}) does not match the signer's ({signer})")]
     TransactionNetworkIdMismatch {
         /// The signer's network ID.
         signer: u64,
         /// The network ID provided by the transaction.
         tx: u64,
     },
+    /// The signature was not produced by the expected signer.
+    SignerMismatch {
+        /// The expected signer.
+        expected: IcanAddress,
+        /// The signer recovered from the signature.
+        recovered: IcanAddress,
+    },
+    /// The transaction violates the signing policy.
+    PolicyViolation(PolicyViolation),
     /// [`base_dyn_abi`] error.
-    #[error(transparent)]
     #[cfg(feature = "eip712")]
-    DynAbiError(#[from] base_dyn_abi::Error),
+    DynAbiError(base_dyn_abi::Error),
     /// [`hex`](mod@hex) error.
-    #[error(transparent)]
-    HexError(#[from] hex::FromHexError),
+    HexError(hex::FromHexError),
     /// Signature error.
-    #[error(transparent)]
-    SignatureError(#[from] base_primitives::SignatureError),
+    SignatureError(base_primitives::SignatureError),
     /// Generic error.
-    #[error(transparent)]
-    Other(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
+    Other(BoxError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedOperation(op) => {
+                write!(f, "operation `{op}` is not supported by the signer")
+            }
+            Self::TransactionNetworkIdMismatch { signer, tx } => write!(
+                f,
+                "transaction-provided network ID ({tx}) does not match the signer's ({signer})"
+            ),
+            Self::SignerMismatch { expected, recovered } => {
+                write!(f, "signature was produced by {recovered}, expected {expected}")
+            }
+            Self::PolicyViolation(violation) => write!(f, "signing policy violation: {violation}"),
+            #[cfg(feature = "eip712")]
+            Self::DynAbiError(err) => fmt::Display::fmt(err, f),
+            Self::HexError(err) => fmt::Display::fmt(err, f),
+            Self::SignatureError(err) => fmt::Display::fmt(err, f),
+            Self::Other(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::PolicyViolation(violation) => Some(violation),
+            #[cfg(feature = "eip712")]
+            Self::DynAbiError(err) => std::error::Error::source(err),
+            Self::HexError(err) => std::error::Error::source(err),
+            Self::SignatureError(err) => std::error::Error::source(err),
+            Self::Other(err) => err.source(),
+            _ => None,
+        }
+    }
+}
+
+impl From<PolicyViolation> for Error {
+    fn from(violation: PolicyViolation) -> Self {
+        Self::PolicyViolation(violation)
+    }
+}
+
+#[cfg(feature = "eip712")]
+impl From<base_dyn_abi::Error> for Error {
+    fn from(err: base_dyn_abi::Error) -> Self {
+        Self::DynAbiError(err)
+    }
+}
+
+impl From<hex::FromHexError> for Error {
+    fn from(err: hex::FromHexError) -> Self {
+        Self::HexError(err)
+    }
+}
+
+impl From<base_primitives::SignatureError> for Error {
+    fn from(err: base_primitives::SignatureError) -> Self {
+        Self::SignatureError(err)
+    }
+}
+
+impl From<BoxError> for Error {
+    fn from(err: BoxError) -> Self {
+        Self::Other(err)
+    }
 }
 
 impl Error {
     /// Constructs a new [`Other`](Self::Other) error.
+    #[cfg(feature = "std")]
     #[cold]
-    pub fn other(error: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>) -> Self {
+    pub fn other(error: impl Into<BoxError>) -> Self {
         Self::Other(error.into())
     }
 
+    /// Constructs a new [`Other`](Self::Other) error.
+    #[cfg(not(feature = "std"))]
+    #[cold]
+    pub fn other(error: impl OtherError + Send + Sync + 'static) -> Self {
+        Self::Other(Box::new(error))
+    }
+
     /// Returns `true` if the error is [`UnsupportedOperation`](Self::UnsupportedOperation).
     #[inline]
     pub const fn is_unsupported(&self) -> bool {
         matches!(self, Self::UnsupportedOperation(_))
     }
 
+    /// Returns the [`PolicyViolation`] if the error is
+    /// [`PolicyViolation`](Self::PolicyViolation).
+    #[inline]
+    pub const fn policy_violation(&self) -> Option<&PolicyViolation> {
+        match self {
+            Self::PolicyViolation(violation) => Some(violation),
+            _ => None,
+        }
+    }
+
     /// Returns the [`UnsupportedSignerOperation`] if the error is
     /// [`UnsupportedOperation`](Self::UnsupportedOperation).
     #[inline]
@@ -89,3 +199,77 @@ impl UnsupportedSignerOperation {
         }
     }
 }
+
+/// A rule of a signing policy violated by a transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PolicyViolation {
+    /// The destination of the transaction is not allowed.
+    DestinationNotAllowed(IcanAddress),
+    /// Contract creations are not allowed.
+    CreateNotAllowed,
+    /// The value of the transaction exceeds the per-transaction cap.
+    ValueTooHigh {
+        /// The value of the transaction.
+        value: U256,
+        /// The per-transaction cap.
+        max: U256,
+    },
+    /// The value of the transaction would exceed the cap of the rolling window.
+    WindowCapExceeded {
+        /// The value of the transaction.
+        value: U256,
+        /// The value already spent in the window.
+        spent: U256,
+        /// The cap of the window.
+        cap: U256,
+    },
+    /// The energy price of the transaction exceeds the ceiling.
+    EnergyPriceTooHigh {
+        /// The energy price of the transaction.
+        energy_price: u128,
+        /// The energy price ceiling.
+        max: u128,
+    },
+    /// The function selector of the transaction is not allowed.
+    SelectorNotAllowed(Selector),
+    /// The input of the transaction is too short to hold a function selector.
+    MissingSelector,
+    /// The network ID of the transaction is not the pinned one.
+    NetworkIdMismatch {
+        /// The pinned network ID.
+        pinned: ChainId,
+        /// The network ID of the transaction.
+        tx: ChainId,
+    },
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DestinationNotAllowed(to) => write!(f, "destination {to} is not allowed"),
+            Self::CreateNotAllowed => f.write_str("contract creation is not allowed"),
+            Self::ValueTooHigh { value, max } => {
+                write!(f, "value {value} exceeds the per-transaction cap of {max}")
+            }
+            Self::WindowCapExceeded { value, spent, cap } => {
+                write!(
+                    f,
+                    "value {value} would exceed the window cap of {cap}, {spent} already spent"
+                )
+            }
+            Self::EnergyPriceTooHigh { energy_price, max } => {
+                write!(f, "energy price {energy_price} exceeds the ceiling of {max}")
+            }
+            Self::SelectorNotAllowed(selector) => {
+                write!(f, "function selector {selector} is not allowed")
+            }
+            Self::MissingSelector => f.write_str("input is too short to hold a function selector"),
+            Self::NetworkIdMismatch { pinned, tx } => {
+                write!(f, "network ID {tx} does not match the pinned network ID {pinned}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PolicyViolation {}
@@ -14,15 +14,28 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![deny(unused_must_use, rust_2018_idioms)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
 
 mod error;
-pub use error::{Error, Result, UnsupportedSignerOperation};
+#[cfg(not(feature = "std"))]
+pub use error::OtherError;
+pub use error::{BoxError, Error, PolicyViolation, Result, UnsupportedSignerOperation};
 
 mod signer;
-pub use signer::{Signer, SignerSync};
+#[cfg(feature = "std")]
+pub use signer::Signer;
+pub use signer::{SignerSync, TxSignerSync};
 
 pub mod utils;
 
+mod verify;
+#[cfg(feature = "eip712")]
+pub use verify::{verify_dynamic_typed_data, verify_typed_data};
+pub use verify::{verify_hash, verify_message, SignedMessage};
+
 pub use base_primitives::Signature;
 
 /// Utility to get and set the network ID on a transaction and the resulting signature within a
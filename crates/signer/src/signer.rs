@@ -1,8 +1,11 @@
 use crate::Result;
-use async_trait::async_trait;
+use atoms_consensus::SignableTransaction;
 use auto_impl::auto_impl;
 use base_primitives::{eip191_hash_message, ChainId, IcanAddress, Signature, B256};
 
+#[cfg(feature = "std")]
+use async_trait::async_trait;
+
 #[cfg(feature = "eip712")]
 use base_dyn_abi::eip712::TypedData;
 #[cfg(feature = "eip712")]
@@ -17,7 +20,10 @@ use base_ylm_types::{Eip712Domain, YlmStruct};
 ///
 /// Synchronous signers should implement both this trait and [`SignerSync`].
 ///
+/// This trait requires the `std` feature.
+///
 /// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+#[cfg(feature = "std")]
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 #[auto_impl(&mut, Box)]
@@ -90,6 +96,8 @@ pub trait Signer<Sig = Signature> {
 /// Synchronous signers should also implement [`Signer`], as they are always able to by delegating
 /// the asynchronous methods to the synchronous ones.
 ///
+/// Unlike [`Signer`], this trait is available without the `std` feature.
+///
 /// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
 #[auto_impl(&, &mut, Box, Rc, Arc)]
 pub trait SignerSync<Sig = Signature> {
@@ -132,6 +140,33 @@ pub trait SignerSync<Sig = Signature> {
     fn network_id_sync(&self) -> ChainId;
 }
 
+/// Synchronous transaction signer,  capable of signing any [`SignableTransaction`] for the given
+/// `Signature` type.
+///
+/// A signer should hold an optional [`ChainId`] value, which is used for [EIP-155] replay
+/// protection.
+///
+/// If `chain_id` is Some, [EIP-155] should be applied to the input transaction in
+/// [`sign_transaction_sync`](Self::sign_transaction_sync), and to the resulting signature in all
+/// the methods. If `chain_id` is None, [EIP-155] should not be applied.
+///
+/// Synchronous signers should also implement `atoms_network::TxSigner`, as they are always able to
+/// by delegating the asynchronous methods to the synchronous ones.
+///
+/// This trait is re-exported by `atoms-network`, and is available without the `std` feature.
+///
+/// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+pub trait TxSignerSync<Signature> {
+    /// Get the address of the signer.
+    fn address(&self) -> IcanAddress;
+
+    /// Synchronously sign an unsigned transaction.
+    fn sign_transaction_sync(
+        &self,
+        tx: &mut dyn SignableTransaction<Signature>,
+    ) -> Result<Signature>;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
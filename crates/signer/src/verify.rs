@@ -0,0 +1,197 @@
+//! Signature verification.
+//!
+//! Ed448 signatures embed the public key of their signer, which is verified against the signed
+//! hash before the address is derived from it. The address depends on the network, so the network
+//! ID the signature was produced for must be known to verify it.
+
+use crate::{Error, Result, SignerSync};
+use base_primitives::{eip191_hash_message, Bytes, ChainId, IcanAddress, Signature, B256};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "std")]
+use crate::Signer;
+
+#[cfg(feature = "eip712")]
+use base_dyn_abi::eip712::TypedData;
+#[cfg(feature = "eip712")]
+use base_ylm_types::{Eip712Domain, YlmStruct};
+
+/// Verifies a signature of the given hash, returning the address of the signer.
+#[inline]
+pub fn verify_hash(signature: &Signature, hash: &B256, network_id: ChainId) -> Result<IcanAddress> {
+    Ok(signature.recover_address_from_prehash(hash, network_id)?)
+}
+
+/// Verifies a signature of the given message, prefixed as specified in [EIP-191], returning the
+/// address of the signer.
+///
+/// This is the counterpart of [`Signer::sign_message`].
+///
+/// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+#[inline]
+pub fn verify_message(
+    signature: &Signature,
+    message: &[u8],
+    network_id: ChainId,
+) -> Result<IcanAddress> {
+    verify_hash(signature, &eip191_hash_message(message), network_id)
+}
+
+/// Verifies a signature of the typed data, encoded according to [EIP-712], returning the address
+/// of the signer.
+///
+/// This is the counterpart of [`Signer::sign_typed_data`].
+///
+/// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+#[cfg(feature = "eip712")]
+#[inline]
+pub fn verify_typed_data<T: YlmStruct>(
+    signature: &Signature,
+    payload: &T,
+    domain: &Eip712Domain,
+    network_id: ChainId,
+) -> Result<IcanAddress> {
+    verify_hash(signature, &payload.eip712_signing_hash(domain), network_id)
+}
+
+/// Verifies a signature of the dynamic typed data, encoded according to [EIP-712], returning the
+/// address of the signer.
+///
+/// This is the counterpart of [`Signer::sign_dynamic_typed_data`].
+///
+/// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+#[cfg(feature = "eip712")]
+#[inline]
+pub fn verify_dynamic_typed_data(
+    signature: &Signature,
+    payload: &TypedData,
+    network_id: ChainId,
+) -> Result<IcanAddress> {
+    verify_hash(signature, &payload.eip712_signing_hash()?, network_id)
+}
+
+/// A message signed as specified in [EIP-191], along with the network ID of the signer.
+///
+/// This is a self-contained, serializable proof of ownership of an address, e.g. for a login flow:
+/// the client signs a challenge with [`SignedMessage::sign`], and the server checks the envelope
+/// with [`SignedMessage::verify`].
+///
+/// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedMessage {
+    /// The signed message, without prefix.
+    pub message: Bytes,
+    /// The signature of the prefixed message.
+    pub signature: Signature,
+    /// The network ID of the signer.
+    pub network_id: ChainId,
+}
+
+impl SignedMessage {
+    /// Creates a new signed message.
+    pub fn new(message: impl Into<Bytes>, signature: Signature, network_id: ChainId) -> Self {
+        Self { message: message.into(), signature, network_id }
+    }
+
+    /// Signs the message with the given signer.
+    #[cfg(feature = "std")]
+    pub async fn sign<S: Signer + ?Sized>(signer: &S, message: impl Into<Bytes>) -> Result<Self> {
+        let message = message.into();
+        let signature = signer.sign_message(&message).await?;
+        Ok(Self { message, signature, network_id: signer.network_id() })
+    }
+
+    /// Signs the message with the given synchronous signer.
+    pub fn sign_sync<S: SignerSync + ?Sized>(
+        signer: &S,
+        message: impl Into<Bytes>,
+    ) -> Result<Self> {
+        let message = message.into();
+        let signature = signer.sign_message_sync(&message)?;
+        Ok(Self { message, signature, network_id: signer.network_id_sync() })
+    }
+
+    /// Verifies the signature, returning the address of the signer.
+    #[inline]
+    pub fn recover(&self) -> Result<IcanAddress> {
+        verify_message(&self.signature, &self.message, self.network_id)
+    }
+
+    /// Verifies that the message was signed by the given address.
+    pub fn verify(&self, address: IcanAddress) -> Result<()> {
+        let recovered = self.recover()?;
+        if recovered != address {
+            return Err(Error::SignerMismatch { expected: address, recovered });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use atoms_signer_wallet::LocalWallet;
+
+    #[test]
+    fn verify_message_and_hash() {
+        let wallet = LocalWallet::random(1);
+        let message = b"hello";
+
+        let signature = wallet.sign_message_sync(message).unwrap();
+        assert_eq!(verify_message(&signature, message, 1).unwrap(), wallet.address());
+        assert_ne!(verify_message(&signature, message, 3).ok(), Some(wallet.address()));
+        assert_ne!(verify_message(&signature, b"world", 1).ok(), Some(wallet.address()));
+
+        let hash = eip191_hash_message(message);
+        assert_eq!(verify_hash(&signature, &hash, 1).unwrap(), wallet.address());
+    }
+
+    #[tokio::test]
+    async fn signed_message() {
+        let wallet = LocalWallet::random(1);
+        let other = LocalWallet::random(1);
+
+        let signed = SignedMessage::sign(&wallet, &b"login: 42"[..]).await.unwrap();
+        assert_eq!(signed, SignedMessage::sign_sync(&wallet, &b"login: 42"[..]).unwrap());
+        assert_eq!(signed.recover().unwrap(), wallet.address());
+        signed.verify(wallet.address()).unwrap();
+        assert_matches!(
+            signed.verify(other.address()),
+            Err(Error::SignerMismatch { expected, recovered })
+                if expected == other.address() && recovered == wallet.address()
+        );
+
+        let json = serde_json::to_value(&signed).unwrap();
+        assert_eq!(json["networkId"], 1);
+        assert_eq!(json["message"], "0x6c6f67696e3a203432");
+        let decoded: SignedMessage = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, signed);
+
+        let tampered = SignedMessage { message: Bytes::from_static(b"login: 43"), ..signed };
+        assert!(tampered.verify(wallet.address()).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "eip712")]
+    fn verify_typed_data_signatures() {
+        base_ylm_types::ylm! {
+            #[derive(Default, serde::Serialize)]
+            struct Eip712Data {
+                uint64 a;
+            }
+        }
+
+        let wallet = LocalWallet::random(1);
+        let payload = Eip712Data { a: 42 };
+        let domain = Eip712Domain::default();
+
+        let signature = wallet.sign_typed_data_sync(&payload, &domain).unwrap();
+        assert_eq!(verify_typed_data(&signature, &payload, &domain, 1).unwrap(), wallet.address());
+
+        let dynamic = TypedData::from_struct(&payload, Some(domain));
+        let signature = wallet.sign_dynamic_typed_data_sync(&dynamic).unwrap();
+        assert_eq!(verify_dynamic_typed_data(&signature, &dynamic, 1).unwrap(), wallet.address());
+    }
+}
@@ -1,7 +1,7 @@
 //! Mock IPC server.
 
 use atoms_json_rpc::Response;
-use interprocess::local_socket::tokio::prelude::*;
+use interprocess::local_socket::{tokio::prelude::*, ListenerOptions};
 use serde::Serialize;
 use std::{collections::VecDeque, path::PathBuf};
 use tempfile::NamedTempFile;
@@ -24,7 +24,9 @@ use tokio::io::{AsyncReadExt, AsyncWriteExt};
 /// // byte vector, or `add_response` to add a json-rpc response.
 /// server.add_reply("hello");
 /// // Run the server. The first request will get "hello" as a response.
-/// MockIpcServer::new().spawn();
+/// # tokio_test::block_on(async {
+/// server.spawn().await;
+/// # });
 /// # Ok(())
 /// # }
 /// ```
@@ -70,19 +72,34 @@ impl MockIpcServer {
     }
 
     /// Run the server.
-    pub async fn spawn(mut self) {
+    ///
+    /// The socket is bound before returning, so clients can connect as soon as this resolves.
+    /// Each request read from the first connection is answered with the next reply.
+    pub async fn spawn(self) {
+        let Self { mut replies, path } = self;
+        let tmp = path.into_temp_path();
+        // the socket is bound at the path of the temporary file, which must not exist
+        std::fs::remove_file(&tmp).unwrap();
+        let name = crate::connect::to_name(tmp.as_os_str()).unwrap();
+        let listener = ListenerOptions::new().name(name).create_tokio().unwrap();
+
         tokio::spawn(async move {
-            let tmp = self.path.into_temp_path();
-            let name = crate::connect::to_name(tmp.as_os_str()).unwrap();
-            let socket = LocalSocketStream::connect(name).await.unwrap();
+            // removes the socket file once the server is done
+            let _tmp = tmp;
+            let socket = listener.accept().await.unwrap();
 
             let (mut reader, mut writer) = socket.split();
 
             let mut buf = [0u8; 4096];
             loop {
-                let _ = reader.read(&mut buf).await.unwrap();
-                let reply = self.replies.pop_front().unwrap_or_default();
-                writer.write_all(&reply).await.unwrap();
+                match reader.read(&mut buf).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {}
+                }
+                let reply = replies.pop_front().unwrap_or_default();
+                if writer.write_all(&reply).await.is_err() {
+                    break;
+                }
             }
         });
     }
@@ -6,6 +6,8 @@ no_std_packages=(
     atoms-genesis
     atoms-serde
     atoms-consensus
+    atoms-signer
+    atoms-signer-wallet
 )
 
 for package in "${no_std_packages[@]}"; do